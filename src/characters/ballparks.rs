@@ -1,15 +1,20 @@
 use std::fs;
 
-use text_colorizer::Colorize;
+use crate::core::{
+    file_locations::{
+        data_format, deserialize_data, parse_number, serialize_data, split_fields, split_list,
+        DataFormat, ParseError,
+    },
+    generate_id, roll,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::core::roll;
-
-use super::teams::{generate_location, Era, Location};
+use super::teams::{generate_location, parse_location, Era, Location};
 
 /*==========================================
 ENUM DEFINITIONS
 ==========================================*/
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StadiumTypeModern {
     JewelBox,
     BaseballPalace,
@@ -19,6 +24,7 @@ pub enum StadiumTypeModern {
     None,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StadiumTypeAncient {
     WoodFramePavilion,
     JewelBox,
@@ -26,7 +32,7 @@ pub enum StadiumTypeAncient {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Turf {
     Ragged,
     Good,
@@ -34,7 +40,7 @@ pub enum Turf {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Roof {
     No,
     Permanent,
@@ -42,7 +48,7 @@ pub enum Roof {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Condition {
     FallingApart,
     Decrepit,
@@ -51,7 +57,7 @@ pub enum Condition {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Quirks {
     CozyOutfield,
     ExpansiveOutfield,
@@ -68,11 +74,19 @@ pub enum Quirks {
     None,
 }
 
+/// a ballpark from either era
+#[derive(Clone)]
+pub enum Ballpark {
+    Modern(BallparkModern),
+    Ancient(BallparkAncient),
+}
+
 /*==========================================
 STRUCTURES
 ==========================================*/
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BallparkModern {
+    pub id: String,
     pub name: String,
     pub location: Location,
     pub park_type: StadiumTypeModern,
@@ -83,7 +97,9 @@ pub struct BallparkModern {
     pub quirks: Vec<Quirks>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BallparkAncient {
+    pub id: String,
     pub name: String,
     pub location: Location,
     pub park_type: StadiumTypeAncient,
@@ -92,13 +108,61 @@ pub struct BallparkAncient {
     pub quirks: Vec<Quirks>,
 }
 
+/*==========================================
+IMPL BLOCKS
+==========================================*/
+impl Ballpark {
+    pub fn id(&self) -> &str {
+        match self {
+            Ballpark::Modern(park) => &park.id,
+            Ballpark::Ancient(park) => &park.id,
+        }
+    }
+
+    pub fn set_id(&mut self, id: String) {
+        match self {
+            Ballpark::Modern(park) => park.id = id,
+            Ballpark::Ancient(park) => park.id = id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Ballpark::Modern(park) => &park.name,
+            Ballpark::Ancient(park) => &park.name,
+        }
+    }
+
+    pub fn quirks_mut(&mut self) -> &mut Vec<Quirks> {
+        match self {
+            Ballpark::Modern(park) => &mut park.quirks,
+            Ballpark::Ancient(park) => &mut park.quirks,
+        }
+    }
+
+    /// writes the park with its era's writer
+    pub fn write(&self, filename: &str) -> Result<(), std::io::Error> {
+        match self {
+            Ballpark::Modern(park) => write_ballpark_modern(park, filename),
+            Ballpark::Ancient(park) => write_ballpark_ancient(park, filename),
+        }
+    }
+}
+
 /*==========================================
 FUNCTIONS
 ==========================================*/
 
-/// load modern park baseball file
-pub fn load_park_modern(contents: String) -> BallparkModern {
+/// load modern park baseball file, [file] is only used for error messages
+pub fn load_park_modern(contents: String, file: &str) -> Result<BallparkModern, ParseError> {
+    // JSON/TOML files go through serde, everything else is the KEY: value format
+    let format = data_format(file);
+    if format != DataFormat::Legacy {
+        return deserialize_data(&contents, file, format);
+    }
+
     // initialize fields
+    let mut id = String::new();
     let mut name = String::new();
     let mut location = Location::None;
     let mut park_type = StadiumTypeModern::None;
@@ -106,103 +170,56 @@ pub fn load_park_modern(contents: String) -> BallparkModern {
     let mut turf = Turf::None;
     let mut roof = Roof::None;
     let mut condition = Condition::None;
-    let mut quirks = vec![Quirks::None];
-
-    let rows: Vec<&str> = contents.split('\n').collect();
-    for row in rows.iter().take(rows.len() - 1) {
-        // last line is usually just a new line character
-        let rowline: Vec<&str> = row.split(':').collect();
-        if rowline[0].trim().eq("NAME") {
-            name = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("LOCATION") {
-            if rowline[1].trim().eq("Middle Of Nowhere") {
-                location = Location::MiddleOfNowhere;
-            } else if rowline[1].trim().eq("Small Town") {
-                location = Location::SmallTown;
-            } else if rowline[1].trim().eq("Small City") {
-                location = Location::SmallCity;
-            } else if rowline[1].trim().eq("Medium Sized City") {
-                location = Location::MediumSizedCity;
-            } else if rowline[1].trim().eq("Metropolis") {
-                location = Location::Metropolis;
-            }
-        } else if rowline[0].trim().eq("TYPE") {
-            if rowline[1].trim().eq("Jewel Box") {
-                park_type = StadiumTypeModern::JewelBox;
-            } else if rowline[1].trim().eq("Baseball Palace") {
-                park_type = StadiumTypeModern::BaseballPalace;
-            } else if rowline[1].trim().eq("Space Age") {
-                park_type = StadiumTypeModern::SpaceAge;
-            } else if rowline[1].trim().eq("Concrete Donut") {
-                park_type = StadiumTypeModern::ConcreteDonut;
-            } else if rowline[1].trim().eq("Retro") {
-                park_type = StadiumTypeModern::Retro;
-            }
-        } else if rowline[0].trim().eq("CAPACITY") {
-            let capacity_result = rowline[1].trim().parse();
-            match capacity_result {
-                Ok(cap) => capacity = cap,
-                Err(_err) => println!("{}", "Failed to convert 'capacity' number.".red().bold()),
-            }
-        } else if rowline[0].trim().eq("TURF") {
-            if rowline[1].trim().eq("Good") {
-                turf = Turf::Good;
-            } else if rowline[1].trim().eq("Ragged") {
-                turf = Turf::Ragged;
-            } else if rowline[1].trim().eq("Artificial") {
-                turf = Turf::Artificial;
+    let mut quirks = Vec::new();
+
+    for (line, field, value) in split_fields(&contents, file)? {
+        match field {
+            "ID" => id = value.to_string(),
+            "NAME" => name = value.to_string(),
+            "LOCATION" => location = parse_location(file, line, field, value)?,
+            "TYPE" => {
+                park_type = match value.to_lowercase().as_str() {
+                    "jewel box" => StadiumTypeModern::JewelBox,
+                    "baseball palace" => StadiumTypeModern::BaseballPalace,
+                    "space age" => StadiumTypeModern::SpaceAge,
+                    // older versions of write_ballpark_modern() had a typo
+                    "concrete donut" | "concrete donot" => StadiumTypeModern::ConcreteDonut,
+                    "retro" => StadiumTypeModern::Retro,
+                    "none" => StadiumTypeModern::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("ROOF") {
-            if rowline[1].trim().eq("No Roof") {
-                roof = Roof::No;
-            } else if rowline[1].trim().eq("Permanent Roof") {
-                roof = Roof::Permanent;
-            } else if rowline[1].trim().eq("Retractable Roof") {
-                roof = Roof::Retractable;
+            "CAPACITY" => capacity = parse_number(file, line, field, value)?,
+            "TURF" => {
+                turf = match value.to_lowercase().as_str() {
+                    "good" => Turf::Good,
+                    "ragged" => Turf::Ragged,
+                    "artificial" => Turf::Artificial,
+                    "none" => Turf::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("CONDITION") {
-            if rowline[1].trim().eq("Well Worn") {
-                condition = Condition::WellWorn;
-            } else if rowline[1].trim().eq("Decrepit") {
-                condition = Condition::Decrepit;
-            } else if rowline[1].trim().eq("Sparkling") {
-                condition = Condition::Sparkling;
-            } else if rowline[1].trim().eq("Falling Apart") {
-                condition = Condition::FallingApart;
+            "ROOF" => {
+                roof = match value.to_lowercase().as_str() {
+                    "no roof" => Roof::No,
+                    "permanent roof" => Roof::Permanent,
+                    "retractable roof" => Roof::Retractable,
+                    "none" => Roof::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("QUIRKS") {
-            let quirk_strings: Vec<&str> = rowline[1].split(',').collect();
-            for quirk_string in quirk_strings.iter() {
-                if quirk_string.trim().eq("Cozy Outfield") {
-                    quirks.push(Quirks::CozyOutfield);
-                } else if quirk_string.trim().eq("Expansive Outfield") {
-                    quirks.push(Quirks::ExpansiveOutfield);
-                } else if quirk_string.trim().eq("Short Left") {
-                    quirks.push(Quirks::ShortLeft);
-                } else if quirk_string.trim().eq("Short Right") {
-                    quirks.push(Quirks::ShortRight);
-                } else if quirk_string.trim().eq("Odd Left") {
-                    quirks.push(Quirks::OddLeft);
-                } else if quirk_string.trim().eq("Odd Center") {
-                    quirks.push(Quirks::OddCenter);
-                } else if quirk_string.trim().eq("Odd Right") {
-                    quirks.push(Quirks::OddRight);
-                } else if quirk_string.trim().eq("Fast Infield") {
-                    quirks.push(Quirks::FastInfield);
-                } else if quirk_string.trim().eq("Slow Infield") {
-                    quirks.push(Quirks::SlowInfield);
-                } else if quirk_string.trim().eq("High Mound") {
-                    quirks.push(Quirks::HighMound);
-                } else if quirk_string.trim().eq("Beautiful") {
-                    quirks.push(Quirks::Beautiful);
-                } else if quirk_string.trim().eq("Hideous") {
-                    quirks.push(Quirks::Hideous);
+            "CONDITION" => condition = parse_condition(file, line, field, value)?,
+            "QUIRKS" => {
+                for entry in split_list(value) {
+                    quirks.push(parse_quirk(file, line, field, entry)?);
                 }
             }
+            _ => return Err(ParseError::new(file, line, field, value, "unknown field")),
         }
     }
 
-    BallparkModern {
+    Ok(BallparkModern {
+        id,
         name,
         location,
         park_type,
@@ -211,13 +228,20 @@ pub fn load_park_modern(contents: String) -> BallparkModern {
         roof,
         condition,
         quirks,
-    }
+    })
 }
 
-/// writes modern park struct to text file
+/// writes modern park struct to text file (JSON/TOML if [filename] ends in .json/.toml)
 pub fn write_ballpark_modern(data: &BallparkModern, filename: &str) -> Result<(), std::io::Error> {
+    let format = data_format(filename);
+    if format != DataFormat::Legacy {
+        return serialize_data(data, filename, format);
+    }
+
     let mut file_text = String::new();
-    file_text.push_str("NAME: ");
+    file_text.push_str("ID: ");
+    file_text.push_str(&data.id);
+    file_text.push_str("\nNAME: ");
     file_text.push_str(&data.name);
     file_text.push_str("\nLOCATION: ");
     match data.location {
@@ -235,7 +259,7 @@ pub fn write_ballpark_modern(data: &BallparkModern, filename: &str) -> Result<()
         StadiumTypeModern::Retro => file_text.push_str("Retro"),
         StadiumTypeModern::JewelBox => file_text.push_str("Jewel Box"),
         StadiumTypeModern::SpaceAge => file_text.push_str("Space Age"),
-        StadiumTypeModern::ConcreteDonut => file_text.push_str("Concrete Donot"),
+        StadiumTypeModern::ConcreteDonut => file_text.push_str("Concrete Donut"),
     }
     file_text.push_str("\nCAPACITY: ");
     file_text.push_str(&data.capacity.to_string());
@@ -283,106 +307,110 @@ pub fn write_ballpark_modern(data: &BallparkModern, filename: &str) -> Result<()
     fs::write(filename, &file_text)
 }
 
-/// loads ancient era park from text file to struct
-pub fn load_park_ancient(contents: String) -> BallparkAncient {
+/// loads ancient era park from text file to struct, [file] is only used for error messages
+pub fn load_park_ancient(contents: String, file: &str) -> Result<BallparkAncient, ParseError> {
+    // JSON/TOML files go through serde, everything else is the KEY: value format
+    let format = data_format(file);
+    if format != DataFormat::Legacy {
+        return deserialize_data(&contents, file, format);
+    }
+
     // initialize fields
+    let mut id = String::new();
     let mut name = String::new();
     let mut location = Location::None;
     let mut park_type = StadiumTypeAncient::None;
     let mut capacity: i32 = 0;
     let mut condition = Condition::None;
-    let mut quirks = vec![Quirks::None];
-
-    let rows: Vec<&str> = contents.split('\n').collect();
-    for row in rows.iter().take(rows.len() - 1) {
-        let rowline: Vec<&str> = row.split(':').collect();
-        if rowline[0].trim().eq("NAME") {
-            name = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("LOCATION") {
-            if rowline[1].trim().eq("Middle Of Nowhere") {
-                location = Location::MiddleOfNowhere;
-            } else if rowline[1].trim().eq("Small Town") {
-                location = Location::SmallTown;
-            } else if rowline[1].trim().eq("Small City") {
-                location = Location::SmallCity;
-            } else if rowline[1].trim().eq("Medium Sized City") {
-                location = Location::MediumSizedCity;
-            } else if rowline[1].trim().eq("Metropolis") {
-                location = Location::Metropolis;
-            }
-        } else if rowline[0].trim().eq("TYPE") {
-            if rowline[1].trim().eq("Jewel Box") {
-                park_type = StadiumTypeAncient::JewelBox;
-            } else if rowline[1].trim().eq("Baseball Palace") {
-                park_type = StadiumTypeAncient::BaseballPalace;
-            } else if rowline[1].trim().eq("Wood Frame Pavilion") {
-                park_type = StadiumTypeAncient::WoodFramePavilion;
-            }
-        } else if rowline[0].trim().eq("CAPACITY") {
-            let capacity_result = rowline[1].trim().parse();
-            match capacity_result {
-                Ok(cap) => capacity = cap,
-                Err(_err) => println!("{}", "Failed to convert 'capacity' number.".red().bold()),
-            }
-        } else if rowline[0].trim().eq("CONDITION") {
-            if rowline[1].trim().eq("Well Worn") {
-                condition = Condition::WellWorn;
-            } else if rowline[1].trim().eq("Decrepit") {
-                condition = Condition::Decrepit;
-            } else if rowline[1].trim().eq("Sparkling") {
-                condition = Condition::Sparkling;
-            } else if rowline[1].trim().eq("Falling Apart") {
-                condition = Condition::FallingApart;
+    let mut quirks = Vec::new();
+
+    for (line, field, value) in split_fields(&contents, file)? {
+        match field {
+            "ID" => id = value.to_string(),
+            "NAME" => name = value.to_string(),
+            "LOCATION" => location = parse_location(file, line, field, value)?,
+            "TYPE" => {
+                park_type = match value.to_lowercase().as_str() {
+                    "jewel box" => StadiumTypeAncient::JewelBox,
+                    "baseball palace" => StadiumTypeAncient::BaseballPalace,
+                    "wood frame pavilion" => StadiumTypeAncient::WoodFramePavilion,
+                    "none" => StadiumTypeAncient::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("QUIRKS") {
-            let quirk_strings: Vec<&str> = rowline[1].split(',').collect();
-            for quirk_string in quirk_strings.iter() {
-                if quirk_string.trim().eq("Cozy Outfield") {
-                    quirks.push(Quirks::CozyOutfield);
-                } else if quirk_string.trim().eq("Expansive Outfield") {
-                    quirks.push(Quirks::ExpansiveOutfield);
-                } else if quirk_string.trim().eq("Short Left") {
-                    quirks.push(Quirks::ShortLeft);
-                } else if quirk_string.trim().eq("Short Right") {
-                    quirks.push(Quirks::ShortRight);
-                } else if quirk_string.trim().eq("Odd Left") {
-                    quirks.push(Quirks::OddLeft);
-                } else if quirk_string.trim().eq("Odd Center") {
-                    quirks.push(Quirks::OddCenter);
-                } else if quirk_string.trim().eq("Odd Right") {
-                    quirks.push(Quirks::OddRight);
-                } else if quirk_string.trim().eq("Fast Infield") {
-                    quirks.push(Quirks::FastInfield);
-                } else if quirk_string.trim().eq("Slow Infield") {
-                    quirks.push(Quirks::SlowInfield);
-                } else if quirk_string.trim().eq("High Mound") {
-                    quirks.push(Quirks::HighMound);
-                } else if quirk_string.trim().eq("Beautiful") {
-                    quirks.push(Quirks::Beautiful);
-                } else if quirk_string.trim().eq("Hideous") {
-                    quirks.push(Quirks::Hideous);
+            "CAPACITY" => capacity = parse_number(file, line, field, value)?,
+            "CONDITION" => condition = parse_condition(file, line, field, value)?,
+            "QUIRKS" => {
+                for entry in split_list(value) {
+                    quirks.push(parse_quirk(file, line, field, entry)?);
                 }
             }
+            _ => return Err(ParseError::new(file, line, field, value, "unknown field")),
         }
     }
 
-    BallparkAncient {
+    Ok(BallparkAncient {
+        id,
         name,
         location,
         park_type,
         capacity,
         condition,
         quirks,
+    })
+}
+
+/// condition values shared by both eras
+fn parse_condition(
+    file: &str,
+    line: usize,
+    field: &str,
+    value: &str,
+) -> Result<Condition, ParseError> {
+    match value.to_lowercase().as_str() {
+        "falling apart" => Ok(Condition::FallingApart),
+        "decrepit" => Ok(Condition::Decrepit),
+        "well worn" => Ok(Condition::WellWorn),
+        "sparkling" => Ok(Condition::Sparkling),
+        "none" => Ok(Condition::None),
+        _ => Err(ParseError::bad_value(file, line, field, value)),
+    }
+}
+
+/// quirk values shared by both eras
+fn parse_quirk(file: &str, line: usize, field: &str, value: &str) -> Result<Quirks, ParseError> {
+    match value.to_lowercase().as_str() {
+        "cozy outfield" => Ok(Quirks::CozyOutfield),
+        "expansive outfield" => Ok(Quirks::ExpansiveOutfield),
+        "short left" => Ok(Quirks::ShortLeft),
+        "short right" => Ok(Quirks::ShortRight),
+        "odd left" => Ok(Quirks::OddLeft),
+        "odd center" => Ok(Quirks::OddCenter),
+        "odd right" => Ok(Quirks::OddRight),
+        "fast infield" => Ok(Quirks::FastInfield),
+        "slow infield" => Ok(Quirks::SlowInfield),
+        "high mound" => Ok(Quirks::HighMound),
+        "beautiful" => Ok(Quirks::Beautiful),
+        "hideous" => Ok(Quirks::Hideous),
+        "none" => Ok(Quirks::None),
+        _ => Err(ParseError::bad_value(file, line, field, value)),
     }
 }
 
-/// writes ancient era ballpark struct to text file
+/// writes ancient era ballpark struct to text file (JSON/TOML if [filename] ends in .json/.toml)
 pub fn write_ballpark_ancient(
     data: &BallparkAncient,
     filename: &str,
 ) -> Result<(), std::io::Error> {
+    let format = data_format(filename);
+    if format != DataFormat::Legacy {
+        return serialize_data(data, filename, format);
+    }
+
     let mut file_text = String::new();
-    file_text.push_str("NAME: ");
+    file_text.push_str("ID: ");
+    file_text.push_str(&data.id);
+    file_text.push_str("\nNAME: ");
     file_text.push_str(&data.name);
     file_text.push_str("\nLOCATION: ");
     match data.location {
@@ -535,6 +563,36 @@ pub fn generate_roof() -> Roof {
     roof
 }
 
+/// rolls one quirk on the quirk table
+pub fn roll_quirk() -> Quirks {
+    let result = roll(20);
+    if result <= 3 {
+        Quirks::CozyOutfield
+    } else if (4..=6).contains(&result) {
+        Quirks::ExpansiveOutfield
+    } else if result == 7 {
+        Quirks::ShortLeft
+    } else if result == 8 {
+        Quirks::ShortRight
+    } else if result == 9 {
+        Quirks::OddLeft
+    } else if result == 10 {
+        Quirks::OddCenter
+    } else if result == 11 {
+        Quirks::OddRight
+    } else if result == 12 {
+        Quirks::FastInfield
+    } else if result == 13 {
+        Quirks::SlowInfield
+    } else if result == 14 {
+        Quirks::HighMound
+    } else if (15..=17).contains(&result) {
+        Quirks::Beautiful
+    } else {
+        Quirks::Hideous
+    }
+}
+
 /// generate ballpark quirks functions
 // TODO: make quirks impact play
 pub fn generate_quirks(quirk_num: i32) -> Vec<Quirks> {
@@ -543,32 +601,7 @@ pub fn generate_quirks(quirk_num: i32) -> Vec<Quirks> {
         quirks.push(Quirks::None);
     } else {
         for _i in 0..quirk_num {
-            let result = roll(20);
-            if result <= 3 {
-                quirks.push(Quirks::CozyOutfield);
-            } else if (4..=6).contains(&result) {
-                quirks.push(Quirks::ExpansiveOutfield);
-            } else if result == 7 {
-                quirks.push(Quirks::ShortLeft);
-            } else if result == 8 {
-                quirks.push(Quirks::ShortRight);
-            } else if result == 9 {
-                quirks.push(Quirks::OddLeft);
-            } else if result == 10 {
-                quirks.push(Quirks::OddCenter);
-            } else if result == 11 {
-                quirks.push(Quirks::OddRight);
-            } else if result == 12 {
-                quirks.push(Quirks::FastInfield);
-            } else if result == 13 {
-                quirks.push(Quirks::SlowInfield);
-            } else if result == 14 {
-                quirks.push(Quirks::HighMound);
-            } else if (15..=17).contains(&result) {
-                quirks.push(Quirks::Beautiful);
-            } else {
-                quirks.push(Quirks::Hideous);
-            }
+            quirks.push(roll_quirk());
         }
     }
 
@@ -604,8 +637,10 @@ pub fn generate_ancient_ballpark(name1: &[String], name2: &[String]) -> Ballpark
     // quirk roll - match stadium type for number of rolls
 
     // build struct
+    let name = generate_ballpark_name(name1, name2);
     BallparkAncient {
-        name: generate_ballpark_name(name1, name2),
+        id: generate_id(&name),
+        name,
         location: generate_location(),
         park_type,
         capacity,
@@ -666,8 +701,10 @@ pub fn generate_modern_ballpark(name1: &[String], name2: &[String]) -> BallparkM
     // quirk roll - match stadium type for number of rolls
 
     // build struct
+    let name = generate_ballpark_name(name1, name2);
     BallparkModern {
-        name: generate_ballpark_name(name1, name2),
+        id: generate_id(&name),
+        name,
         location: generate_location(),
         park_type: generate_modern_park_type(),
         capacity,
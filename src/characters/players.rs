@@ -2,19 +2,44 @@
 MODULE INCLUSIONS
 ========================================================*/
 use std::fs; // needed to read in files
+use std::path::Path;
 
-use text_colorizer::*;
-
+use serde::{Deserialize, Serialize};
 //use super::teams::Era;
 use crate::core::{
+    bo_wrap,
+    file_locations::{
+        data_format, deserialize_data, id_from_path, parse_number, resolve_reference,
+        serialize_data, split_fields, split_list, DataFormat, ParseError, PLAYER_FOLDER,
+    },
     game_functions::{find_by_position, GameModern, GameState, InningTB},
-    roll,
+    generate_id, roll,
 };
 
+/*========================================================
+CONSTANTS
+========================================================*/
+/// every pitch die, in the order change_pitch_die() steps through them
+pub const PITCH_DICE: [i32; 8] = [-20, -12, -8, -4, 4, 8, 12, 20];
+
+/// traits that can't go on the same player, at most one from each group
+const TRAIT_CONFLICTS: [&[Traits]; 5] = [
+    &[
+        Traits::ElitePowerHitter,
+        Traits::PowerHitter,
+        Traits::WeakHitter,
+        Traits::ExtraWeakHitter,
+    ],
+    &[Traits::ContactHitter, Traits::FreeSwinger],
+    &[Traits::SpeedyRunner, Traits::SlowRunner],
+    &[Traits::GreatDefender, Traits::PoorDefender],
+    &[Traits::ControlPitcher, Traits::Wild],
+];
+
 /*========================================================
 ENUM DEFINITIONS
 ========================================================*/
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Position {
     Pitcher,
     Catcher,
@@ -29,7 +54,7 @@ pub enum Position {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Handedness {
     Right,
     Left,
@@ -37,7 +62,7 @@ pub enum Handedness {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Traits {
     // hitter traits
     PowerHitter,
@@ -62,7 +87,7 @@ pub enum Traits {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InjuryLocation {
     Head,
     Shoulder,
@@ -80,7 +105,7 @@ pub enum InjuryLocation {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InjurySeverity {
     Catastrophic,
     Major,
@@ -100,8 +125,9 @@ pub enum PlayerClass {
 /*========================================================
 STRUCT DEFINITIONS
 ========================================================*/
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Player {
+    pub id: String, // stable ID, also the file name in the league players folder
     pub first_name: String,
     pub last_name: String,
     pub nickname: String,
@@ -118,6 +144,7 @@ pub struct Player {
 impl Default for Player {
     fn default() -> Self {
         Self {
+            id: "Seth_Loveall".to_string(),
             first_name: "Seth".to_string(),
             last_name: "Loveall".to_string(),
             nickname: "White Lightning".to_string(),
@@ -133,6 +160,52 @@ impl Default for Player {
     }
 }
 
+impl Traits {
+    pub const HITTER: [Traits; 11] = [
+        Traits::PowerHitter,
+        Traits::ElitePowerHitter,
+        Traits::ContactHitter,
+        Traits::SpeedyRunner,
+        Traits::GreatDefender,
+        Traits::ToughPlayer,
+        Traits::WeakHitter,
+        Traits::ExtraWeakHitter,
+        Traits::FreeSwinger,
+        Traits::SlowRunner,
+        Traits::PoorDefender,
+    ];
+    pub const PITCHER: [Traits; 5] = [
+        Traits::StrikeoutArtist,
+        Traits::GroundballMachine,
+        Traits::ControlPitcher,
+        Traits::GreatStamina,
+        Traits::Wild,
+    ];
+
+    /// the short code used on the player cards in the rulebook
+    pub fn code(&self) -> &'static str {
+        match self {
+            Traits::PowerHitter => "P+",
+            Traits::ElitePowerHitter => "P++",
+            Traits::ContactHitter => "C+",
+            Traits::SpeedyRunner => "S+",
+            Traits::GreatDefender => "D+",
+            Traits::ToughPlayer => "T+",
+            Traits::WeakHitter => "P-",
+            Traits::ExtraWeakHitter => "P--",
+            Traits::FreeSwinger => "C-",
+            Traits::SlowRunner => "S-",
+            Traits::PoorDefender => "D-",
+            Traits::StrikeoutArtist => "K+",
+            Traits::GroundballMachine => "GB+",
+            Traits::ControlPitcher => "CN+",
+            Traits::GreatStamina => "ST+",
+            Traits::Wild => "CN-",
+            Traits::None => "None",
+        }
+    }
+}
+
 impl Player {
     // BATTER TRAITS
     /// returns player specific modifier for defense rolls (D+/D-)
@@ -271,9 +344,16 @@ impl Player {
 FUNCTION DEFINITIONS
 ========================================================*/
 
-// loads a *.DBP file and converts to Player struct
-pub fn load_player(contents: String) -> Player {
+// loads a *.DBP file and converts to Player struct, [file] is only used for error messages
+pub fn load_player(contents: String, file: &str) -> Result<Player, ParseError> {
+    // JSON/TOML files go through serde, everything else is the KEY: value format
+    let format = data_format(file);
+    if format != DataFormat::Legacy {
+        return deserialize_data(&contents, file, format);
+    }
+
     // initialize player data
+    let mut read_id = String::new();
     let mut read_first_name = String::new();
     let mut read_last_name = String::new();
     let mut read_nickname = String::new();
@@ -282,164 +362,110 @@ pub fn load_player(contents: String) -> Player {
     let mut read_batter_target: i32 = 0;
     let mut read_on_base_target: i32 = 0;
     let mut read_pitch_die: i32 = 0;
-    let mut read_traits = vec![Traits::None];
-    let mut read_injury_location = vec![InjuryLocation::None];
-    let mut read_injury_severity = vec![InjurySeverity::Uninjured];
+    let mut read_traits = Vec::new();
+    let mut read_injury_location = Vec::new();
+    let mut read_injury_severity = Vec::new();
 
     // sort data into player struct
-    let stats: Vec<&str> = contents.split('\n').collect();
-    for stat in stats.iter().take(stats.len() - 1) {
-        // last line is usually just a new line character
-        let statline: Vec<&str> = stat.split(':').collect();
-        if statline[0].trim().eq("First Name") {
-            read_first_name = statline[1].trim().to_string();
-        } else if statline[0].trim().eq("Last Name") {
-            read_last_name = statline[1].trim().to_string();
-        } else if statline[0].trim().eq("Nickname") {
-            read_nickname = statline[1].trim().to_string();
-        } else if statline[0].trim().eq("Position") {
-            let pos_str = statline[1];
-            if pos_str.trim().eq("C") {
-                read_position = Position::Catcher;
-            } else if pos_str.trim().eq("1B") {
-                read_position = Position::Firstbase;
-            } else if pos_str.trim().eq("2B") {
-                read_position = Position::Secondbase;
-            } else if pos_str.trim().eq("SS") {
-                read_position = Position::Shortstop;
-            } else if pos_str.trim().eq("3B") {
-                read_position = Position::Thirdbase;
-            } else if pos_str.trim().eq("LF") {
-                read_position = Position::Leftfield;
-            } else if pos_str.trim().eq("CF") {
-                read_position = Position::Centerfield;
-            } else if pos_str.trim().eq("RF") {
-                read_position = Position::Rightfield;
-            } else if pos_str.trim().eq("Bench") {
-                read_position = Position::Bench;
-            }
-        } else if statline[0].trim().eq("Handedness") {
-            let hand_str = statline[1];
-            if hand_str.trim().eq("R") {
-                read_handedness = Handedness::Right;
-            } else if hand_str.trim().eq("L") {
-                read_handedness = Handedness::Left;
-            } else if hand_str.trim().eq("S") {
-                read_handedness = Handedness::Switch;
-            }
-        } else if statline[0].trim().eq("Batter Target") {
-            let bt_result = statline[1].trim().parse();
-            match bt_result {
-                Ok(bt) => read_batter_target = bt,
-                Err(_err) => println!("{}", "Failed to convert batter target number.".red().bold()),
-            }
-        } else if statline[0].trim().eq("On Base Target") {
-            let obt_result = statline[1].trim().parse();
-            match obt_result {
-                Ok(obt) => read_on_base_target = obt,
-                Err(_) => println!(
-                    "{}",
-                    "Failed to convert on base target number.".red().bold()
-                ),
+    for (line, field, value) in split_fields(&contents, file)? {
+        match field {
+            "ID" => read_id = value.to_string(),
+            "First Name" => read_first_name = value.to_string(),
+            "Last Name" => read_last_name = value.to_string(),
+            "Nickname" => read_nickname = value.to_string(),
+            "Position" => {
+                read_position = match value.to_uppercase().as_str() {
+                    "P" => Position::Pitcher,
+                    "C" => Position::Catcher,
+                    "1B" => Position::Firstbase,
+                    "2B" => Position::Secondbase,
+                    "SS" => Position::Shortstop,
+                    "3B" => Position::Thirdbase,
+                    "LF" => Position::Leftfield,
+                    "CF" => Position::Centerfield,
+                    "RF" => Position::Rightfield,
+                    "BENCH" => Position::Bench,
+                    "NONE" => Position::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if statline[0].trim().eq("Pitch Die") {
-            let pd_result = statline[1].trim().parse();
-            match pd_result {
-                Ok(pd) => read_pitch_die = pd,
-                Err(_) => println!(
-                    "{}",
-                    "Failed to convert pitch die: leave out the 'd'."
-                        .red()
-                        .bold()
-                ),
+            "Handedness" => {
+                read_handedness = match value.to_uppercase().as_str() {
+                    "R" => Handedness::Right,
+                    "L" => Handedness::Left,
+                    "S" => Handedness::Switch,
+                    "NONE" => Handedness::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if statline[0].trim().eq("Traits") {
-            let trait_string: Vec<&str> = statline[1].split(',').collect();
-            for traits in trait_string.iter() {
-                if traits.trim().eq("P+") {
-                    read_traits.push(Traits::PowerHitter);
-                } else if traits.trim().eq("P++") {
-                    read_traits.push(Traits::ElitePowerHitter);
-                } else if traits.trim().eq("C+") {
-                    read_traits.push(Traits::ContactHitter);
-                } else if traits.trim().eq("S+") {
-                    read_traits.push(Traits::SpeedyRunner);
-                } else if traits.trim().eq("D+") {
-                    read_traits.push(Traits::GreatDefender);
-                } else if traits.trim().eq("T+") {
-                    read_traits.push(Traits::ToughPlayer);
-                } else if traits.trim().eq("P-") {
-                    read_traits.push(Traits::WeakHitter);
-                } else if traits.trim().eq("P--") {
-                    read_traits.push(Traits::ExtraWeakHitter);
-                } else if traits.trim().eq("C-") {
-                    read_traits.push(Traits::FreeSwinger);
-                } else if traits.trim().eq("S-") {
-                    read_traits.push(Traits::SlowRunner);
-                } else if traits.trim().eq("D-") {
-                    read_traits.push(Traits::PoorDefender);
-                } else if traits.trim().eq("K+") {
-                    read_traits.push(Traits::StrikeoutArtist);
-                } else if traits.trim().eq("GB+") {
-                    read_traits.push(Traits::GroundballMachine);
-                } else if traits.trim().eq("CN+") {
-                    read_traits.push(Traits::ControlPitcher);
-                } else if traits.trim().eq("ST+") {
-                    read_traits.push(Traits::GreatStamina);
-                } else if traits.trim().eq("CN-") {
-                    read_traits.push(Traits::Wild);
+            "Batter Target" => read_batter_target = parse_number(file, line, field, value)?,
+            "On Base Target" => read_on_base_target = parse_number(file, line, field, value)?,
+            // NOTE: pitch die is written without the 'd', e.g. -8 or 12
+            "Pitch Die" => read_pitch_die = parse_number(file, line, field, value)?,
+            "Traits" => {
+                for entry in split_list(value) {
+                    read_traits.push(match entry.to_uppercase().as_str() {
+                        "P+" => Traits::PowerHitter,
+                        "P++" => Traits::ElitePowerHitter,
+                        "C+" => Traits::ContactHitter,
+                        "S+" => Traits::SpeedyRunner,
+                        "D+" => Traits::GreatDefender,
+                        "T+" => Traits::ToughPlayer,
+                        "P-" => Traits::WeakHitter,
+                        "P--" => Traits::ExtraWeakHitter,
+                        "C-" => Traits::FreeSwinger,
+                        "S-" => Traits::SlowRunner,
+                        "D-" => Traits::PoorDefender,
+                        "K+" => Traits::StrikeoutArtist,
+                        "GB+" => Traits::GroundballMachine,
+                        "CN+" => Traits::ControlPitcher,
+                        "ST+" => Traits::GreatStamina,
+                        "CN-" => Traits::Wild,
+                        "NONE" => Traits::None,
+                        _ => return Err(ParseError::bad_value(file, line, field, entry)),
+                    });
                 }
             }
-        } else if statline[0].trim().eq("Injury Location") {
-            let inj_loc_str: Vec<&str> = statline[1].split(',').collect();
-            for inj in inj_loc_str.iter() {
-                if inj.trim().eq("Head") {
-                    read_injury_location.push(InjuryLocation::Head);
-                } else if inj.trim().eq("Shoulder") {
-                    read_injury_location.push(InjuryLocation::Shoulder);
-                } else if inj.trim().eq("Elbow") {
-                    read_injury_location.push(InjuryLocation::Elbow);
-                } else if inj.trim().eq("Forearm") {
-                    read_injury_location.push(InjuryLocation::Forearm);
-                } else if inj.trim().eq("Wrist") {
-                    read_injury_location.push(InjuryLocation::Wrist);
-                } else if inj.trim().eq("Hand") {
-                    read_injury_location.push(InjuryLocation::Hand);
-                } else if inj.trim().eq("Back") {
-                    read_injury_location.push(InjuryLocation::Back);
-                } else if inj.trim().eq("Oblique") {
-                    read_injury_location.push(InjuryLocation::Oblique);
-                } else if inj.trim().eq("Hip") {
-                    read_injury_location.push(InjuryLocation::Hip);
-                } else if inj.trim().eq("Hamstring") {
-                    read_injury_location.push(InjuryLocation::Hamstring)
-                } else if inj.trim().eq("Knee") {
-                    read_injury_location.push(InjuryLocation::Knee);
-                } else if inj.trim().eq("Ankle") {
-                    read_injury_location.push(InjuryLocation::Ankle);
-                } else if inj.trim().eq("Foot") {
-                    read_injury_location.push(InjuryLocation::Foot);
+            "Injury Location" => {
+                for entry in split_list(value) {
+                    read_injury_location.push(match entry.to_lowercase().as_str() {
+                        "head" => InjuryLocation::Head,
+                        "shoulder" => InjuryLocation::Shoulder,
+                        "elbow" => InjuryLocation::Elbow,
+                        "forearm" => InjuryLocation::Forearm,
+                        "wrist" => InjuryLocation::Wrist,
+                        "hand" => InjuryLocation::Hand,
+                        "back" => InjuryLocation::Back,
+                        "oblique" => InjuryLocation::Oblique,
+                        "hip" => InjuryLocation::Hip,
+                        "hamstring" => InjuryLocation::Hamstring,
+                        "knee" => InjuryLocation::Knee,
+                        "ankle" => InjuryLocation::Ankle,
+                        "foot" => InjuryLocation::Foot,
+                        "none" => InjuryLocation::None,
+                        _ => return Err(ParseError::bad_value(file, line, field, entry)),
+                    });
                 }
             }
-        } else if statline[0].trim().eq("Injury Severity") {
-            let inj_sev_str: Vec<&str> = statline[1].split(',').collect();
-            for inj in inj_sev_str.iter() {
-                if inj.trim().eq("Catastrophic") {
-                    read_injury_severity.push(InjurySeverity::Catastrophic);
-                } else if inj.trim().eq("Major") {
-                    read_injury_severity.push(InjurySeverity::Major);
-                } else if inj.trim().eq("Minor") {
-                    read_injury_severity.push(InjurySeverity::Minor);
-                } else if inj.trim().eq("Superficial") {
-                    read_injury_severity.push(InjurySeverity::Superficial);
-                } else if inj.trim().eq("Uninjured") {
-                    read_injury_severity.push(InjurySeverity::Uninjured);
+            "Injury Severity" => {
+                for entry in split_list(value) {
+                    read_injury_severity.push(match entry.to_lowercase().as_str() {
+                        "catastrophic" => InjurySeverity::Catastrophic,
+                        "major" => InjurySeverity::Major,
+                        "minor" => InjurySeverity::Minor,
+                        "superficial" => InjurySeverity::Superficial,
+                        // old sample files used "none"
+                        "uninjured" | "none" => InjurySeverity::Uninjured,
+                        _ => return Err(ParseError::bad_value(file, line, field, entry)),
+                    });
                 }
             }
+            _ => return Err(ParseError::new(file, line, field, value, "unknown field")),
         }
     }
 
-    Player {
+    Ok(Player {
+        id: read_id,
         first_name: read_first_name,
         last_name: read_last_name,
         nickname: read_nickname,
@@ -451,13 +477,20 @@ pub fn load_player(contents: String) -> Player {
         traits: read_traits,
         injury_location: read_injury_location,
         injury_severity: read_injury_severity,
-    }
+    })
 }
 
-/// writes a Player struct to a *.DBP file
+/// writes a Player struct to a *.DBP file (JSON/TOML if [filename] ends in .json/.toml)
 pub fn write_player(data: &Player, filename: &str) -> Result<(), std::io::Error> {
+    let format = data_format(filename);
+    if format != DataFormat::Legacy {
+        return serialize_data(data, filename, format);
+    }
+
     let mut file_text = String::new();
-    file_text.push_str("First Name: ");
+    file_text.push_str("ID: ");
+    file_text.push_str(&data.id);
+    file_text.push_str("\nFirst Name: ");
     file_text.push_str(&data.first_name);
     file_text.push_str("\nLast Name: ");
     file_text.push_str(&data.last_name);
@@ -545,6 +578,20 @@ pub fn write_player(data: &Player, filename: &str) -> Result<(), std::io::Error>
     fs::write(filename, &file_text)
 }
 
+/// loads a player from a team file reference (ID or file path), see resolve_reference()
+/// players written before IDs existed get their file name as an ID
+pub fn load_player_ref(reference: &str, data_dir: &Path) -> Result<Player, ParseError> {
+    let path = resolve_reference(reference, data_dir, PLAYER_FOLDER, "dbp");
+    let file = path.display().to_string();
+    let contents = fs::read_to_string(&path).map_err(|e| ParseError::io(&file, e))?;
+    let mut player = load_player(contents, &file)?;
+    if player.id.is_empty() {
+        player.id = id_from_path(&path);
+    }
+
+    Ok(player)
+}
+
 // NOTE: pretty sure this function has been replaced by the load_csv function - not deleting yet
 // just in case
 /*
@@ -720,6 +767,51 @@ pub fn generate_traits(player_type: &PlayerClass) -> Vec<Traits> {
     traits
 }
 
+/// checks a player card follows the rules - targets in range, a real pitch die, no conflicting
+/// traits and an injury severity for every injury
+pub fn check_player(player: &Player) -> Result<(), Vec<String>> {
+    let mut problems = vec![];
+    if !(1..=99).contains(&player.batter_target) {
+        problems.push(format!(
+            "Batter target {} isn't between 1 and 99.",
+            player.batter_target
+        ));
+    }
+    if player.on_base_target < player.batter_target || player.on_base_target > 99 {
+        problems.push(format!(
+            "On base target {} has to be between the batter target and 99.",
+            player.on_base_target
+        ));
+    }
+    if !PITCH_DICE.contains(&player.pitch_die) {
+        problems.push(format!("{} isn't a pitch die.", player.pitch_die));
+    }
+    for (i, player_trait) in player.traits.iter().enumerate() {
+        if player.traits[..i].contains(player_trait) {
+            problems.push(format!("{} is listed twice.", player_trait.code()));
+        }
+    }
+    for group in TRAIT_CONFLICTS {
+        let found: Vec<&str> = group
+            .iter()
+            .filter(|conflict| player.traits.contains(conflict))
+            .map(|conflict| conflict.code())
+            .collect();
+        if found.len() > 1 {
+            problems.push(format!("{} can't go together.", found.join(" and ")));
+        }
+    }
+    if player.injury_location.len() != player.injury_severity.len() {
+        problems.push("Every injury needs a location and a severity.".to_string());
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
 /// generates a new player in struct format
 pub fn generate_player(
     player_type: PlayerClass,
@@ -732,6 +824,7 @@ pub fn generate_player(
     let (bt, ot) = generate_batter_target(&player_type);
 
     Player {
+        id: generate_id(&format!("{} {}", first_name, last_name)),
         first_name,
         last_name,
         nickname: "".to_string(),
@@ -769,13 +862,16 @@ pub fn def_trait_check(half: &InningTB, game: &GameModern, position: Position) -
 
 /// checks inning half and returns hit roll modifier for appropriate player
 pub fn pow_trait_check(game: &GameModern, state: &GameState) -> i32 {
+    // current batter has already been moved up to the next slot by the time this is called
     let modifier: i32 = match state.inning_half {
         InningTB::Top => {
-            let player = &game.away_active.roster[state.away_state.current_batter as usize];
+            let player =
+                &game.away_active.batting_order[bo_wrap(state.away_state.current_batter, 1, true)];
             player.power()
         }
         InningTB::Bottom => {
-            let player = &game.home_active.roster[state.home_state.current_batter as usize];
+            let player =
+                &game.home_active.batting_order[bo_wrap(state.home_state.current_batter, 1, true)];
             player.power()
         }
     };
@@ -785,7 +881,7 @@ pub fn pow_trait_check(game: &GameModern, state: &GameState) -> i32 {
 
 /// adjust pitch die in set increments
 pub fn change_pitch_die(current: i32, increment: i32) -> i32 {
-    let die_vec = [-20, -12, -8, -4, 4, 8, 12, 20];
+    let die_vec = PITCH_DICE;
     let mut current_pos_res = die_vec.iter().position(|&r| r == current);
     if current_pos_res.is_none() {
         // pitch die is somehow not an increment of 4, round to nearest
@@ -2,13 +2,17 @@
 MODULE INCLUSIONS
 ==========================================*/
 use std::fs;
-use text_colorizer::*;
+use std::path::{Path, PathBuf};
 
 use crate::core::{
-    file_locations::{BALLPARK_LOCATION, PLAYER_LOCATION},
+    file_locations::{
+        data_format, deserialize_data, parse_number, serialize_data, split_fields, DataFormat,
+        ParseError, BALLPARK_FOLDER, PLAYER_FOLDER, TEAM_FOLDER,
+    },
     //game_functions::modern_game_flow,
     *,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{ballparks::*, players::*};
 
@@ -16,14 +20,14 @@ use super::{ballparks::*, players::*};
 ENUM DEFINITIONS
 ==========================================*/
 // TEAM ENUMS
-#[derive(Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Era {
     Ancient,
     Modern,
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Location {
     MiddleOfNowhere,
     SmallTown,
@@ -33,7 +37,7 @@ pub enum Location {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Priority {
     Power,
     Average,
@@ -44,7 +48,7 @@ pub enum Priority {
     None,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Makeup {
     MostlyProspects,
     Balanced,
@@ -110,7 +114,7 @@ pub enum Background {
 // park name
 // park Location
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Fanbase {
     Nonexistent,
     Indifferent,
@@ -121,20 +125,30 @@ pub enum Fanbase {
 }
 
 // Manager
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ManagerLeague {
     Major,
     Minor,
     None,
 }
 
+/// the four player lists on a team, in file order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RosterGroup {
+    Roster,
+    Bench,
+    Pitcher,
+    Bullpen,
+}
+
 /*==========================================
 STRUCTURES
 ==========================================*/
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Team {
+    pub id: String,
     pub name: String,
-    pub ballpark: String, // file name to *.DBB file
+    pub ballpark: String, // ballpark ID (or path to *.DBB file for older teams)
     pub manager: String,  //name
     pub logo: String,     // image file?
     pub era: Era,
@@ -153,14 +167,23 @@ pub struct Team {
     pub motto: String,
     pub owner_background: String,
     pub owner_personality: String,
+    // player IDs, resolved against the league data directory (older teams use file paths)
     pub roster: Vec<String>,
     pub bench: Vec<String>,
     pub pitcher: Vec<String>,
     pub bullpen: Vec<String>,
 }
 
-/// struct for teams in a game - loads player files into Player structs for easier reference
+/// a generated team with its players and ballpark, nothing is on disk until write()
 #[derive(Clone)]
+pub struct GeneratedTeam {
+    pub team: Team,
+    pub players: [Vec<Player>; 4], // indexed by RosterGroup
+    pub ballpark: Ballpark,
+}
+
+/// struct for teams in a game - loads player files into Player structs for easier reference
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ActiveTeam {
     pub roster: Vec<Player>,
     pub bench: Vec<Player>,
@@ -175,6 +198,7 @@ pub struct ActiveTeam {
 pub struct TeamState {
     pub current_batter: u32,
     pub current_pitcher: Player,
+    pub starter: Player, // the game's starting pitcher, pitching changes don't touch it
     pub innings_pitched: u32,
     pub runs: Vec<u32>,
     pub hits: Vec<u32>,
@@ -182,13 +206,122 @@ pub struct TeamState {
 }
 // TODO: make fields to track temp bonuses
 
+/*==========================================
+IMPL BLOCKS
+==========================================*/
+impl RosterGroup {
+    pub const ALL: [RosterGroup; 4] = [
+        RosterGroup::Roster,
+        RosterGroup::Bench,
+        RosterGroup::Pitcher,
+        RosterGroup::Bullpen,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RosterGroup::Roster => "Starters",
+            RosterGroup::Bench => "Bench",
+            RosterGroup::Pitcher => "Starting Pitchers",
+            RosterGroup::Bullpen => "Bullpen",
+        }
+    }
+
+    /// what generate_player() makes for this group
+    pub fn class(&self) -> PlayerClass {
+        match self {
+            RosterGroup::Roster => PlayerClass::StartingHitter,
+            RosterGroup::Bench => PlayerClass::PinchHitter,
+            RosterGroup::Pitcher | RosterGroup::Bullpen => PlayerClass::Pitchers,
+        }
+    }
+}
+
+impl Team {
+    /// player references in [group]
+    pub fn group(&self, group: RosterGroup) -> &Vec<String> {
+        match group {
+            RosterGroup::Roster => &self.roster,
+            RosterGroup::Bench => &self.bench,
+            RosterGroup::Pitcher => &self.pitcher,
+            RosterGroup::Bullpen => &self.bullpen,
+        }
+    }
+
+    pub fn group_mut(&mut self, group: RosterGroup) -> &mut Vec<String> {
+        match group {
+            RosterGroup::Roster => &mut self.roster,
+            RosterGroup::Bench => &mut self.bench,
+            RosterGroup::Pitcher => &mut self.pitcher,
+            RosterGroup::Bullpen => &mut self.bullpen,
+        }
+    }
+}
+
+impl ActiveTeam {
+    /// makes pitching[index] the starter, swapping them into the front of the rotation and the
+    /// pitcher's spot in the batting order
+    pub fn set_starter(&mut self, index: usize) {
+        if index == 0 || index >= self.pitching.len() {
+            return;
+        }
+        let old = self.pitching[0].clone();
+        self.pitching.swap(0, index);
+        for player in self.batting_order.iter_mut() {
+            if *player == old {
+                *player = self.pitching[0].clone();
+            }
+        }
+    }
+}
+
+impl GeneratedTeam {
+    /// writes the players, ballpark and team into the league folders under [data_dir], returns
+    /// the team file - IDs that already have a file in their folder get a new number so nothing
+    /// gets overwritten
+    pub fn write(&self, data_dir: &Path) -> Result<PathBuf, std::io::Error> {
+        for folder in [PLAYER_FOLDER, BALLPARK_FOLDER, TEAM_FOLDER] {
+            fs::create_dir_all(data_dir.join(folder))?;
+        }
+        let mut team = self.team.clone();
+        let player_folder = data_dir.join(PLAYER_FOLDER);
+        for group in RosterGroup::ALL {
+            for (i, player) in self.players[group as usize].iter().enumerate() {
+                let mut player = player.clone();
+                player.id = unused_id(&player.id, &player_folder, "dbp");
+                team.group_mut(group)[i] = player.id.clone();
+                let file_name = player_folder.join(format!("{}.dbp", player.id));
+                write_player(&player, &file_name.to_string_lossy())?;
+            }
+        }
+        let mut ballpark = self.ballpark.clone();
+        let ballpark_folder = data_dir.join(BALLPARK_FOLDER);
+        ballpark.set_id(unused_id(ballpark.id(), &ballpark_folder, "dbb"));
+        team.ballpark = ballpark.id().to_string();
+        let file_name = ballpark_folder.join(format!("{}.dbb", ballpark.id()));
+        ballpark.write(&file_name.to_string_lossy())?;
+        let team_folder = data_dir.join(TEAM_FOLDER);
+        team.id = unused_id(&team.id, &team_folder, "dbt");
+        let file_name = team_folder.join(format!("{}.dbt", team.id));
+        write_team(team, &file_name.to_string_lossy())?;
+
+        Ok(file_name)
+    }
+}
+
 /*==========================================
 FUNCTIONS
 ==========================================*/
 
-/// load team file *.DBT
-pub fn load_team(contents: String) -> Team {
+/// load team file *.DBT, [file] is only used for error messages
+pub fn load_team(contents: String, file: &str) -> Result<Team, ParseError> {
+    // JSON/TOML files go through serde, everything else is the KEY: value format
+    let format = data_format(file);
+    if format != DataFormat::Legacy {
+        return deserialize_data(&contents, file, format);
+    }
+
     // initialize variables for all the different fields
+    let mut id = String::new();
     let mut name = String::new();
     let mut ballpark = String::new();
     let mut manager = String::new();
@@ -215,154 +348,100 @@ pub fn load_team(contents: String) -> Team {
     let mut owner_personality = String::new();
 
     // sort text into relevant fields
-    let rows: Vec<&str> = contents.split('\n').collect();
-    for row in rows.iter() {
-        // last line is usually just a new line character
-        let rowline: Vec<&str> = row.split(':').collect();
-        if rowline[0].trim().eq("TEAM") {
-            name = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("BALLPARK") {
-            ballpark = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("MANAGER") {
-            manager = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("LOGO") {
-            logo = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("ERA") {
-            if rowline[1].trim().eq("Modern") {
-                era = Era::Modern;
-            } else if rowline[1].trim().eq("Ancient") {
-                era = Era::Ancient;
-            }
-        } else if rowline[0].trim().eq("LOCATION") {
-            if rowline[1].trim().eq("Middle of Nowhere") {
-                location = Location::MiddleOfNowhere;
-            } else if rowline[1].trim().eq("Small Town") {
-                location = Location::SmallTown;
-            } else if rowline[1].trim().eq("Small City") {
-                location = Location::SmallCity;
-            } else if rowline[1].trim().eq("Medium Sized City") {
-                location = Location::MediumSizedCity;
-            } else if rowline[1].trim().eq("Metropolis") {
-                location = Location::Metropolis;
-            }
-        } else if rowline[0].trim().eq("MASCOT") {
-            mascot = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("TEAM PRIORITY") {
-            if rowline[1].trim().eq("Power") {
-                priority = Priority::Power;
-            } else if rowline[1].trim().eq("Average") {
-                priority = Priority::Average;
-            } else if rowline[1].trim().eq("Starting Pitching") {
-                priority = Priority::StartingPitching;
-            } else if rowline[1].trim().eq("Bullpen") {
-                priority = Priority::Bullpen;
-            } else if rowline[1].trim().eq("Speed") {
-                priority = Priority::Speed;
-            } else if rowline[1].trim().eq("Defense") {
-                priority = Priority::Defense;
-            }
-        } else if rowline[0].trim().eq("TEAM MAKEUP") {
-            if rowline[1].trim().eq("Mostly Prospects") {
-                makeup = Makeup::MostlyProspects;
-            } else if rowline[1].trim().eq("Balanced") {
-                makeup = Makeup::Balanced;
-            } else if rowline[1].trim().eq("Mostly Veterans") {
-                makeup = Makeup::MostlyVeterans;
-            }
-        } else if rowline[0].trim().eq("YEARS IN LEAGUE") {
-            let years_result = rowline[1].trim().parse();
-            match years_result {
-                Ok(yr) => years = yr,
-                Err(_err) => println!(
-                    "{}",
-                    "Failed to convert 'years in league' number".red().bold()
-                ),
+    for (line, field, value) in split_fields(&contents, file)? {
+        match field.to_uppercase().as_str() {
+            "ID" => id = value.to_string(),
+            "TEAM" => name = value.to_string(),
+            "BALLPARK" => ballpark = value.to_string(),
+            "MANAGER" => manager = value.to_string(),
+            "LOGO" => logo = value.to_string(),
+            "ERA" => {
+                era = match value.to_lowercase().as_str() {
+                    "modern" => Era::Modern,
+                    "ancient" => Era::Ancient,
+                    "none" => Era::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("MOST RECENT CHAMPIONSHIP") {
-            let champ_result = rowline[1].trim().parse();
-            match champ_result {
-                Ok(champ) => championship = champ,
-                Err(_err) => println!(
-                    "{}",
-                    "Failed to convert 'most recent championship' number"
-                        .red()
-                        .bold()
-                ),
+            "LOCATION" => location = parse_location(file, line, field, value)?,
+            "MASCOT" => mascot = value.to_string(),
+            "TEAM PRIORITY" => {
+                priority = match value.to_lowercase().as_str() {
+                    "power" => Priority::Power,
+                    "average" => Priority::Average,
+                    // older versions of write_team() left out the space
+                    "starting pitching" | "startingpitching" => Priority::StartingPitching,
+                    "bullpen" => Priority::Bullpen,
+                    "speed" => Priority::Speed,
+                    "defense" => Priority::Defense,
+                    "none" => Priority::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("FANBASE") {
-            if rowline[1].trim().eq("Non-existent") {
-                fanbase = Fanbase::Nonexistent;
-            } else if rowline[1].trim().eq("Indifferent") {
-                fanbase = Fanbase::Indifferent;
-            } else if rowline[1].trim().eq("Fair Weather") {
-                fanbase = Fanbase::FairWeather;
-            } else if rowline[1].trim().eq("Loyal") {
-                fanbase = Fanbase::Loyal;
-            } else if rowline[1].trim().eq("Obsessive") {
-                fanbase = Fanbase::Obsessive;
+            "TEAM MAKEUP" => {
+                makeup = match value.to_lowercase().as_str() {
+                    "mostly prospects" => Makeup::MostlyProspects,
+                    "balanced" => Makeup::Balanced,
+                    "mostly veterans" => Makeup::MostlyVeterans,
+                    "none" => Makeup::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("POSITION") {
-            if rowline[1].trim().eq("P") {
-                manager_position = Position::Pitcher;
-            } else if rowline[1].trim().eq("C") {
-                manager_position = Position::Catcher;
-            } else if rowline[1].trim().eq("1B") {
-                manager_position = Position::Firstbase;
-            } else if rowline[1].trim().eq("2B") {
-                manager_position = Position::Secondbase;
-            } else if rowline[1].trim().eq("SS") {
-                manager_position = Position::Shortstop;
-            } else if rowline[1].trim().eq("3B") {
-                manager_position = Position::Thirdbase;
-            } else if rowline[1].trim().eq("RF") {
-                manager_position = Position::Rightfield;
-            } else if rowline[1].trim().eq("CF") {
-                manager_position = Position::Centerfield;
-            } else if rowline[1].trim().eq("LF") {
-                manager_position = Position::Leftfield;
-            } else if rowline[1].trim().eq("None") {
-                manager_position = Position::None;
+            "YEARS IN LEAGUE" => years = parse_number(file, line, field, value)?,
+            "MOST RECENT CHAMPIONSHIP" => championship = parse_number(file, line, field, value)?,
+            "FANBASE" => {
+                // NOTE: older versions of write_team() wrote a literal "/n/n## MANAGER INFO" here
+                let value = value.split("/n").next().unwrap_or_default().trim();
+                fanbase = match value.to_lowercase().as_str() {
+                    "non-existent" | "nonexistent" => Fanbase::Nonexistent,
+                    "indifferent" => Fanbase::Indifferent,
+                    "fair weather" => Fanbase::FairWeather,
+                    "loyal" => Fanbase::Loyal,
+                    "obsessive" => Fanbase::Obsessive,
+                    "none" => Fanbase::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("LEAGUE") {
-            if rowline[1].trim().eq("Majors") {
-                manager_league = ManagerLeague::Major;
-            } else if rowline[1].trim().eq("Minors") {
-                manager_league = ManagerLeague::Minor;
-            } else if rowline[1].trim().eq("None") {
-                manager_league = ManagerLeague::None;
+            "POSITION" => {
+                manager_position = match value.to_uppercase().as_str() {
+                    "P" => Position::Pitcher,
+                    "C" => Position::Catcher,
+                    "1B" => Position::Firstbase,
+                    "2B" => Position::Secondbase,
+                    "SS" => Position::Shortstop,
+                    "3B" => Position::Thirdbase,
+                    "RF" => Position::Rightfield,
+                    "CF" => Position::Centerfield,
+                    "LF" => Position::Leftfield,
+                    "BENCH" => Position::Bench,
+                    "NONE" => Position::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("RETIRED") {
-            let retired_result = rowline[1].trim().parse();
-            match retired_result {
-                Ok(rtr) => retired = rtr,
-                Err(_err) => println!("{}", "Failed to convert 'retired' to number".red().bold()),
+            "LEAGUE" => {
+                manager_league = match value.to_lowercase().as_str() {
+                    "majors" => ManagerLeague::Major,
+                    "minors" => ManagerLeague::Minor,
+                    "none" => ManagerLeague::None,
+                    _ => return Err(ParseError::bad_value(file, line, field, value)),
+                }
             }
-        } else if rowline[0].trim().eq("PERSONALITY") {
-            personality = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("DARING") {
-            let daring_result = rowline[1].trim().parse();
-            match daring_result {
-                Ok(dare) => daring = dare,
-                Err(_err) => println!("{}", "Failed to convert 'daring' to number.".red().bold()),
-            }
-        } else if rowline[0].trim().eq("MOTTO") {
-            motto = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("BACKGROUND") {
-            owner_background = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("PERSONALITY") {
-            owner_personality = rowline[1].trim().to_string();
-        } else if rowline[0].trim().eq("PLAYER") {
-            roster.push(rowline[1].trim().to_string());
-        } else if rowline[0].trim().eq("BENCH") {
-            bench.push(rowline[1].trim().to_string());
-        } else if rowline[0].trim().eq("PITCHER") {
-            pitcher.push(rowline[1].trim().to_string());
-        } else if rowline[0].trim().eq("BULLPEN") {
-            bullpen.push(rowline[1].trim().to_string());
+            "RETIRED" => retired = parse_number(file, line, field, value)?,
+            "PERSONALITY" => personality = value.to_string(),
+            "DARING" => daring = parse_number(file, line, field, value)?,
+            "MOTTO" => motto = value.to_string(),
+            "BACKGROUND" => owner_background = value.to_string(),
+            "OWNER PERSONALITY" => owner_personality = value.to_string(),
+            "PLAYER" => roster.push(value.to_string()),
+            "BENCH" => bench.push(value.to_string()),
+            "PITCHER" => pitcher.push(value.to_string()),
+            "BULLPEN" => bullpen.push(value.to_string()),
+            _ => return Err(ParseError::new(file, line, field, value, "unknown field")),
         }
     }
 
-    Team {
+    Ok(Team {
+        id,
         name,
         ballpark,
         manager,
@@ -387,13 +466,38 @@ pub fn load_team(contents: String) -> Team {
         bench,
         pitcher,
         bullpen,
+    })
+}
+
+/// location values shared by team and ballpark files
+pub fn parse_location(
+    file: &str,
+    line: usize,
+    field: &str,
+    value: &str,
+) -> Result<Location, ParseError> {
+    match value.to_lowercase().as_str() {
+        "middle of nowhere" => Ok(Location::MiddleOfNowhere),
+        "small town" => Ok(Location::SmallTown),
+        "small city" => Ok(Location::SmallCity),
+        "medium sized city" => Ok(Location::MediumSizedCity),
+        "metropolis" => Ok(Location::Metropolis),
+        "none" => Ok(Location::None),
+        _ => Err(ParseError::bad_value(file, line, field, value)),
     }
 }
 
-/// write team file *.DBT
+/// write team file *.DBT (JSON/TOML if [filename] ends in .json/.toml)
 pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
+    let format = data_format(filename);
+    if format != DataFormat::Legacy {
+        return serialize_data(&data, filename, format);
+    }
+
     let mut file_text = String::new();
-    file_text.push_str("TEAM: ");
+    file_text.push_str("ID: ");
+    file_text.push_str(&data.id);
+    file_text.push_str("\nTEAM: ");
     file_text.push_str(&data.name);
     file_text.push_str("\nBALLPARK: ");
     file_text.push_str(&data.ballpark);
@@ -405,7 +509,7 @@ pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
         Era::Modern => file_text.push_str("Modern"),
         Era::Ancient => file_text.push_str("Ancient"),
     }
-    file_text.push_str("\nLocation: ");
+    file_text.push_str("\nLOCATION: ");
     match data.location {
         Location::None => file_text.push_str("None"),
         Location::SmallTown => file_text.push_str("Small Town"),
@@ -424,7 +528,7 @@ pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
         Priority::Average => file_text.push_str("Average"),
         Priority::Bullpen => file_text.push_str("Bullpen"),
         Priority::Defense => file_text.push_str("Defense"),
-        Priority::StartingPitching => file_text.push_str("StartingPitching"),
+        Priority::StartingPitching => file_text.push_str("Starting Pitching"),
     }
     file_text.push_str("\nTEAM MAKEUP: ");
     match data.makeup {
@@ -446,7 +550,7 @@ pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
         Fanbase::Indifferent => file_text.push_str("Indifferent"),
         Fanbase::FairWeather => file_text.push_str("Fair Weather"),
     }
-    file_text.push_str("/n/n## MANAGER INFO\nMANAGER: ");
+    file_text.push_str("\n\n## MANAGER INFO\nMANAGER: ");
     file_text.push_str(&data.manager);
     file_text.push_str("\nPOSITION: ");
     match data.manager_position {
@@ -455,7 +559,7 @@ pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
         Position::Pitcher => file_text.push('P'),
         Position::Catcher => file_text.push('C'),
         Position::Firstbase => file_text.push_str("1B"),
-        Position::Shortstop => file_text.push_str("2B"),
+        Position::Shortstop => file_text.push_str("SS"),
         Position::Thirdbase => file_text.push_str("3B"),
         Position::Leftfield => file_text.push_str("LF"),
         Position::Secondbase => file_text.push_str("2B"),
@@ -502,54 +606,31 @@ pub fn write_team(data: Team, filename: &str) -> Result<(), std::io::Error> {
 }
 
 /// turns a Team struct into separate vectors for fielders/bench/pitcher/bullpen
-pub fn load_roster(team: &Team) -> (Vec<Player>, Vec<Player>, Vec<Player>, Vec<Player>) {
+/// player references are resolved against [data_dir], fails if any player can't be loaded
+#[allow(clippy::type_complexity)]
+pub fn load_roster(
+    team: &Team,
+    data_dir: &Path,
+) -> Result<(Vec<Player>, Vec<Player>, Vec<Player>, Vec<Player>), ParseError> {
     let mut roster = Vec::new();
     let mut bench = Vec::new();
     let mut pitcher = Vec::new();
     let mut bullpen = Vec::new();
 
-    for i in 0..team.roster.len() {
-        let read_results = fs::read_to_string(&team.roster[i]);
-        match read_results {
-            Ok(content) => roster.push(load_player(content)),
-            Err(_err) => println!(
-                "{}: {}",
-                "failed to load file".red().bold(),
-                &team.roster[i]
-            ),
-        }
+    for reference in team.roster.iter() {
+        roster.push(load_player_ref(reference, data_dir)?);
     }
-    for i in 0..team.bench.len() {
-        let read_results = fs::read_to_string(&team.bench[i]);
-        match read_results {
-            Ok(content) => bench.push(load_player(content)),
-            Err(_err) => println!("{}: {}", "failed to load file".red().bold(), &team.bench[i]),
-        }
+    for reference in team.bench.iter() {
+        bench.push(load_player_ref(reference, data_dir)?);
     }
-    for i in 0..team.pitcher.len() {
-        let read_results = fs::read_to_string(&team.pitcher[i]);
-        match read_results {
-            Ok(content) => pitcher.push(load_player(content)),
-            Err(_err) => println!(
-                "{}: {}",
-                "failed to load file".red().bold(),
-                &team.pitcher[i]
-            ),
-        }
+    for reference in team.pitcher.iter() {
+        pitcher.push(load_player_ref(reference, data_dir)?);
     }
-    for i in 0..team.bullpen.len() {
-        let read_results = fs::read_to_string(&team.bullpen[i]);
-        match read_results {
-            Ok(content) => bullpen.push(load_player(content)),
-            Err(_err) => println!(
-                "{}: {}",
-                "failed to load file".red().bold(),
-                &team.bullpen[i]
-            ),
-        }
+    for reference in team.bullpen.iter() {
+        bullpen.push(load_player_ref(reference, data_dir)?);
     }
 
-    (roster, bench, pitcher, bullpen)
+    Ok((roster, bench, pitcher, bullpen))
 }
 
 /// generate manager - can borrow a lot from player gen function
@@ -843,7 +924,7 @@ pub fn generate_background() -> Background {
 }
 */
 
-/// generate team function
+/// generate team function, nothing is written - see GeneratedTeam::write()
 // TODO: combine inputs - load all the csv databases into a vector or array, makes it easier to pass into functions
 // probably need to be references as well
 pub fn generate_team(
@@ -863,120 +944,53 @@ pub fn generate_team(
     //locations: Vec<String>, // honestly I forget why this was here in the first place
     name1: &[String],
     name2: &[String],
-) -> Team {
-    // iterate over number of players
-    let mut roster_raw: Vec<Player> = vec![];
-    let mut bench_raw: Vec<Player> = vec![];
-    let mut pitcher_raw: Vec<Player> = vec![];
-    let mut bullpen_raw: Vec<Player> = vec![];
-    let mut roster: Vec<String> = vec![];
-    let mut bench: Vec<String> = vec![];
-    let mut pitcher: Vec<String> = vec![];
-    let mut bullpen: Vec<String> = vec![];
-    let mut position: Position;
-    // create player structs, then write to files - it's the filenames that need to be stored in
-    // the team struct
-    for i in 0..starters_num {
-        // TODO: IDK what to do here, should always be 8 position players so I'm just going to hard
-        // code for now
-        if i == 0 {
-            position = Position::Catcher;
-        } else if i == 1 {
-            position = Position::Firstbase;
-        } else if i == 2 {
-            position = Position::Secondbase;
-        } else if i == 3 {
-            position = Position::Shortstop;
-        } else if i == 4 {
-            position = Position::Thirdbase;
-        } else if i == 5 {
-            position = Position::Leftfield;
-        } else if i == 6 {
-            position = Position::Centerfield;
-        } else if i == 7 {
-            position = Position::Rightfield;
-        } else {
-            position = Position::Firstbase;
-        }
-        roster_raw.push(generate_player(
-            super::players::PlayerClass::StartingHitter,
-            //&era, // uncomment when reintroducing Era
-            position,
-            firstnames,
-            lastnames,
-        ));
-        // write player struct, if file write is successful add it to the filename struct
-        let mut file_name_str = PLAYER_LOCATION.to_owned();
-        file_name_str.push_str(&roster_raw[i as usize].first_name);
-        file_name_str.push('_');
-        file_name_str.push_str(&roster_raw[i as usize].last_name);
-        file_name_str.push_str(".dbp");
-        let write_result = write_player(&roster_raw[i as usize], &file_name_str);
-        match write_result {
-            Ok(()) => roster.push(file_name_str),
-            Err(_err) => println!("Error writing file: {}", file_name_str),
-        }
-    }
-
-    for i in 0..bench_num as usize {
-        bench_raw.push(generate_player(
-            super::players::PlayerClass::PinchHitter,
-            Position::None,
-            firstnames,
-            lastnames,
-        ));
-        // write player struct, if file write is successful add it to the filename struct
-        let mut file_name_str = PLAYER_LOCATION.to_owned();
-        file_name_str.push_str(&bench_raw[i].first_name);
-        file_name_str.push('_');
-        file_name_str.push_str(&bench_raw[i].last_name);
-        file_name_str.push_str(".dbp");
-        let write_result = write_player(&bench_raw[i], &file_name_str);
-        match write_result {
-            Ok(()) => bench.push(file_name_str),
-            Err(_err) => println!("Error writing file: {}", file_name_str),
-        }
-    }
-
-    for i in 0..pitchers_num as usize {
-        pitcher_raw.push(generate_player(
-            super::players::PlayerClass::Pitchers,
-            Position::Pitcher,
-            firstnames,
-            lastnames,
-        ));
-        // write player struct, if file write is successful add it to the filename struct
-        let mut file_name_str = PLAYER_LOCATION.to_owned();
-        file_name_str.push_str(&pitcher_raw[i].first_name);
-        file_name_str.push('_');
-        file_name_str.push_str(&pitcher_raw[i].last_name);
-        file_name_str.push_str(".dbp");
-        let write_result = write_player(&pitcher_raw[i], &file_name_str);
-        match write_result {
-            Ok(()) => pitcher.push(file_name_str),
-            Err(_err) => println!("Error writing file: {}", file_name_str),
-        }
-    }
-
-    for i in 0..bullpen_num as usize {
-        bullpen_raw.push(generate_player(
-            super::players::PlayerClass::Pitchers,
-            Position::Pitcher,
-            firstnames,
-            lastnames,
-        ));
-        // write player struct, if file write is successful add it to the filename struct
-        let mut file_name_str = PLAYER_LOCATION.to_owned();
-        file_name_str.push_str(&bullpen_raw[i].first_name);
-        file_name_str.push('_');
-        file_name_str.push_str(&bullpen_raw[i].last_name);
-        file_name_str.push_str(".dbp");
-        let write_result = write_player(&bullpen_raw[i], &file_name_str);
-        match write_result {
-            Ok(()) => bullpen.push(file_name_str),
-            Err(_err) => println!("Error writing file: {}", file_name_str),
+) -> GeneratedTeam {
+    // starters get the 8 fielding positions in order, anyone past that plays first
+    let positions = [
+        Position::Catcher,
+        Position::Firstbase,
+        Position::Secondbase,
+        Position::Shortstop,
+        Position::Thirdbase,
+        Position::Leftfield,
+        Position::Centerfield,
+        Position::Rightfield,
+    ];
+    let mut players: [Vec<Player>; 4] = Default::default();
+    for group in RosterGroup::ALL {
+        let count = match group {
+            RosterGroup::Roster => starters_num,
+            RosterGroup::Bench => bench_num,
+            RosterGroup::Pitcher => pitchers_num,
+            RosterGroup::Bullpen => bullpen_num,
+        };
+        for i in 0..count as usize {
+            let position = match group {
+                RosterGroup::Roster => positions.get(i).cloned().unwrap_or(Position::Firstbase),
+                RosterGroup::Bench => Position::None,
+                RosterGroup::Pitcher | RosterGroup::Bullpen => Position::Pitcher,
+            };
+            players[group as usize].push(generate_player(
+                group.class(),
+                //&era, // uncomment when reintroducing Era
+                position,
+                firstnames,
+                lastnames,
+            ));
         }
     }
+    let ids = |group: RosterGroup| -> Vec<String> {
+        players[group as usize]
+            .iter()
+            .map(|player| player.id.clone())
+            .collect()
+    };
+    let (roster, bench, pitcher, bullpen) = (
+        ids(RosterGroup::Roster),
+        ids(RosterGroup::Bench),
+        ids(RosterGroup::Pitcher),
+        ids(RosterGroup::Bullpen),
+    );
 
     // manager details
     let manager_name = generate_manager(firstnames, lastnames);
@@ -987,53 +1001,17 @@ pub fn generate_team(
     let years_in_league = roll(100);
     let years_since_championship = roll(years_in_league);
 
-    // generate ballpark structure then write it to file
-    let mut ballpark_string: String = String::new();
-
-    match era {
-        Era::Modern => {
-            // ballpark details
-            let ballpark = generate_modern_ballpark(name1, name2);
-            // build file name string
-            let mut file_name_str = BALLPARK_LOCATION.to_owned();
-            file_name_str.push_str(&ballpark.name);
-            file_name_str.push_str(".dbb");
-            let write_result = write_ballpark_modern(&ballpark, &file_name_str);
-            match write_result {
-                Ok(()) => ballpark_string.push_str(&file_name_str),
-                Err(_err) => ballpark_string.push_str("src/testfiles/railyard.dbb"),
-            }
-        }
-        Era::Ancient => {
-            let ballpark = generate_ancient_ballpark(name1, name2);
-            // build file name string
-            let mut file_name_str = BALLPARK_LOCATION.to_owned();
-            file_name_str.push_str(&ballpark.name);
-            file_name_str.push_str(".dbb");
-            let write_result = write_ballpark_ancient(&ballpark, &file_name_str);
-            match write_result {
-                Ok(()) => ballpark_string.push_str(&file_name_str),
-                Err(_err) => ballpark_string.push_str("src/testfiles/mayfair_park.dbb"),
-            }
-        }
-        Era::None => {
-            let ballpark = generate_ancient_ballpark(name1, name2);
-            // build file_name_str
-            let mut file_name_str = BALLPARK_LOCATION.to_owned();
-            file_name_str.push_str(&ballpark.name);
-            file_name_str.push_str(".dbb");
-            let write_result = write_ballpark_ancient(&ballpark, &file_name_str);
-            match write_result {
-                Ok(()) => ballpark_string.push_str(&file_name_str),
-                Err(_err) => ballpark_string.push_str("src/testfiles/mayfair_park.dbb"),
-            }
-        }
-    }
+    // ballpark details
+    let ballpark = match era {
+        Era::Modern => Ballpark::Modern(generate_modern_ballpark(name1, name2)),
+        Era::Ancient | Era::None => Ballpark::Ancient(generate_ancient_ballpark(name1, name2)),
+    };
 
     // build team struct
-    Team {
+    let team = Team {
+        id: generate_id(name),
         name: name.to_string(),
-        ballpark: ballpark_string, // TODO: auto generate or user define
+        ballpark: ballpark.id().to_string(), // TODO: auto generate or user define
         manager: manager_name,
         logo: generate_logo(logos),
         era,
@@ -1056,5 +1034,11 @@ pub fn generate_team(
         bench,
         pitcher,
         bullpen,
+    };
+
+    GeneratedTeam {
+        team,
+        players,
+        ballpark,
     }
 }
@@ -0,0 +1,328 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::path::Path;
+
+use text_colorizer::*;
+
+use crate::characters::{ballparks::load_park_modern, teams::load_team};
+use crate::core::{
+    calibration::{calibrate, calibration_report, sample_swings, CalibrationInput},
+    file_locations::{
+        resolve_reference, DataFormat, ParseError, BALLPARK_FOLDER, DATA_LOCATION, TEAM_FOLDER,
+    },
+    game_functions::create_modern_game,
+    lahman::{import_lahman, write_import},
+    league::{convert_league, validate_league, IssueKind, LeagueReport},
+    simulation::{simulate_games, SimulationReport, AT_BAT_RESULTS},
+};
+
+const USAGE: &str = "usage:
+    deadball                                          start the game
+    deadball check [DIR]                              validate a league folder
+    deadball convert [DIR] [--legacy|--json|--toml]   rewrite a league folder in one format
+    deadball import-lahman LAHMAN_DIR SEASON [DIR]    make players/teams from Lahman CSVs
+    deadball simulate HOME AWAY PARK [GAMES] [DIR] [--oddity]
+                                                      play GAMES games (default 1000) and show odds
+    deadball calibrate BT OBT DIE [SAMPLES] [--oddity]
+                                                      exact table odds, SAMPLES checks the dice
+
+DIR defaults to the built in league folder, it should contain teams/, players/ and ballparks/
+HOME, AWAY and PARK can be IDs in DIR or paths to files";
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// runs a command line subcommand, returns the process exit code
+/// 0 = fine, 1 = problems found (or a simulation failed to start), 2 = bad arguments
+pub fn run(args: &[String]) -> i32 {
+    let command = args[0].as_str();
+    let mut positional: Vec<&str> = vec![];
+    let mut format = DataFormat::Legacy;
+    let mut oddity = false;
+    for arg in args.iter().skip(1) {
+        match arg.as_str() {
+            "--oddity" => oddity = true,
+            "--legacy" => format = DataFormat::Legacy,
+            "--json" => format = DataFormat::Json,
+            "--toml" => format = DataFormat::Toml,
+            flag if flag.starts_with("--") => {
+                eprintln!("{} {}\n{}", "unknown option".red().bold(), flag, USAGE);
+                return 2;
+            }
+            value => positional.push(value),
+        }
+    }
+
+    let data_dir = match command {
+        "import-lahman" => positional.get(2),
+        "simulate" => positional.get(4),
+        _ => positional.first(),
+    }
+    .copied()
+    .unwrap_or(DATA_LOCATION);
+    let report = match command {
+        "check" => validate_league(Path::new(data_dir)),
+        "convert" => convert_league(Path::new(data_dir), format),
+        "import-lahman" => {
+            let season = positional.get(1).and_then(|season| season.parse().ok());
+            let (Some(lahman_dir), Some(season)) = (positional.first(), season) else {
+                eprintln!(
+                    "{}\n{}",
+                    "need a Lahman folder and season".red().bold(),
+                    USAGE
+                );
+                return 2;
+            };
+            if let Err(err) = import_season(Path::new(lahman_dir), season, Path::new(data_dir)) {
+                eprintln!("{} {}", "import failed:".red().bold(), err);
+                return 1;
+            }
+            // show what the new teams look like
+            validate_league(Path::new(data_dir))
+        }
+        "simulate" => {
+            let games = match positional.get(3).map(|games| games.parse::<u32>()) {
+                None => 1000,
+                Some(Ok(games)) if games > 0 => games,
+                Some(_) => {
+                    eprintln!(
+                        "{}\n{}",
+                        "GAMES must be a positive number".red().bold(),
+                        USAGE
+                    );
+                    return 2;
+                }
+            };
+            let [Some(home), Some(away), Some(park)] =
+                [0, 1, 2].map(|i| positional.get(i).copied())
+            else {
+                eprintln!("{}\n{}", "need home, away and ballpark".red().bold(), USAGE);
+                return 2;
+            };
+            return match simulate(home, away, park, games, oddity, Path::new(data_dir)) {
+                Ok(()) => 0,
+                Err(err) => {
+                    eprintln!("{} {}", "simulation failed:".red().bold(), err);
+                    1
+                }
+            };
+        }
+        "calibrate" => {
+            let numbers: Vec<Option<i32>> = positional.iter().map(|n| n.parse().ok()).collect();
+            let (Some(Some(bat_target)), Some(Some(on_base_target)), Some(Some(pitch_die))) =
+                (numbers.first(), numbers.get(1), numbers.get(2))
+            else {
+                eprintln!("{}\n{}", "need BT, OBT and pitch die".red().bold(), USAGE);
+                return 2;
+            };
+            let samples = match numbers.get(3) {
+                None => 0,
+                Some(Some(samples)) if *samples >= 0 => *samples as u32,
+                Some(_) => {
+                    eprintln!("{}\n{}", "SAMPLES must be a number".red().bold(), USAGE);
+                    return 2;
+                }
+            };
+            if *pitch_die == 0 {
+                eprintln!("{}\n{}", "pitch die can't be 0".red().bold(), USAGE);
+                return 2;
+            }
+            let input = CalibrationInput {
+                bat_target: *bat_target,
+                on_base_target: *on_base_target,
+                pitch_die: *pitch_die,
+                oddity,
+                power_mod: 0,
+                defense_mod: 0,
+            };
+            let calibration = calibrate(&input);
+            print!("{}", calibration_report(&calibration));
+            if samples > 0 {
+                print_samples(
+                    &calibration.swings,
+                    &sample_swings(&input, samples),
+                    samples,
+                );
+            }
+            return 0;
+        }
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        _ => {
+            eprintln!("{} {}\n{}", "unknown command".red().bold(), command, USAGE);
+            return 2;
+        }
+    };
+    print_report(data_dir, &report);
+
+    if report.issues.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
+fn import_season(lahman_dir: &Path, season: u32, data_dir: &Path) -> Result<(), String> {
+    let import = import_lahman(lahman_dir, season).map_err(|err| err.to_string())?;
+    write_import(&import, data_dir).map_err(|err| err.to_string())?;
+    for lahman_id in import.skipped.iter() {
+        eprintln!(
+            "{} {} has no People.csv entry",
+            "skipped:".yellow().bold(),
+            lahman_id
+        );
+    }
+    println!(
+        "imported {} players on {} teams from {}",
+        import.players.len(),
+        import.teams.len(),
+        import.season
+    );
+
+    Ok(())
+}
+
+fn simulate(
+    home: &str,
+    away: &str,
+    park: &str,
+    games: u32,
+    oddity: bool,
+    data_dir: &Path,
+) -> Result<(), String> {
+    let load = |reference: &str, folder: &str, extension: &str| {
+        let path = resolve_reference(reference, data_dir, folder, extension);
+        let file = path.display().to_string();
+        std::fs::read_to_string(&path)
+            .map(|contents| (contents, file.clone()))
+            .map_err(|err| ParseError::io(&file, err).to_string())
+    };
+    let (contents, file) = load(home, TEAM_FOLDER, "dbt")?;
+    let home = load_team(contents, &file).map_err(|err| err.to_string())?;
+    let (contents, file) = load(away, TEAM_FOLDER, "dbt")?;
+    let away = load_team(contents, &file).map_err(|err| err.to_string())?;
+    let (contents, file) = load(park, BALLPARK_FOLDER, "dbb")?;
+    let park = load_park_modern(contents, &file).map_err(|err| err.to_string())?;
+    let game = create_modern_game(home, away, park, oddity, data_dir).map_err(|err| err.message)?;
+
+    let report = simulate_games(&game, games, 0);
+    print_simulation(&game.home.name, &game.away.name, &report);
+
+    Ok(())
+}
+
+fn print_simulation(home: &str, away: &str, report: &SimulationReport) {
+    println!("{} games at {}", report.games, home.bold());
+    for (name, is_home, win_pct) in [
+        (away, false, report.away_win_pct()),
+        (home, true, report.home_win_pct()),
+    ] {
+        let (runs, hits, errors) = report.means(is_home);
+        println!(
+            "    {:<24} win {:>5.1}%   R {:>5.2}   H {:>5.2}   E {:>4.2}",
+            name,
+            win_pct * 100.0,
+            runs,
+            hits,
+            errors
+        );
+    }
+    if report.ties > 0 {
+        println!("    {} games didn't finish", report.ties);
+    }
+
+    println!("\n{}", "Runs per game".bold());
+    let most = report
+        .home
+        .runs
+        .keys()
+        .chain(report.away.runs.keys())
+        .max()
+        .copied()
+        .unwrap_or(0);
+    println!("    {:>3}  {:>7}  {:>7}", "R", "away", "home");
+    for runs in 0..=most {
+        let share = |side: &std::collections::BTreeMap<u32, u32>| {
+            *side.get(&runs).unwrap_or(&0) as f32 / report.games.max(1) as f32 * 100.0
+        };
+        println!(
+            "    {:>3}  {:>6.1}%  {:>6.1}%",
+            runs,
+            share(&report.away.runs),
+            share(&report.home.runs)
+        );
+    }
+
+    println!("\n{}", "Swing results".bold());
+    for (result, share) in report.at_bat_frequencies() {
+        println!(
+            "    {:<16} {:>5.1}%",
+            format!("{:?}", result),
+            share * 100.0
+        );
+    }
+}
+
+// sampled swing odds next to the exact ones, big gaps mean the dice are off
+fn print_samples(exact: &[f64; 9], sampled: &[f64; 9], samples: u32) {
+    println!(
+        "\n{}",
+        format!("Sampled with the game's dice ({})", samples).bold()
+    );
+    for ((result, exact), sampled) in AT_BAT_RESULTS.iter().zip(exact).zip(sampled) {
+        let diff = (sampled - exact) * 100.0;
+        let text = format!(
+            "    {:<18}{:>7.2}%  exact {:>6.2}%  {:+.2}",
+            format!("{:?}", result),
+            sampled * 100.0,
+            exact * 100.0,
+            diff
+        );
+        // more than a point off is worth a look
+        if diff.abs() > 1.0 {
+            println!("{}", text.red());
+        } else {
+            println!("{}", text);
+        }
+    }
+}
+
+fn print_report(data_dir: &str, report: &LeagueReport) {
+    println!(
+        "{}: {} players, {} teams, {} ballparks",
+        data_dir.bold(),
+        report.players,
+        report.teams,
+        report.ballparks
+    );
+    if report.converted > 0 {
+        println!("converted {} files", report.converted);
+    }
+    for (kind, title) in [
+        (IssueKind::Parse, "Malformed files"),
+        (IssueKind::BrokenReference, "Broken references"),
+        (
+            IssueKind::DuplicatePlayer,
+            "Players on more than one roster",
+        ),
+        (IssueKind::Roster, "Teams that can't start a game"),
+    ] {
+        if report.count(kind) == 0 {
+            continue;
+        }
+        println!("\n{} ({})", title.red().bold(), report.count(kind));
+        for issue in report.issues.iter().filter(|issue| issue.kind == kind) {
+            match kind {
+                // parse errors already start with the file name
+                IssueKind::Parse => println!("    {}", issue.message),
+                _ => println!("    {}: {}", issue.file, issue.message),
+            }
+        }
+    }
+    if report.issues.is_empty() {
+        println!("{}", "no problems found".green().bold());
+    }
+}
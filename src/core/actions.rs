@@ -0,0 +1,296 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::characters::{players::Position, teams::ActiveTeam};
+use crate::gui::debug::DebugConfig;
+
+use super::game_functions::{
+    bunt, find_by_position, hit_and_run, modern_game_flow, process_steals, GameModern, GameState,
+    GameStatus, InningTB, Outs, StealType,
+};
+
+/*========================================================
+ENUM DEFINITIONS
+========================================================*/
+/// something a manager does on the way through a game
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Action {
+    Swing, // next at bat, no decision made
+    Steal(StealType),
+    Bunt,
+    HitAndRun,
+    Substitution { home: bool, team: ActiveTeam }, // [team] is the roster after the swap
+}
+
+/// which team makes the call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionSide {
+    Offense,
+    Defense,
+}
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// an action the rules know about, whether it's allowed right now and what it needs to be
+pub struct ActionOption {
+    pub action: Action,
+    pub side: ActionSide,
+    pub legal: bool,
+    pub precondition: &'static str,
+}
+
+//======== CUSTOM ERRORS =================================
+/// an action the rules don't allow in the current game state
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionError {
+    pub action: String,
+    pub reason: String,
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl Action {
+    /// bunts and hit and runs are the batter's plate appearance, same as a swing
+    pub fn ends_at_bat(&self) -> bool {
+        matches!(self, Action::Swing | Action::Bunt | Action::HitAndRun)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Action::Swing => "Swing".to_string(),
+            Action::Steal(StealType::Second) => "Steal 2nd".to_string(),
+            Action::Steal(StealType::Third) => "Steal 3rd".to_string(),
+            Action::Steal(StealType::Home) => "Steal Home".to_string(),
+            Action::Steal(StealType::Double) => "Double Steal".to_string(),
+            Action::Bunt => "Bunt".to_string(),
+            Action::HitAndRun => "Hit & Run".to_string(),
+            Action::Substitution { home, .. } => {
+                format!("{} substitution", if *home { "Home" } else { "Away" })
+            }
+        }
+    }
+
+    /// what the rules need before the action is allowed
+    pub fn precondition(&self) -> &'static str {
+        match self {
+            Action::Swing => "Game not over.",
+            Action::Steal(StealType::Second) => "Runner on 1st, 2nd base open.",
+            Action::Steal(StealType::Third) => "Runner on 2nd, 3rd base open.",
+            Action::Steal(StealType::Home) => "Speedy (S+) runner on 3rd.",
+            Action::Steal(StealType::Double) => "Runners on 1st and 2nd, 3rd base open.",
+            Action::Bunt => "Runner on base.",
+            Action::HitAndRun => "Runner on 1st only.",
+            Action::Substitution { .. } => {
+                "Game not over, 9 batters, a pitcher and every position in the field."
+            }
+        }
+    }
+
+    fn error(&self, reason: &str) -> ActionError {
+        ActionError {
+            action: self.label(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.action, self.reason)
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl GameState {
+    /// checks the rules allow [action] right now
+    pub fn check_action(&self, game: &GameModern, action: &Action) -> Result<(), ActionError> {
+        if self.status == GameStatus::Over {
+            return Err(action.error("the game is over"));
+        }
+        let play_allowed = || {
+            if self.status != GameStatus::Ongoing {
+                Err(action.error("the game hasn't started"))
+            } else if self.outs == Outs::Three {
+                Err(action.error("the inning is over"))
+            } else {
+                Ok(())
+            }
+        };
+        match action {
+            Action::Swing => Ok(()),
+            Action::Steal(_) => {
+                play_allowed()?;
+                self.check_bases(action)?;
+                // process_steals needs the catcher's arm
+                match find_by_position(Position::Catcher, &self.fielding(game).roster) {
+                    Some(_) => Ok(()),
+                    None => Err(action.error("no catcher in the field")),
+                }
+            }
+            Action::Bunt | Action::HitAndRun => {
+                play_allowed()?;
+                self.check_bases(action)
+            }
+            Action::Substitution { team, .. } => check_lineup(team).map_err(|e| action.error(&e)),
+        }
+    }
+
+    /// checks the runners [action] needs are on base and the bases they're going to are open
+    pub fn check_bases(&self, action: &Action) -> Result<(), ActionError> {
+        let bases = &self.bases;
+        let open = |base: u32| {
+            if bases.is_occupied(base) {
+                Err(action.error(&format!("base {} is taken", base)))
+            } else {
+                Ok(())
+            }
+        };
+        let runner = |base: u32| {
+            if bases.is_occupied(base) {
+                Ok(())
+            } else {
+                Err(action.error(&format!("no runner on base {}", base)))
+            }
+        };
+        match action {
+            Action::Steal(StealType::Second) => runner(1).and(open(2)),
+            Action::Steal(StealType::Third) => runner(2).and(open(3)),
+            Action::Steal(StealType::Home) => match bases.runner(3) {
+                Some(stealer) if stealer.speedy() => Ok(()),
+                Some(_) => Err(action.error("the runner on 3rd isn't speedy")),
+                None => runner(3),
+            },
+            Action::Steal(StealType::Double) => runner(1).and(runner(2)).and(open(3)),
+            Action::Bunt if bases.is_empty() => Err(action.error("no runners on base")),
+            Action::HitAndRun => runner(1).and(open(2)).and(open(3)),
+            _ => Ok(()),
+        }
+    }
+
+    /// every action the rules know about for both teams, legal or not
+    pub fn action_options(&self, game: &GameModern) -> Vec<ActionOption> {
+        let home_fielding = self.inning_half == InningTB::Top;
+        let mut actions = vec![
+            Action::Swing,
+            Action::Steal(StealType::Second),
+            Action::Steal(StealType::Third),
+            Action::Steal(StealType::Home),
+            Action::Steal(StealType::Double),
+            Action::Bunt,
+            Action::HitAndRun,
+        ];
+        // the fielding team's change first, the batting team's (pinch hitters) after
+        for home in [home_fielding, !home_fielding] {
+            let team = if home {
+                game.home_active.clone()
+            } else {
+                game.away_active.clone()
+            };
+            actions.push(Action::Substitution { home, team });
+        }
+
+        actions
+            .into_iter()
+            .map(|action| {
+                let side = match &action {
+                    Action::Substitution { home, .. } if *home == home_fielding => {
+                        ActionSide::Defense
+                    }
+                    _ => ActionSide::Offense,
+                };
+                ActionOption {
+                    legal: self.check_action(game, &action).is_ok(),
+                    precondition: action.precondition(),
+                    side,
+                    action,
+                }
+            })
+            .collect()
+    }
+
+    fn fielding<'a>(&self, game: &'a GameModern) -> &'a ActiveTeam {
+        match self.inning_half {
+            InningTB::Top => &game.home_active,
+            InningTB::Bottom => &game.away_active,
+        }
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// runs one manager action on the game, without recording it - fails without touching the game if
+/// the rules don't allow it
+pub fn apply_action(
+    game: &mut GameModern,
+    state: GameState,
+    action: &Action,
+    debug: &mut DebugConfig,
+) -> Result<GameState, ActionError> {
+    state.check_action(game, action)?;
+    let (batting, bat_num) = match state.inning_half {
+        InningTB::Top => (&game.away_active, state.away_state.current_batter),
+        InningTB::Bottom => (&game.home_active, state.home_state.current_batter),
+    };
+    let batter = batting.batting_order[bat_num as usize].clone();
+    // queued debug rolls start over for every play
+    debug.roll_index = 0;
+    let state = match action {
+        Action::Swing => modern_game_flow(game, state, debug),
+        Action::Steal(steal) => {
+            // checked above
+            let catcher =
+                find_by_position(Position::Catcher, &state.fielding(game).roster).unwrap();
+            process_steals(steal.clone(), state, debug, &catcher)?
+        }
+        Action::Bunt => bunt(state, game, debug, batter),
+        Action::HitAndRun => hit_and_run(state, game, debug, batter)?,
+        Action::Substitution { home, team } => {
+            if *home {
+                game.home_active = team.clone();
+            } else {
+                game.away_active = team.clone();
+            }
+            state
+        }
+    };
+
+    Ok(state)
+}
+
+// a lineup the engine can play with, everything update_player_labels()/process_steals() look up
+fn check_lineup(team: &ActiveTeam) -> Result<(), String> {
+    if team.batting_order.len() != 9 {
+        return Err(format!(
+            "{} batters in the lineup, needs 9",
+            team.batting_order.len()
+        ));
+    }
+    if team.pitching.is_empty() {
+        return Err("no pitcher".to_string());
+    }
+    let positions = [
+        Position::Catcher,
+        Position::Firstbase,
+        Position::Secondbase,
+        Position::Shortstop,
+        Position::Thirdbase,
+        Position::Leftfield,
+        Position::Centerfield,
+        Position::Rightfield,
+    ];
+    for position in positions {
+        if find_by_position(position.clone(), &team.roster).is_none() {
+            return Err(format!("nobody playing {:?}", position));
+        }
+    }
+
+    Ok(())
+}
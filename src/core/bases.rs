@@ -0,0 +1,184 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use crate::characters::players::Player;
+
+use super::game_functions::RunnersOn;
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// runners on first, second and third - which bases are occupied comes from the runners
+/// themselves, so the two can't get out of sync
+/// bases are numbered 1-3 like the rules, anything past 3 means the runner scored
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bases {
+    runners: [Option<Player>; 3],
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl Bases {
+    /// bases filled in [occupancy], [runner] picks who stands on each occupied base
+    pub fn from_occupancy(occupancy: RunnersOn, mut runner: impl FnMut(u32) -> Player) -> Self {
+        let mut bases = Bases::default();
+        for (i, occupied) in occupancy.occupied().iter().enumerate() {
+            if *occupied {
+                let base = i as u32 + 1;
+                bases.place(base, runner(base));
+            }
+        }
+        bases
+    }
+
+    /// runner on [base], None if it's empty (or not a base)
+    pub fn runner(&self, base: u32) -> Option<&Player> {
+        slot(base).and_then(|i| self.runners[i].as_ref())
+    }
+
+    /// the runners on first, second and third
+    pub fn runners(&self) -> &[Option<Player>; 3] {
+        &self.runners
+    }
+
+    pub fn is_occupied(&self, base: u32) -> bool {
+        self.runner(base).is_some()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.runners
+            .iter()
+            .filter(|runner| runner.is_some())
+            .count() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// which bases are occupied, for rules that go by the runners on pattern
+    pub fn occupancy(&self) -> RunnersOn {
+        RunnersOn::from_occupied(self.runners.each_ref().map(|runner| runner.is_some()))
+    }
+
+    /// base of the runner furthest along, None if the bases are empty
+    pub fn lead_runner(&self) -> Option<u32> {
+        (1..=3).rev().find(|base| self.is_occupied(*base))
+    }
+
+    /// puts [runner] on [base], does nothing and returns false if the base is already taken
+    pub fn place(&mut self, base: u32, runner: Player) -> bool {
+        match slot(base) {
+            Some(i) if self.runners[i].is_none() => {
+                self.runners[i] = Some(runner);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// takes the runner off [base] (out, or moving somewhere else)
+    pub fn remove(&mut self, base: u32) -> Option<Player> {
+        slot(base).and_then(|i| self.runners[i].take())
+    }
+
+    pub fn clear(&mut self) {
+        self.runners = Default::default();
+    }
+
+    /// moves the runner on [from] to [to], returns the runs scored (1 if [to] is past third)
+    /// [to] should be empty, move the runners ahead first
+    pub fn move_runner(&mut self, from: u32, to: u32) -> u32 {
+        let Some(runner) = self.remove(from) else {
+            return 0;
+        };
+        if to > 3 {
+            return 1;
+        }
+        let placed = self.place(to, runner);
+        debug_assert!(
+            placed,
+            "moved a runner from {} onto a taken base {}",
+            from, to
+        );
+        0
+    }
+
+    /// every runner on [from] or further along moves up [bases] bases, returns the runs scored
+    pub fn advance_from(&mut self, from: u32, bases: u32) -> u32 {
+        if bases == 0 {
+            return 0;
+        }
+        // lead runner first so nobody lands on a taken base
+        (from.max(1)..=3)
+            .rev()
+            .map(|base| self.move_runner(base, base + bases))
+            .sum()
+    }
+
+    /// every runner moves up [bases] bases, returns the runs scored
+    pub fn advance(&mut self, bases: u32) -> u32 {
+        self.advance_from(1, bases)
+    }
+
+    /// the batter is headed to [batter_base], runners in the way are pushed ahead of them (walks,
+    /// HBP, balks) - returns the runs scored, the batter still has to be placed
+    pub fn force(&mut self, batter_base: u32) -> u32 {
+        let mut forced = std::mem::take(&mut self.runners);
+        // furthest base taken by the batter or a runner pushed ahead of them
+        let mut taken = batter_base;
+        let mut runs = 0;
+        for (i, runner) in forced.iter_mut().enumerate() {
+            let Some(runner) = runner.take() else {
+                continue;
+            };
+            let base = i as u32 + 1;
+            let to = if base <= taken { taken + 1 } else { base };
+            taken = to;
+            if to > 3 {
+                runs += 1;
+            } else {
+                self.place(to, runner);
+            }
+        }
+        runs
+    }
+}
+
+impl RunnersOn {
+    /// [first, second, third], same as the binary name
+    pub fn occupied(&self) -> [bool; 3] {
+        match self {
+            RunnersOn::Runner000 => [false, false, false],
+            RunnersOn::Runner100 => [true, false, false],
+            RunnersOn::Runner010 => [false, true, false],
+            RunnersOn::Runner001 => [false, false, true],
+            RunnersOn::Runner110 => [true, true, false],
+            RunnersOn::Runner101 => [true, false, true],
+            RunnersOn::Runner011 => [false, true, true],
+            RunnersOn::Runner111 => [true, true, true],
+        }
+    }
+
+    pub fn from_occupied(occupied: [bool; 3]) -> Self {
+        match occupied {
+            [false, false, false] => RunnersOn::Runner000,
+            [true, false, false] => RunnersOn::Runner100,
+            [false, true, false] => RunnersOn::Runner010,
+            [false, false, true] => RunnersOn::Runner001,
+            [true, true, false] => RunnersOn::Runner110,
+            [true, false, true] => RunnersOn::Runner101,
+            [false, true, true] => RunnersOn::Runner011,
+            [true, true, true] => RunnersOn::Runner111,
+        }
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+// array index for a base number
+fn slot(base: u32) -> Option<usize> {
+    (1..=3).contains(&base).then(|| base as usize - 1)
+}
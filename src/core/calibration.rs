@@ -0,0 +1,198 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use super::game_functions::{
+    at_bat, crit_hit, defense_entry, hit_table_entry, AtBatResults, Defense, HitTable,
+};
+use super::roll;
+use super::simulation::AT_BAT_RESULTS;
+
+/*========================================================
+CONSTANTS
+========================================================*/
+/// every hit table entry, in the order they're reported
+pub const HIT_TABLE: [HitTable; 11] = [
+    HitTable::Single,
+    HitTable::SingleDef1B,
+    HitTable::SingleDef2B,
+    HitTable::SingleDef3B,
+    HitTable::SingleDefSS,
+    HitTable::SingleRunnersAdv,
+    HitTable::DoubleDefLF,
+    HitTable::DoubleDefCF,
+    HitTable::DoubleDefRF,
+    HitTable::DoubleRunnerAdv,
+    HitTable::HomeRun,
+];
+
+/// every defense table entry, in the order they're reported
+pub const DEFENSE_TABLE: [Defense; 4] = [
+    Defense::Error,
+    Defense::NoChange,
+    Defense::DoubleToSingle,
+    Defense::HitToOut,
+];
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// inputs for a calibration run, modifiers are the trait bonuses added to the d20/d12 rolls
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationInput {
+    pub bat_target: i32,
+    pub on_base_target: i32,
+    pub pitch_die: i32, // negative dice subtract from the MSS, same as the game
+    pub oddity: bool,
+    pub power_mod: i32,   // P+/P- etc., added to the hit roll
+    pub defense_mod: i32, // D+/D- etc., added to the defense roll
+}
+
+/// exact odds for every table, each entry is a probability (0.0 - 1.0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    pub input: CalibrationInput,
+    pub swings: [f64; 9],     // AT_BAT_RESULTS order
+    pub hits: [f64; 11],      // HIT_TABLE order
+    pub crit_hits: [f64; 11], // HIT_TABLE order, after crit_hit() bumps the roll
+    pub defense: [f64; 4],    // DEFENSE_TABLE order
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// enumerates every pitch die + d100, d20 and d12 outcome through the same functions the game
+/// uses (at_bat(), hit_table_entry(), crit_hit(), defense_entry())
+pub fn calibrate(input: &CalibrationInput) -> Calibration {
+    let mut swings = [0.0; 9];
+    let combinations = input.pitch_die.abs() * 100;
+    for pitch in pitch_results(input.pitch_die) {
+        for d100 in 1..=100 {
+            let result = at_bat(
+                input.bat_target,
+                input.on_base_target,
+                pitch + d100,
+                input.oddity,
+            );
+            swings[result_index(&result)] += 1.0 / combinations as f64;
+        }
+    }
+
+    let mut hits = [0.0; 11];
+    let mut crit_hits = [0.0; 11];
+    for d20 in 1..=20 {
+        let hit_result = d20 + input.power_mod;
+        hits[hit_index(hit_table_entry(hit_result))] += 1.0 / 20.0;
+        crit_hits[hit_index(hit_table_entry(crit_hit(&hit_result)))] += 1.0 / 20.0;
+    }
+
+    let mut defense = [0.0; 4];
+    for d12 in 1..=12 {
+        let entry = defense_entry(d12 + input.defense_mod);
+        let i = DEFENSE_TABLE.iter().position(|d| *d == entry).unwrap_or(0);
+        defense[i] += 1.0 / 12.0;
+    }
+
+    Calibration {
+        input: input.clone(),
+        swings,
+        hits,
+        crit_hits,
+        defense,
+    }
+}
+
+/// swing result odds from [samples] rolls of the game's own dice, to compare against the exact
+/// odds from calibrate() - a difference means the dice don't match the book
+pub fn sample_swings(input: &CalibrationInput, samples: u32) -> [f64; 9] {
+    let mut swings = [0.0; 9];
+    for _ in 0..samples {
+        // same as modern_inning_flow()
+        let pitch = if input.pitch_die > 0 {
+            roll(input.pitch_die)
+        } else {
+            -roll(input.pitch_die.abs())
+        };
+        let mss = pitch + roll(100);
+        let result = at_bat(input.bat_target, input.on_base_target, mss, input.oddity);
+        swings[result_index(&result)] += 1.0;
+    }
+    swings.map(|count| count / samples.max(1) as f64)
+}
+
+/// plain text report of a calibration run, also used for the golden files in testfiles/calibration
+pub fn calibration_report(calibration: &Calibration) -> String {
+    let input = &calibration.input;
+    let mut lines = vec![
+        format!(
+            "BT {}, OBT {}, pitch die {}, oddities {}",
+            input.bat_target,
+            input.on_base_target,
+            die_name(input.pitch_die),
+            if input.oddity { "on" } else { "off" }
+        ),
+        String::new(),
+        format!(
+            "Swing results (pitch die + d100, {} combinations)",
+            input.pitch_die.abs() * 100
+        ),
+    ];
+    for (result, odds) in AT_BAT_RESULTS.iter().zip(calibration.swings.iter()) {
+        lines.push(odds_line(&format!("{:?}", result), *odds));
+    }
+    let swings = &calibration.swings;
+    lines.push(odds_line("on base", swings[1] + swings[2] + swings[3]));
+
+    for (title, table) in [
+        (
+            format!("Hit table (d20{:+})", input.power_mod),
+            &calibration.hits,
+        ),
+        (
+            format!("Critical hit table (d20{:+}, bumped)", input.power_mod),
+            &calibration.crit_hits,
+        ),
+    ] {
+        lines.push(String::new());
+        lines.push(title);
+        for (entry, odds) in HIT_TABLE.iter().zip(table.iter()) {
+            lines.push(odds_line(&format!("{:?}", entry), *odds));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Defense table (d12{:+})", input.defense_mod));
+    for (entry, odds) in DEFENSE_TABLE.iter().zip(calibration.defense.iter()) {
+        lines.push(odds_line(&format!("{:?}", entry), *odds));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// every face of the pitch die, negative for negative dice
+fn pitch_results(pitch_die: i32) -> Vec<i32> {
+    if pitch_die > 0 {
+        (1..=pitch_die).collect()
+    } else {
+        (1..=pitch_die.abs()).map(|pitch| -pitch).collect()
+    }
+}
+
+fn result_index(result: &AtBatResults) -> usize {
+    AT_BAT_RESULTS.iter().position(|r| r == result).unwrap_or(0)
+}
+
+fn hit_index(entry: HitTable) -> usize {
+    HIT_TABLE.iter().position(|h| *h == entry).unwrap_or(0)
+}
+
+fn die_name(pitch_die: i32) -> String {
+    if pitch_die < 0 {
+        format!("-d{}", pitch_die.abs())
+    } else {
+        format!("d{}", pitch_die)
+    }
+}
+
+fn odds_line(label: &str, odds: f64) -> String {
+    format!("    {:<18}{:>7.2}%", label, odds * 100.0)
+}
@@ -2,16 +2,72 @@
 
 /* CONSTANTS */
 
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use egui_toast::{Toast, ToastKind, ToastOptions, ToastStyle, Toasts};
+use serde::{de::DeserializeOwned, Serialize};
 
-pub const TEAM_LOCATION: &str = "src/testfiles/game/teams/";
-pub const PLAYER_LOCATION: &str = "src/testfiles/game/players/";
-pub const BALLPARK_LOCATION: &str = "src/testfiles/game/ballparks/";
+use crate::core::settings::config_dir;
+
+// default league data directory, teams/players/ballparks live in sub folders
+pub const DATA_LOCATION: &str = "src/testfiles/game/";
+pub const TEAM_FOLDER: &str = "teams";
+pub const PLAYER_FOLDER: &str = "players";
+pub const BALLPARK_FOLDER: &str = "ballparks";
+pub const RECORD_LOCATION: &str = "src/testfiles/game/records"; // game records for replays
+
+// name/park databases, looked for in this folder before falling back to the built in copies
+pub const DATABASE_FOLDER: &str = "databases";
+pub const DATABASE_ENV: &str = "DEADBALL_DATABASES"; // extra folder to check first
+
+// built in databases, so an installed binary doesn't need the source tree next to it
+const EMBEDDED_DATABASES: [(&str, &str); 9] = [
+    ("firstname.csv", include_str!("../databases/firstname.csv")),
+    ("lastname.csv", include_str!("../databases/lastname.csv")),
+    ("logo.csv", include_str!("../databases/logo.csv")),
+    ("mascot.csv", include_str!("../databases/mascot.csv")),
+    ("motto.csv", include_str!("../databases/motto.csv")),
+    (
+        "personality.csv",
+        include_str!("../databases/personality.csv"),
+    ),
+    (
+        "background.csv",
+        include_str!("../databases/background.csv"),
+    ),
+    ("park1.csv", include_str!("../databases/park1.csv")),
+    ("park2.csv", include_str!("../databases/park2.csv")),
+];
 
 /* ENUMS */
+/// formats player/team/ballpark files can be stored in, picked by file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataFormat {
+    Legacy, // KEY: value text, *.dbp/*.dbt/*.dbb
+    Json,
+    Toml,
+}
+
+/// where a database ended up coming from
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseSource {
+    Embedded,
+    File(PathBuf),
+}
+
 /* STRUCTS */
+/// how loading one database went, for the diagnostics window
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseReport {
+    pub file: String,
+    pub source: DatabaseSource,
+    pub entries: usize,
+    pub error: Option<String>, // an override that couldn't be used, the built in copy was
+}
+
 /// struct for tracking database status
 pub struct DeadballDatabases {
     pub loaded: bool, // flag for loading database (reset button sets to false)
@@ -33,6 +89,8 @@ pub struct DeadballDatabases {
     pub backgrounds: Vec<String>,
     pub park1: Vec<String>,
     pub park2: Vec<String>,
+    pub search_path: Vec<PathBuf>, // folders checked for overrides, first match wins
+    pub reports: Vec<DatabaseReport>,
 }
 
 impl Default for DeadballDatabases {
@@ -57,49 +115,175 @@ impl Default for DeadballDatabases {
             backgrounds: vec!["Background".to_string()],
             park1: vec!["Park1".to_string()],
             park2: vec!["Park2".to_string()],
+            search_path: vec![],
+            reports: vec![],
         }
     }
 }
 
+/// error for a malformed line in a player/team/ballpark file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: String,
+    pub line: usize, // starts at 1, 0 means the file couldn't be read at all
+    pub field: String,
+    pub value: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(file: &str, line: usize, field: &str, value: &str, message: &str) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            field: field.to_string(),
+            value: value.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// known field with a value we don't recognize
+    pub fn bad_value(file: &str, line: usize, field: &str, value: &str) -> Self {
+        Self::new(file, line, field, value, "unrecognized value")
+    }
+
+    /// file couldn't be opened/read
+    pub fn io(file: &str, err: std::io::Error) -> Self {
+        Self::new(file, 0, "", "", &err.to_string())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.file, self.message)
+        } else if self.field.is_empty() && self.value.is_empty() {
+            write!(f, "{} line {}: {}", self.file, self.line, self.message)
+        } else if self.field.is_empty() {
+            write!(
+                f,
+                "{} line {}: {} '{}'",
+                self.file, self.line, self.message, self.value
+            )
+        } else {
+            write!(
+                f,
+                "{} line {}: {} for {}: '{}'",
+                self.file, self.line, self.message, self.field, self.value
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /* FUNCTIONS */
 
 /// basic csv read function, useful for reading name databases, etc.
 pub fn load_csv(filename: &str, delimiter: &str) -> Result<Vec<String>, std::io::Error> {
-    let read_result = fs::read_to_string(filename);
-    match read_result {
-        Ok(raw_text) => {
-            // separate text into vector elements and return
-            let mut result: Vec<String> = raw_text.split(delimiter).map(str::to_string).collect();
-            // need to check for "empty" entries
-            result.retain(|x| !x.is_empty());
-            // trim whitespace, can't figure out how to do it during collection
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..result.len() {
-                result[i] = result[i].trim().to_string();
+    let raw_text = fs::read_to_string(filename)?;
+    Ok(parse_csv(&raw_text, delimiter))
+}
+
+/// splits csv text into trimmed entries, skipping empty ones
+pub fn parse_csv(raw_text: &str, delimiter: &str) -> Vec<String> {
+    raw_text
+        .split(delimiter)
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// splits one csv line on commas outside of double quotes ("Washington, D.C."), a doubled quote
+/// inside quotes is a literal quote
+pub fn split_csv_line(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
             }
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    values.push(current);
+    values.iter().map(|v| v.trim().to_string()).collect()
+}
+
+/// quotes a value for a csv line if it has a comma or quote in it, the reverse of split_csv_line
+pub fn quote_csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
 
-            Ok(result)
+/// folders checked for database overrides, in order - the DEADBALL_DATABASES folder, the league
+/// folder's databases and then the one in the user's config directory
+pub fn database_search_path(data_dir: &str) -> Vec<PathBuf> {
+    let mut search = vec![];
+    if let Some(dir) = std::env::var_os(DATABASE_ENV).filter(|dir| !dir.is_empty()) {
+        search.push(PathBuf::from(dir));
+    }
+    search.push(Path::new(data_dir).join(DATABASE_FOLDER));
+    if let Some(dir) = config_dir() {
+        search.push(dir.join(DATABASE_FOLDER));
+    }
+    search
+}
+
+/// reads one database, the first copy of [file] on the search path or the built in one
+pub fn load_database(file: &str, search: &[PathBuf]) -> (Vec<String>, DatabaseReport) {
+    let mut report = DatabaseReport {
+        file: file.to_string(),
+        source: DatabaseSource::Embedded,
+        entries: 0,
+        error: None,
+    };
+    if let Some(path) = search
+        .iter()
+        .map(|dir| dir.join(file))
+        .find(|p| p.is_file())
+    {
+        match load_csv(&path.display().to_string(), "\n") {
+            Ok(entries) if !entries.is_empty() => {
+                report.entries = entries.len();
+                report.source = DatabaseSource::File(path);
+                return (entries, report);
+            }
+            Ok(_) => report.error = Some(format!("{} is empty", path.display())),
+            Err(e) => report.error = Some(format!("{}: {}", path.display(), e)),
         }
-        Err(err) => Err(err),
     }
+    let entries = EMBEDDED_DATABASES
+        .iter()
+        .find(|(name, _)| *name == file)
+        .map(|(_, text)| parse_csv(text, "\n"))
+        .unwrap_or_default();
+    report.entries = entries.len();
+    (entries, report)
 }
 
-/// function to load databases when program launches
-pub fn load_databases(toasts: &mut Toasts) -> DeadballDatabases {
+/// function to load databases when program launches, overrides that can't be read are reported
+/// with a toast and the built in copy is used instead
+pub fn load_databases(toasts: &mut Toasts, search: &[PathBuf]) -> DeadballDatabases {
     let mut database = DeadballDatabases {
         loaded: true, // this way databases won't be read again until manual reset
+        search_path: search.to_vec(),
         ..Default::default()
     };
-    // this way databases won't be read until again until manual reset
-    match load_csv("src/databases/firstname.csv", "\n") {
-        Ok(a) => {
-            database.first_names = a;
-            database.status_first_names = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load first name DB:\n{}", e);
+    let mut load = |file: &str| -> (Vec<String>, bool) {
+        let (entries, report) = load_database(file, search);
+        if let Some(err) = &report.error {
             toasts.add(Toast {
-                text: e_str.into(),
+                text: format!("Failed to load {}, using the built in one:\n{}", file, err).into(),
                 kind: ToastKind::Info,
                 style: ToastStyle::default(),
                 options: ToastOptions::default()
@@ -108,159 +292,186 @@ pub fn load_databases(toasts: &mut Toasts) -> DeadballDatabases {
                     .show_icon(true),
             });
         }
-    }
+        database.reports.push(report);
+        let loaded = !entries.is_empty();
+        (entries, loaded)
+    };
+    let (first_names, status_first_names) = load("firstname.csv");
+    let (last_names, status_last_names) = load("lastname.csv");
+    let (logos, status_logos) = load("logo.csv");
+    let (mascots, status_mascot) = load("mascot.csv");
+    let (mottos, status_motto) = load("motto.csv");
+    let (personalities, status_personalities) = load("personality.csv");
+    let (backgrounds, status_backgrounds) = load("background.csv");
+    let (park1, status_park1) = load("park1.csv");
+    let (park2, status_park2) = load("park2.csv");
 
-    match load_csv("src/databases/lastname.csv", "\n") {
-        Ok(a) => {
-            database.last_names = a;
-            database.status_last_names = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load last name DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+    DeadballDatabases {
+        status_first_names,
+        status_last_names,
+        status_logos,
+        status_mascot,
+        status_motto,
+        status_personalities,
+        status_backgrounds,
+        status_park1,
+        status_park2,
+        first_names,
+        last_names,
+        logos,
+        mascots,
+        mottos,
+        personalities,
+        backgrounds,
+        park1,
+        park2,
+        ..database
     }
+}
 
-    match load_csv("src/databases/logo.csv", "\n") {
-        Ok(a) => {
-            database.logos = a;
-            database.status_logos = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load logo DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+/// turns a player/team/ballpark reference from a file into a path
+/// references can be an ID (file name without extension inside [data_dir]/[folder]) or an old
+/// style file path - if the path doesn't exist anymore (league folder was moved, absolute path
+/// from another machine, etc.) look for a file with the same name in [data_dir]/[folder]
+pub fn resolve_reference(
+    reference: &str,
+    data_dir: &Path,
+    folder: &str,
+    extension: &str,
+) -> PathBuf {
+    let path = Path::new(reference.trim());
+    // NOTE: can't just check for any extension, IDs like "James_St. Claire" have a period
+    let is_path = [extension, "json", "toml"]
+        .iter()
+        .any(|ext| path.extension().map(|e| e == *ext) == Some(true));
+    if !is_path {
+        // IDs can point at any of the supported formats, the legacy one wins if there are several
+        let by_id = |ext: &str| {
+            data_dir
+                .join(folder)
+                .join(format!("{}.{}", reference.trim(), ext))
+        };
+        return [extension, "json", "toml"]
+            .iter()
+            .map(|ext| by_id(ext))
+            .find(|candidate| candidate.exists())
+            .unwrap_or_else(|| by_id(extension));
     }
-
-    match load_csv("src/databases/mascot.csv", "\n") {
-        Ok(a) => {
-            database.mascots = a;
-            database.status_mascot = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load mascot DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+    if path.exists() {
+        return path.to_path_buf();
     }
-
-    match load_csv("src/databases/motto.csv", "\n") {
-        Ok(a) => {
-            database.mottos = a;
-            database.status_motto = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load motto DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
+    if let Some(file_name) = path.file_name() {
+        let moved = data_dir.join(folder).join(file_name);
+        if moved.exists() {
+            return moved;
         }
     }
+    data_dir.join(path)
+}
 
-    match load_csv("src/databases/personality.csv", "\n") {
-        Ok(a) => {
-            database.personalities = a;
-            database.status_personalities = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load personality DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+/// ID for files that were written before IDs existed - just the file name without extension
+pub fn id_from_path(path: &Path) -> String {
+    match path.file_stem() {
+        Some(stem) => stem.to_string_lossy().to_string(),
+        None => "".to_string(),
     }
+}
 
-    match load_csv("src/databases/background.csv", "\n") {
-        Ok(a) => {
-            database.backgrounds = a;
-            database.status_backgrounds = true;
+/// splits a *.dbp/*.dbt/*.dbb file into (line number, field, value)
+/// blank lines and "## HEADER" lines are skipped, a missing trailing newline is fine
+pub fn split_fields<'a>(
+    contents: &'a str,
+    file: &str,
+) -> Result<Vec<(usize, &'a str, &'a str)>, ParseError> {
+    let mut fields = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
         }
-        Err(e) => {
-            let e_str = format!("Failed to load background DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
+        // only split on the first colon, mottos etc. can have more
+        match trimmed.split_once(':') {
+            Some((field, value)) => fields.push((i + 1, field.trim(), value.trim())),
+            None => {
+                return Err(ParseError::new(
+                    file,
+                    i + 1,
+                    "",
+                    trimmed,
+                    "missing ':' in line",
+                ))
+            }
         }
     }
 
-    match load_csv("src/databases/park1.csv", "\n") {
-        Ok(a) => {
-            database.park1 = a;
-            database.status_park1 = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load Park1 DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+    Ok(fields)
+}
+
+/// parses a number field, reporting the line on failure
+pub fn parse_number<T: FromStr>(
+    file: &str,
+    line: usize,
+    field: &str,
+    value: &str,
+) -> Result<T, ParseError> {
+    value
+        .parse()
+        .map_err(|_| ParseError::new(file, line, field, value, "expected a number"))
+}
+
+/// comma separated list field ("Traits: P+, S+,"), empty entries are dropped
+pub fn split_list(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// which format a file is in, based on its extension
+pub fn data_format(file: &str) -> DataFormat {
+    let extension = Path::new(file)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("json") => DataFormat::Json,
+        Some("toml") => DataFormat::Toml,
+        _ => DataFormat::Legacy,
     }
+}
 
-    match load_csv("src/databases/park2.csv", "\n") {
-        Ok(a) => {
-            database.park2 = a;
-            database.status_park2 = true;
-        }
-        Err(e) => {
-            let e_str = format!("Failed to load Park2 DB:\n{}", e);
-            toasts.add(Toast {
-                text: e_str.into(),
-                kind: ToastKind::Info,
-                style: ToastStyle::default(),
-                options: ToastOptions::default()
-                    .duration_in_seconds(5.0)
-                    .show_progress(true)
-                    .show_icon(true),
-            });
-        }
+/// reads a JSON/TOML player/team/ballpark, errors keep the line number where possible
+pub fn deserialize_data<T: DeserializeOwned>(
+    contents: &str,
+    file: &str,
+    format: DataFormat,
+) -> Result<T, ParseError> {
+    match format {
+        DataFormat::Json => serde_json::from_str(contents)
+            .map_err(|err| ParseError::new(file, err.line(), "", "", &err.to_string())),
+        DataFormat::Toml => toml::from_str(contents).map_err(|err| {
+            let line = match err.span() {
+                Some(span) => contents[..span.start].matches('\n').count() + 1,
+                None => 0,
+            };
+            ParseError::new(file, line, "", "", err.message())
+        }),
+        DataFormat::Legacy => Err(ParseError::new(file, 0, "", "", "not a JSON or TOML file")),
     }
+}
 
-    database
+/// writes a JSON/TOML player/team/ballpark
+pub fn serialize_data<T: Serialize>(
+    data: &T,
+    filename: &str,
+    format: DataFormat,
+) -> Result<(), std::io::Error> {
+    let text = match format {
+        DataFormat::Json => serde_json::to_string_pretty(data).map_err(std::io::Error::other)?,
+        DataFormat::Toml => toml::to_string_pretty(data).map_err(std::io::Error::other)?,
+        DataFormat::Legacy => {
+            return Err(std::io::Error::other("not a JSON or TOML file"));
+        }
+    };
+    fs::write(filename, text)
 }
@@ -1,14 +1,21 @@
 /*========================================================
 MODULE INCLUSIONS
 ========================================================*/
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
 use text_colorizer::*;
 
 use crate::characters::ballparks::BallparkModern;
 use crate::characters::{players::*, teams::*};
-use crate::core::roll;
 use crate::gui::debug::{combined_roll, DebugConfig};
 
+use super::actions::{Action, ActionError};
+use super::bases::Bases;
 use super::bo_wrap;
+use super::invariants::debug_check_invariants;
+use super::retrosheet::{record_play, PlayKind, PlayRecord};
+use super::stats::{record_plate_appearance, BoxScore};
 
 /*========================================================
 ENUM DEFINITIONS
@@ -41,7 +48,8 @@ pub enum Outs {
 }
 
 // each number is base binary (1 is runner on, 0 is no runner)
-#[derive(Debug, Clone, PartialEq)]
+// NOTE: the runners themselves live in Bases, this is just the pattern (see Bases::occupancy())
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RunnersOn {
     Runner000,
     Runner100,
@@ -60,7 +68,7 @@ pub enum GameStatus {
     Over,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StealType {
     Second,
     Third,
@@ -93,8 +101,8 @@ pub enum Oddity {
 }
 */
 
-/*
-// d20
+// d20, see hit_table_entry()
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HitTable {
     Single,
     SingleDef1B,
@@ -109,6 +117,16 @@ pub enum HitTable {
     HomeRun,
 }
 
+// d12, see defense_entry()
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Defense {
+    Error,
+    NoChange,
+    DoubleToSingle, // hit level drops by one, singles become outs
+    HitToOut,
+}
+
+/*
 // last digit of swing result
 pub enum OutType {
     K,
@@ -128,14 +146,6 @@ pub enum Stealing {
     RunnerOut,
     RunnerSafe,
 }
-
-// d12
-pub enum Defense {
-    Error,
-    NoChange,
-    DoubleToSingle,
-    HitToOut,
-}
 */
 
 #[derive(Debug)]
@@ -164,6 +174,7 @@ pub struct GameModern {
     pub away_active: ActiveTeam,
     pub ballpark: BallparkModern,
     pub oddity: bool, // enables oddity roll (option rule)
+    pub quiet: bool,  // no console output, for batch simulations
 }
 
 #[derive(Debug, Clone)]
@@ -172,13 +183,35 @@ pub struct GameState {
     pub inning: u32,
     pub inning_half: InningTB,
     pub outs: Outs,
-    pub runners: RunnersOn,
-    pub runner1: Option<Player>,
-    pub runner2: Option<Player>,
-    pub runner3: Option<Player>,
+    pub bases: Bases,
     pub home_state: TeamState,
     pub away_state: TeamState,
     pub game_text: String,
+    pub box_score: BoxScore,
+    pub plays: Vec<PlayRecord>, // play by play log for exports
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl GameState {
+    /// adds [runs] to the batting team's line for the current inning
+    pub fn score(&mut self, runs: u32) {
+        let inning = (self.inning - 1) as usize;
+        match self.inning_half {
+            InningTB::Top => self.away_state.runs[inning] += runs,
+            InningTB::Bottom => self.home_state.runs[inning] += runs,
+        }
+    }
+
+    /// moves the batting team's order on to the next batter
+    pub fn next_batter(&mut self) {
+        let team = match self.inning_half {
+            InningTB::Top => &mut self.away_state,
+            InningTB::Bottom => &mut self.home_state,
+        };
+        team.current_batter = bo_wrap(team.current_batter, 1, false) as u32;
+    }
 }
 
 //======== CUSTOM ERRORS =================================
@@ -229,52 +262,36 @@ pub fn at_bat(bat_target: i32, on_base_target: i32, mss_result: i32, oddity: boo
     at_bat_result
 }
 
+/// checks a team has what create_modern_game() needs, [label] starts the error message
+pub fn check_modern_roster(team: &Team, label: &str) -> Result<(), TeamError> {
+    let mut problem = None;
+    if team.roster.len() < 8 {
+        problem = Some(format!("{} does not have a complete roster", label));
+    } else if team.era != Era::Modern {
+        problem = Some(format!("{} is not for the modern era", label));
+    } else if team.pitcher.is_empty() {
+        problem = Some(format!("{} does not have a starting pitcher", label));
+    }
+    match problem {
+        Some(message) => Err(TeamError {
+            message,
+            team: team.name.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
 /// creates a GameModern struct
 pub fn create_modern_game(
     home: Team,
     away: Team,
     ballpark: BallparkModern,
     oddity: bool,
+    data_dir: &Path,
 ) -> Result<GameModern, TeamError> {
     // check teams and park for complete information
-    if home.roster.len() < 8 {
-        println!(
-            "{}",
-            "Home team does not have a complete roster".red().bold()
-        );
-        return Err(TeamError {
-            message: "Home team does not have a complete roster".to_string(),
-            team: home.name.clone(),
-        });
-    }
-    if away.roster.len() < 8 {
-        println!(
-            "{}",
-            "Away team does not have a complete roster".red().bold()
-        );
-        return Err(TeamError {
-            message: "Away team does not have a complete roster".to_string(),
-            team: away.name.clone(),
-        });
-    }
-    match home.era {
-        Era::Modern => (),
-        _ => {
-            return Err(TeamError {
-                message: "Home team is not for the modern era".to_string(),
-                team: home.name.clone(),
-            })
-        }
-    }
-    match away.era {
-        Era::Modern => (),
-        _ => {
-            return Err(TeamError {
-                message: "Away team is not for the modern era".to_string(),
-                team: away.name.clone(),
-            })
-        }
-    }
+    check_modern_roster(&home, "Home team")?;
+    check_modern_roster(&away, "Away team")?;
     // initialize structs and then push
     let mut home_active = ActiveTeam {
         roster: vec![],
@@ -288,7 +305,15 @@ pub fn create_modern_game(
         home_active.bench,
         home_active.pitching,
         home_active.bullpen,
-    ) = load_roster(&home);
+    ) = match load_roster(&home, data_dir) {
+        Ok(roster) => roster,
+        Err(err) => {
+            return Err(TeamError {
+                message: format!("Failed to load player: {}", err),
+                team: home.name.clone(),
+            })
+        }
+    };
     /*
     // try to load all the players, return error if it fails
     for i in 0..home.roster.len() {
@@ -350,7 +375,15 @@ pub fn create_modern_game(
         away_active.bench,
         away_active.pitching,
         away_active.bullpen,
-    ) = load_roster(&away);
+    ) = match load_roster(&away, data_dir) {
+        Ok(roster) => roster,
+        Err(err) => {
+            return Err(TeamError {
+                message: format!("Failed to load player: {}", err),
+                team: away.name.clone(),
+            })
+        }
+    };
     /*
     for i in 0..away.roster.len() {
         let read_results = fs::read_to_string(&away.roster[i]);
@@ -406,24 +439,24 @@ pub fn create_modern_game(
         home_active,
         away_active,
         oddity,
+        quiet: false,
     };
 
     Ok(game)
 }
 
 /// call to enter core game logic loop
-pub fn modern_game_flow(game: &GameModern, mut state: GameState, debug: DebugConfig) -> GameState {
-    // check top of the 9th at a different place
-    if state.inning > 9 {
-        // check score
-        if state.home_state.runs != state.away_state.runs {
-            state.status = GameStatus::Over;
-        }
-    }
+pub fn modern_game_flow(
+    game: &GameModern,
+    mut state: GameState,
+    debug: &mut DebugConfig,
+) -> GameState {
     match state.status {
         GameStatus::NotStarted => {
             state.status = GameStatus::Ongoing;
-            println!("Play ball!");
+            if !game.quiet {
+                println!("Play ball!");
+            }
             state.game_text += "\nPlay ball!";
         }
         GameStatus::Ongoing => match state.inning_half {
@@ -433,10 +466,7 @@ pub fn modern_game_flow(game: &GameModern, mut state: GameState, debug: DebugCon
                         // clean up game state, reset for new inning
                         state.inning_half = InningTB::Bottom;
                         state.outs = Outs::None;
-                        state.runners = RunnersOn::Runner000;
-                        state.runner1 = None;
-                        state.runner2 = None;
-                        state.runner3 = None;
+                        state.bases.clear();
                         state.game_text += "\nTop of the inning over.";
                         // create next inning element in run/hit/error arrays
                         state.away_state.runs.push(0);
@@ -452,17 +482,29 @@ pub fn modern_game_flow(game: &GameModern, mut state: GameState, debug: DebugCon
                 match state.outs {
                     Outs::Three => {
                         state.inning_half = InningTB::Top;
-                        state.runners = RunnersOn::Runner000;
-                        state.runner1 = None;
-                        state.runner2 = None;
-                        state.runner3 = None;
+                        state.bases.clear();
                         state.outs = Outs::None; // reset outs
                         state.game_text += "\nBottom of the inning over.";
                         // create next inning element in run/hit/error arrays
                         state.home_state.runs.push(0);
                         state.home_state.hits.push(0);
                         state.home_state.errors.push(0);
-                        state.inning += 1;
+                        // only a full inning can end the game, check totals not the inning lines
+                        let home_runs = state.home_state.runs.iter().sum::<u32>();
+                        let away_runs = state.away_state.runs.iter().sum::<u32>();
+                        if state.inning >= 9 && home_runs != away_runs {
+                            state.status = GameStatus::Over;
+                            if !game.quiet {
+                                println!("FINAL SCORE");
+                                println!("HOME: {} - AWAY: {}", home_runs, away_runs);
+                            }
+                            state.game_text += &format!(
+                                "\nThat's game!  Final score: {} - {}",
+                                home_runs, away_runs
+                            );
+                        } else {
+                            state.inning += 1;
+                        }
                     }
                     _ => {
                         state = modern_inning_flow(game, state, debug);
@@ -472,20 +514,10 @@ pub fn modern_game_flow(game: &GameModern, mut state: GameState, debug: DebugCon
         },
         GameStatus::Over => {
             // TODO: score report pop up window
-            state.inning -= 1; // offsets inning tick up at end of inning flow function
-            println!("FINAL SCORE");
-            println!(
-                "HOME: {} - AWAY: {}",
-                state.home_state.runs.iter().sum::<u32>(),
-                state.away_state.runs.iter().sum::<u32>()
-            );
-            state.game_text += &format!(
-                "\nThat's game!  Final score: {} - {}",
-                state.home_state.runs.iter().sum::<u32>(),
-                state.away_state.runs.iter().sum::<u32>()
-            );
+            // nothing left to play, the final score was posted when the last inning ended
         }
     }
+    debug_check_invariants(&state, "modern_game_flow");
 
     state
 }
@@ -494,8 +526,9 @@ pub fn modern_game_flow(game: &GameModern, mut state: GameState, debug: DebugCon
 pub fn modern_inning_flow(
     game: &GameModern,
     mut state: GameState,
-    mut debug: DebugConfig,
+    debug: &mut DebugConfig,
 ) -> GameState {
+    let before = state.clone(); // for the box score
     let (off, os, ds) = match state.inning_half {
         InningTB::Top => {
             let off = &game.away_active;
@@ -519,7 +552,7 @@ pub fn modern_inning_flow(
             let batter = off.batting_order[os.current_batter as usize].clone();
             let mut pd = ds.current_pitcher.pitch_die;
             // NOTE: special rules for GB+
-            if state.runners == RunnersOn::Runner111 {
+            if state.bases.count() == 3 {
                 pd = change_pitch_die(pd, 1);
             }
             // NOTE: handedness check
@@ -533,23 +566,16 @@ pub fn modern_inning_flow(
             }
             let control_mod = ds.current_pitcher.control();
             let pitch_result = if pd > 0 {
-                combined_roll(&mut debug, pd)
+                combined_roll(debug, pd, "pitch")
             } else {
-                -combined_roll(&mut debug, pd.abs())
+                -combined_roll(debug, pd.abs(), "pitch")
             };
             state.game_text += &format!("\n\nPitch result: {}", &pitch_result);
-            let mss_result = pitch_result + combined_roll(&mut debug, 100);
+            let mss_result = pitch_result + combined_roll(debug, 100, "swing");
             let mut hit_mod: i32 = 0;
-            if batter.free_swing() {
-                match state.runners {
-                    RunnersOn::Runner010 => hit_mod = -3,
-                    RunnersOn::Runner001 => hit_mod = -3,
-                    RunnersOn::Runner110 => hit_mod = -3,
-                    RunnersOn::Runner101 => hit_mod = -3,
-                    RunnersOn::Runner011 => hit_mod = -3,
-                    RunnersOn::Runner111 => hit_mod = -3,
-                    _ => hit_mod = 0,
-                }
+            // runners in scoring position
+            if batter.free_swing() && (state.bases.is_occupied(2) || state.bases.is_occupied(3)) {
+                hit_mod = -3;
             }
             state.game_text += &format!("\nMSS: {}", &mss_result);
             let swing_result = at_bat(
@@ -561,37 +587,38 @@ pub fn modern_inning_flow(
             state.game_text += &format!(" -> {:?}", swing_result);
             os.current_batter = bo_wrap(os.current_batter, 1, false) as u32;
 
+            let mut hit_bases = 0;
             match swing_result {
                 AtBatResults::Oddity => {
                     let oddity_result =
-                        combined_roll(&mut debug, 10) + combined_roll(&mut debug, 10);
+                        combined_roll(debug, 10, "oddity") + combined_roll(debug, 10, "oddity");
                     state.game_text += &format!("\n Oddity roll: {}", &oddity_result);
-                    state = oddity(&mut debug, &oddity_result, &pitch_result, game, state);
+                    state = oddity(debug, &oddity_result, &pitch_result, game, state);
                 }
                 AtBatResults::CriticalHit => {
                     // make hit roll, bump up a level
                     let mut hit_result =
-                        combined_roll(&mut debug, 20) + pow_trait_check(game, &state);
+                        combined_roll(debug, 20, "critical hit") + pow_trait_check(game, &state);
                     state.game_text += &format!("\nCrit hit roll: {}", &hit_result);
                     hit_result = crit_hit(&hit_result);
-                    state = hit_table(&hit_result, state, game, &mut debug, true);
+                    (state, hit_bases) = hit_table(&hit_result, state, game, debug, true);
                 }
                 AtBatResults::Hit => {
                     // hit roll
-                    let hit_result = combined_roll(&mut debug, 20) + pow_trait_check(game, &state);
+                    let hit_result =
+                        combined_roll(debug, 20, "hit") + pow_trait_check(game, &state);
                     state.game_text += &format!("\nHit roll: {}", &hit_result);
-                    state = hit_table(&hit_result, state, game, &mut debug, false);
+                    (state, hit_bases) = hit_table(&hit_result, state, game, debug, false);
                 }
                 AtBatResults::Walk => {
                     // basically like a single, just don't update the hit values
                     state.game_text += "\n Walk.";
-                    let batter = off.batting_order[bo_wrap(os.current_batter, 2, true)].clone();
                     state = walk_advance(state);
-                    state = add_runner(state, &1, batter);
+                    state = add_runner(state, &1, batter.clone());
                 }
                 AtBatResults::PossibleError => {
                     state = possible_error(
-                        &mut debug,
+                        debug,
                         state,
                         game,
                         position_by_number(get_swing_position(&mss_result)),
@@ -601,11 +628,7 @@ pub fn modern_inning_flow(
                     state = productive_out1(state, &mss_result);
                 }
                 AtBatResults::ProductiveOut2 => {
-                    // NOTE: in case you forget the reason for the -2 again:
-                    // -1 for arrays start at 0, -1 since current batter was already
-                    // incremented by this point
-                    let batter = off.batting_order[bo_wrap(os.current_batter, 2, true)].clone();
-                    state = productive_out2(state, &mss_result, batter);
+                    state = productive_out2(state, &mss_result, batter.clone());
                 }
                 AtBatResults::Out => {
                     state = actual_out(state, &mss_result);
@@ -614,6 +637,21 @@ pub fn modern_inning_flow(
                     state = mega_out(state);
                 }
             }
+            state = record_plate_appearance(
+                &before,
+                state,
+                &batter,
+                &swing_result,
+                &mss_result,
+                hit_bases,
+            );
+            state = record_play(
+                &before,
+                state,
+                PlayKind::AtBat(swing_result),
+                Some(&batter),
+                &mss_result,
+            );
 
             state
         }
@@ -647,7 +685,7 @@ pub fn oddity(
     if *oddity_result == 2 {
         if pitch_result % 2 == 1 {
             // fan catches sure out, at bat continues
-            *batting_order -= 1;
+            *batting_order = bo_wrap(*batting_order, 1, true) as u32;
             state.game_text += "\nFan catches a sure out, at bat continues!";
         } else {
             // home run overturned, batter out
@@ -656,40 +694,45 @@ pub fn oddity(
         }
     } else if *oddity_result == 3 {
         // animal on the field
-        println!("{}", "Animal on the field!".bold().yellow());
+        if !game.quiet {
+            println!("{}", "Animal on the field!".bold().yellow());
+        }
         let animal = animal(debug);
         state.game_text += &format!("\n{:?} on the field!  [development]", animal);
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 4 {
         // rain delay
-        println!("{}", "Rain delay.".bold().cyan());
-        let delay = roll(100) + roll(100);
+        if !game.quiet {
+            println!("{}", "Rain delay.".bold().cyan());
+        }
+        let delay =
+            combined_roll(debug, 100, "rain delay") + combined_roll(debug, 100, "rain delay");
         state.game_text += &format!("\nRain delay for {} minutes.", delay);
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 5 {
         // player injured
         state.game_text += "\nPlayer injured!  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 6 {
         // pitcher appears injured
         state.game_text += "\nPitcher inured!  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 7 {
         // TOOTBLAN
         state.game_text += "\nTOOTBLAN [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 8 {
         // pick off
         state.game_text += "\nPick off!  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 9 {
         // call blown at first
         state.game_text += "\nCall blown at first [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 10 {
         // call blown at home
         state.game_text += "\nCall blown at home [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 11 {
         // hit by pitch
         state.game_text += "\nHit by pitch!";
@@ -698,45 +741,44 @@ pub fn oddity(
     } else if *oddity_result == 12 {
         // wild pitch
         state.game_text += "\nWild pitch! [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 13 {
         // pitcher distracted
         state.game_text += "\nPitcher distracted.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 14 {
         // dropped third strike
         state.game_text += "\nDropped 3rd strike.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 15 {
         // passed ball
         state.game_text += "\nPassed ball.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 16 {
         // current batter appears injured
         state.game_text += "\nCurrent batter appears injured.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 17 {
         // previous batter appears injured
         state.game_text += "\nPrevious batter appears injured.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 18 {
         // pitcher error
         state.game_text += "\nPitcher error.  [development]";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     } else if *oddity_result == 19 {
         // balk
         state.game_text += "\nBalk!";
         state = force_advance(state, 1);
         state = add_runner(state, &1, batter);
     } else if *oddity_result == 20 {
-        // catcher interference
-        *batting_order -= 1;
+        // catcher interference, the batter takes first so the order moves on like a walk
         state.game_text += "\nCatcher interference.";
         state = force_advance(state, 1);
         state = add_runner(state, &1, batter);
     } else {
         state.game_text += "\nYou shouldn't get here (oddity roll > 20 somehow)";
-        *batting_order -= 1;
+        *batting_order = bo_wrap(*batting_order, 1, true) as u32;
     }
 
     state
@@ -761,14 +803,33 @@ pub fn crit_hit(hit_result: &i32) -> i32 {
     crit_result
 }
 
-/// rolls on the hit table and updates game state accordingly
+/// which hit table entry a (modified) d20 hit roll lands on
+pub fn hit_table_entry(hit_result: i32) -> HitTable {
+    match hit_result {
+        i32::MIN..=2 => HitTable::Single,
+        3 => HitTable::SingleDef1B,
+        4 => HitTable::SingleDef2B,
+        5 => HitTable::SingleDef3B,
+        6 => HitTable::SingleDefSS,
+        7..=9 => HitTable::Single,
+        10..=14 => HitTable::SingleRunnersAdv,
+        15 => HitTable::DoubleDefLF,
+        16 => HitTable::DoubleDefCF,
+        17 => HitTable::DoubleDefRF,
+        18 => HitTable::DoubleRunnerAdv,
+        _ => HitTable::HomeRun,
+    }
+}
+
+/// rolls on the hit table and updates game state accordingly, also returns how many bases the
+/// batter's hit is credited for (0 when the defense takes the hit away)
 pub fn hit_table(
     hit_result: &i32,
     mut state: GameState,
     game: &GameModern,
     debug: &mut DebugConfig,
     is_crit: bool,
-) -> GameState {
+) -> (GameState, u32) {
     // 1. defense roll (if needed)
     // 2. advance runners
     // 3 move hitter to runner
@@ -779,10 +840,13 @@ pub fn hit_table(
             [bo_wrap(state.away_state.current_batter, 1, true)]
         .clone(),
         InningTB::Bottom => game.home_active.batting_order
-            [bo_wrap(state.away_state.current_batter, 1, true)]
+            [bo_wrap(state.home_state.current_batter, 1, true)]
         .clone(),
     };
-    if *hit_result <= 2 {
+    let entry = hit_table_entry(*hit_result);
+    // S+/C+ rules only apply to the 1-2 singles, not 7-9
+    if entry == HitTable::Single && *hit_result <= 2 {
+        let bases;
         if batter.speedy() {
             // NOTE: special rules for S+
             // on 1: batter doubles, runners advance 2, no DEF roll
@@ -791,10 +855,12 @@ pub fn hit_table(
                 state = runners_advance(state, &2);
                 state = add_runner(state, &2, batter);
                 state.game_text += " -> Double (S+)";
+                bases = 2;
             } else {
                 state = runners_advance(state, &3);
                 state = add_runner(state, &3, batter);
                 state.game_text += " -> Triple (S+)";
+                bases = 3;
             }
         } else {
             // NOTE: special rules for C+ (S+ is better if batter has both)
@@ -803,11 +869,13 @@ pub fn hit_table(
                 state = runners_advance(state, &2);
                 state = add_runner(state, &2, batter);
                 state.game_text += " -> Double (C+)";
+                bases = 2;
             } else {
                 state.game_text += " -> Single";
                 // single
                 state = runners_advance(state, &1);
                 state = add_runner(state, &1, batter);
+                bases = 1;
                 // simple hit increment when no defense roll involved
                 match state.inning_half {
                     InningTB::Top => {
@@ -820,8 +888,8 @@ pub fn hit_table(
             }
         }
 
-        state
-    } else if *hit_result == 3 {
+        (state, bases)
+    } else if entry == HitTable::SingleDef1B {
         // single DEF 1B
         state.game_text += " -> Single DEF 1B";
         let mut advance = 1;
@@ -837,14 +905,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Firstbase);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 4 {
+        return (state, credited_bases(1, base));
+    } else if entry == HitTable::SingleDef2B {
         state.game_text += " -> Single DEF 2B";
         // single DEF 2B
         let mut advance = 1;
@@ -858,14 +926,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Secondbase);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 5 {
+        return (state, credited_bases(1, base));
+    } else if entry == HitTable::SingleDef3B {
         state.game_text += " -> Single DEF 3B";
         // single DEF 3B
         let mut advance = 1;
@@ -879,14 +947,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Thirdbase);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 6 {
+        return (state, credited_bases(1, base));
+    } else if entry == HitTable::SingleDefSS {
         state.game_text += " -> Single DEF SS";
         // single DEF SS
         let mut advance = 1;
@@ -900,14 +968,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Shortstop);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result >= 7 && *hit_result <= 9 {
+        return (state, credited_bases(1, base));
+    } else if entry == HitTable::Single {
         state.game_text += " -> Single";
         // single
         state = runners_advance(state, &1);
@@ -920,8 +988,8 @@ pub fn hit_table(
                 state.home_state.hits[(state.inning - 1) as usize] += 1;
             }
         }
-        return state;
-    } else if *hit_result >= 10 && *hit_result <= 14 {
+        return (state, 1);
+    } else if entry == HitTable::SingleRunnersAdv {
         state.game_text += " -> Single, runners advance 2";
         // single, runners advance 2
         state = runners_advance(state, &2);
@@ -934,8 +1002,8 @@ pub fn hit_table(
                 state.home_state.hits[(state.inning - 1) as usize] += 1;
             }
         }
-        return state;
-    } else if *hit_result == 15 {
+        return (state, 1);
+    } else if entry == HitTable::DoubleDefLF {
         state.game_text += " -> Double DEF LF";
         // double DEF LF
         let mut advance = 2;
@@ -949,14 +1017,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Leftfield);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 16 {
+        return (state, credited_bases(2, base));
+    } else if entry == HitTable::DoubleDefCF {
         state.game_text += " -> Double, DEF CF";
         // double DEF CF
         let mut advance = 2;
@@ -970,14 +1038,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Centerfield);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 17 {
+        return (state, credited_bases(2, base));
+    } else if entry == HitTable::DoubleDefRF {
         state.game_text += " -> Double DEF RF";
         // double DEF RF
         let mut advance = 2;
@@ -991,14 +1059,14 @@ pub fn hit_table(
             }
         }
         if !is_crit {
-            let def_roll = combined_roll(debug, 12)
+            let def_roll = combined_roll(debug, 12, "defense")
                 + def_trait_check(&state.inning_half, game, Position::Rightfield);
             (state, advance, base) = defense(state, &def_roll, advance, base);
         }
         state = runners_advance(state, &advance);
         state = add_runner(state, &base, batter);
-        return state;
-    } else if *hit_result == 18 {
+        return (state, credited_bases(2, base));
+    } else if entry == HitTable::DoubleRunnerAdv {
         state.game_text += " -> Double, runners advance 3";
         // double, runners advance 3
         state = runners_advance(state, &3);
@@ -1011,24 +1079,12 @@ pub fn hit_table(
                 state.home_state.hits[(state.inning - 1) as usize] += 1;
             }
         }
-        return state;
-    } else if *hit_result >= 19 {
+        return (state, 2);
+    } else if entry == HitTable::HomeRun {
         state.game_text += " -> HOME RUN!";
         // home run
-        let mut runs = runnerson(&state);
-        runs += 1;
-        state.runners = RunnersOn::Runner000;
-        state.runner1 = None;
-        state.runner2 = None;
-        state.runner3 = None;
-        match state.inning_half {
-            InningTB::Top => {
-                state.away_state.runs[(state.inning - 1) as usize] += runs;
-            }
-            InningTB::Bottom => {
-                state.home_state.runs[(state.inning - 1) as usize] += runs;
-            }
-        }
+        let runs = state.bases.advance(4) + 1;
+        state.score(runs);
         match state.inning_half {
             InningTB::Top => {
                 state.away_state.hits[(state.inning - 1) as usize] += 1;
@@ -1037,9 +1093,29 @@ pub fn hit_table(
                 state.home_state.hits[(state.inning - 1) as usize] += 1;
             }
         }
-        return state;
+        return (state, 4);
+    } else {
+        return (state, 0);
+    }
+}
+
+/// bases credited for a [hit] base hit once the defense roll put the batter on [base] - reaching
+/// past the hit means the batter got there on an error, so no hit at all
+fn credited_bases(hit: u32, base: u32) -> u32 {
+    if base > hit {
+        0
     } else {
-        return state;
+        base
+    }
+}
+
+/// which defense table entry a (modified) d12 defense roll lands on
+pub fn defense_entry(def_result: i32) -> Defense {
+    match def_result {
+        i32::MIN..=2 => Defense::Error,
+        3..=9 => Defense::NoChange,
+        10..=11 => Defense::DoubleToSingle,
+        _ => Defense::HitToOut,
     }
 }
 
@@ -1051,7 +1127,8 @@ pub fn defense(
     mut base: u32,
 ) -> (GameState, u32, u32) {
     state.game_text += &format!("\n Defense roll: {}", def_result);
-    if *def_result <= 2 {
+    let entry = defense_entry(*def_result);
+    if entry == Defense::Error {
         state.game_text += " -> Error";
         // error, runners take an extra base
         // modify hit and error values
@@ -1067,11 +1144,11 @@ pub fn defense(
             }
         }
         (state, advance + 1, base + 1)
-    } else if *def_result >= 3 && *def_result <= 9 {
+    } else if entry == Defense::NoChange {
         state.game_text += " -> Normal";
         // no change
         (state, advance, base)
-    } else if *def_result >= 10 && *def_result <= 11 {
+    } else if entry == Defense::DoubleToSingle {
         state.game_text += " -> good defense, reduce hit level by 1";
         // double turns to single, runners advance 2, single turns to out, runners advance 1
         if base == 1 {
@@ -1083,7 +1160,7 @@ pub fn defense(
             advance = 2;
         }
         (state, advance, base)
-    } else if *def_result >= 12 {
+    } else if entry == Defense::HitToOut {
         state.game_text += " -> Out!  What a play, Runners hold.";
         // hit turned to out, runners hold
         state.outs = increment_out(state.outs, 1);
@@ -1103,292 +1180,24 @@ pub fn defense(
     }
 }
 /// advance runners function - handles base runners and scoring after a hit/etc.
-/// this function just clones state.runner1/2/3 so make sure you already have players in the right
-/// spot
-/// for now I think the best way is to handle advancing runners first, then add the batter after
+/// every runner moves up [advance_num] bases, add the batter after advancing the runners
 pub fn runners_advance(mut state: GameState, advance_num: &u32) -> GameState {
-    if *advance_num == 1 {
-        // move 1
-        match state.runners {
-            RunnersOn::Runner000 => {} // no runners on, don't do anything
-            RunnersOn::Runner100 => {
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-            }
-            RunnersOn::Runner010 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-            }
-            RunnersOn::Runner001 => {
-                // runner scores, clear bases and update box score
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner110 => {
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-                return state;
-            }
-            RunnersOn::Runner011 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner101 => {
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state.runner3 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner111 => {
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-        }
-    } else if *advance_num == 2 {
-        // move 2
-        match state.runners {
-            RunnersOn::Runner000 => {} // no runners on, don't do anything
-            RunnersOn::Runner100 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = None;
-            }
-            RunnersOn::Runner010 => {
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner001 => {
-                // runner scores, clear bases and update box score
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner110 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner011 => {
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                state.runner3 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 2;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 2;
-                    }
-                }
-            }
-            RunnersOn::Runner101 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        // away team at bat, update team 2 score
-                        state.away_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 1;
-                    }
-                }
-            }
-            RunnersOn::Runner111 => {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = None;
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => {
-                        state.away_state.runs[(state.inning - 1) as usize] += 2;
-                    }
-                    InningTB::Bottom => {
-                        state.home_state.runs[(state.inning - 1) as usize] += 2;
-                    }
-                }
-            }
-        }
-    } else if *advance_num == 3 {
-        // all runners score
-        let num_runners = runnerson(&state);
-        state.runners = RunnersOn::Runner000;
-        state.runner1 = None;
-        state.runner2 = None;
-        state.runner3 = None;
-        match state.inning_half {
-            InningTB::Top => {
-                // away team at bat, update team 2 score
-                state.away_state.runs[(state.inning - 1) as usize] += num_runners;
-            }
-            InningTB::Bottom => {
-                state.home_state.runs[(state.inning - 1) as usize] += num_runners;
-            }
-        }
-    }
+    let runs = state.bases.advance(*advance_num);
+    state.score(runs);
 
     state
 }
 
 /// gets number of runners on base
 pub fn runnerson(state: &GameState) -> u32 {
-    match state.runners {
-        RunnersOn::Runner000 => 0,
-        RunnersOn::Runner100 => 1,
-        RunnersOn::Runner010 => 1,
-        RunnersOn::Runner001 => 1,
-        RunnersOn::Runner110 => 2,
-        RunnersOn::Runner101 => 2,
-        RunnersOn::Runner011 => 2,
-        RunnersOn::Runner111 => 3,
-    }
+    state.bases.count()
 }
 
 /// function to put a hitter onto the bases
 /// clone the current batter from GameModern struct roster to put on base
 pub fn add_runner(mut state: GameState, base: &u32, batter: Player) -> GameState {
-    // certain conditions shouldn't come up ever, so just skip them
-    match state.runners {
-        RunnersOn::Runner000 => {
-            if *base == 1 {
-                state.runners = RunnersOn::Runner100;
-                state.runner1 = Some(batter);
-            } else if *base == 2 {
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = Some(batter);
-            } else if *base == 3 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = Some(batter);
-            }
-        }
-        RunnersOn::Runner100 => {
-            // TODO: is it even possible to get here? after advancing, no one should be on first
-            // skip 1 in this case
-            if *base == 2 {
-                state.runners = RunnersOn::Runner110;
-                state.runner2 = Some(batter);
-            } else if *base == 3 {
-                state.runners = RunnersOn::Runner101;
-                state.runner3 = Some(batter);
-            }
-        }
-        RunnersOn::Runner010 => {
-            // skip 2 in this case
-            if *base == 1 {
-                state.runners = RunnersOn::Runner110;
-                state.runner1 = Some(batter);
-            } else if *base == 3 {
-                // TODO: delete these unecessary branches
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = Some(batter);
-            }
-        }
-        RunnersOn::Runner001 => {
-            // skip 3
-            if *base == 1 {
-                state.runners = RunnersOn::Runner101;
-                state.runner1 = Some(batter);
-            } else if *base == 2 {
-                state.runners = RunnersOn::Runner011;
-                state.runner2 = Some(batter);
-            }
-        }
-        RunnersOn::Runner110 => {
-            // skip 1 and 2
-            if *base == 3 {
-                // TODO: delete
-                state.runners = RunnersOn::Runner111;
-                state.runner3 = Some(batter);
-            }
-        }
-        RunnersOn::Runner101 => {
-            // skip 1 and 3
-            if *base == 2 {
-                // TODO: delete
-                state.runners = RunnersOn::Runner111;
-                state.runner2 = Some(batter);
-            }
-        }
-        RunnersOn::Runner011 => {
-            // skip 2 and 3
-            if *base == 1 {
-                state.runners = RunnersOn::Runner111;
-                state.runner1 = Some(batter);
-            }
-        }
-        RunnersOn::Runner111 => {
-            // nothing to do on this one
-        }
-    }
+    // taken bases shouldn't come up ever (advance runners first), so just skip them
+    state.bases.place(*base, batter);
 
     state
 }
@@ -1403,7 +1212,8 @@ pub fn get_swing_position(mss_result: &i32) -> i32 {
 pub fn init_new_game_state(home_pitcher: Player, away_pitcher: Player) -> GameState {
     let home_state = TeamState {
         current_batter: 0,
-        current_pitcher: home_pitcher,
+        current_pitcher: home_pitcher.clone(),
+        starter: home_pitcher,
         innings_pitched: 0,
         runs: vec![0],
         hits: vec![0],
@@ -1411,7 +1221,8 @@ pub fn init_new_game_state(home_pitcher: Player, away_pitcher: Player) -> GameSt
     };
     let away_state = TeamState {
         current_batter: 0,
-        current_pitcher: away_pitcher,
+        current_pitcher: away_pitcher.clone(),
+        starter: away_pitcher,
         innings_pitched: 0,
         runs: vec![0],
         hits: vec![0],
@@ -1422,13 +1233,12 @@ pub fn init_new_game_state(home_pitcher: Player, away_pitcher: Player) -> GameSt
         inning: 1,
         inning_half: InningTB::Top,
         outs: Outs::None,
-        runners: RunnersOn::Runner000,
-        runner1: None,
-        runner2: None,
-        runner3: None,
+        bases: Bases::default(),
         home_state,
         away_state,
         game_text: "Game created.".to_string(),
+        box_score: BoxScore::default(),
+        plays: vec![],
     }
 }
 
@@ -1473,14 +1283,19 @@ pub fn position_by_number(mut last_digit: i32) -> Position {
 
 /// convenience function to return a default GameState struct
 pub fn new_game_state_struct() -> GameState {
-    let home_state = TeamState {
-        current_batter: 1,
-        current_pitcher: generate_player(
+    let pitcher = || {
+        generate_player(
             PlayerClass::Pitchers,
             Position::Pitcher,
             &["Seth".to_string()],
             &["Loveall".to_string()],
-        ),
+        )
+    };
+    let (home_pitcher, away_pitcher) = (pitcher(), pitcher());
+    let home_state = TeamState {
+        current_batter: 1,
+        current_pitcher: home_pitcher.clone(),
+        starter: home_pitcher,
         innings_pitched: 0,
         runs: vec![0],
         hits: vec![0],
@@ -1488,12 +1303,8 @@ pub fn new_game_state_struct() -> GameState {
     };
     let away_state = TeamState {
         current_batter: 1,
-        current_pitcher: generate_player(
-            PlayerClass::Pitchers,
-            Position::Pitcher,
-            &["Seth".to_string()],
-            &["Loveall".to_string()],
-        ),
+        current_pitcher: away_pitcher.clone(),
+        starter: away_pitcher,
         innings_pitched: 0,
         runs: vec![0],
         hits: vec![0],
@@ -1504,13 +1315,12 @@ pub fn new_game_state_struct() -> GameState {
         inning: 1,
         inning_half: InningTB::Top,
         outs: Outs::None,
-        runners: RunnersOn::Runner000,
-        runner1: None,
-        runner2: None,
-        runner3: None,
+        bases: Bases::default(),
         home_state,
         away_state,
         game_text: "Game created.".to_string(),
+        box_score: BoxScore::default(),
+        plays: vec![],
     }
 }
 
@@ -1544,8 +1354,8 @@ fn possible_error(
         }
     };
     state.game_text += "\n Possible error -> ";
-    let mut def_roll =
-        combined_roll(debug, 12) + def_trait_check(&state.inning_half, game, position);
+    let mut def_roll = combined_roll(debug, 12, "possible error")
+        + def_trait_check(&state.inning_half, game, position);
     def_roll += defender.defense();
     state.game_text += &format!("defense roll: {}", &def_roll);
     if def_roll <= 2 {
@@ -1570,7 +1380,7 @@ fn possible_error(
 }
 
 /// handles ProductiveOut1 swing result
-fn productive_out1(mut state: GameState, mss_result: &i32) -> GameState {
+pub fn productive_out1(mut state: GameState, mss_result: &i32) -> GameState {
     // if first or outfield, runners on 2nd and 3rd advance
     // if 2B/SS/3B, runner at first advances and batter is out
     match state.outs {
@@ -1582,90 +1392,23 @@ fn productive_out1(mut state: GameState, mss_result: &i32) -> GameState {
             state.game_text += "\nPossible productive out (type 1).";
             let fielder = get_swing_position(mss_result);
             if fielder == 3 || fielder >= 7 {
-                // check for runners on second and third
-                // advance if they exist
+                // runner at first doesn't move
                 state.game_text += "\nRunners on second and third advance.";
-                match state.runners {
-                    RunnersOn::Runner000 => {}
-                    RunnersOn::Runner100 => {}
-                    RunnersOn::Runner010 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner001 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner011 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner110 => {
-                        // can't use normal runners advance function because
-                        // runner at first doesn't move
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                    }
-                    RunnersOn::Runner101 => {
-                        state.runners = RunnersOn::Runner100;
-                        state.runner3 = None;
-                        match state.inning_half {
-                            InningTB::Top => {
-                                state.away_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                            InningTB::Bottom => {
-                                state.home_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                        }
-                    }
-                    RunnersOn::Runner111 => {
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                        match state.inning_half {
-                            InningTB::Top => {
-                                state.away_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                            InningTB::Bottom => {
-                                state.home_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                        }
-                    }
-                }
-            } else {
+                let runs = state.bases.advance_from(2, 1);
+                state.score(runs);
+            } else if state.bases.is_occupied(1) && !state.bases.is_occupied(2) {
                 let pitcher: &Player = match state.inning_half {
                     InningTB::Top => &state.home_state.current_pitcher,
                     InningTB::Bottom => &state.away_state.current_pitcher,
                 };
-                // check for runner on first
-                match state.runners {
-                    RunnersOn::Runner100 => {
-                        // NOTE: special rules for GB+ pitchers
-                        if pitcher.groundball() && fielder == 2 {
-                            state.game_text += "\nGB+ automatic double play.";
-                            state.runners = RunnersOn::Runner000;
-                            state.runner1 = None;
-                            state.outs = increment_out(state.outs, 1); // one extra
-                        } else {
-                            state.game_text += "\nRunner at first advances, batter is out.";
-                            state.runners = RunnersOn::Runner010;
-                            state.runner2 = state.runner1.clone();
-                            state.runner1 = None;
-                            state.outs = increment_out(state.outs, 1);
-                        }
-                    }
-                    RunnersOn::Runner101 => {
-                        // NOTE: special rules for GB+ pitchers
-                        if pitcher.groundball() && fielder == 2 {
-                            state.game_text += "\nGB+ automatic double play.";
-                            state.runners = RunnersOn::Runner001;
-                            state.runner1 = None;
-                        } else {
-                            state.game_text += "\nRunner at first advances, batter is out.";
-                            state.runners = RunnersOn::Runner011;
-                            state.runner2 = state.runner1.clone();
-                            state.runner1 = None;
-                        }
-                    }
-                    _ => {}
+                // NOTE: special rules for GB+ pitchers
+                if pitcher.groundball() && fielder == 2 {
+                    state.game_text += "\nGB+ automatic double play.";
+                    state.bases.remove(1);
+                    state.outs = increment_out(state.outs, 1); // one extra
+                } else {
+                    state.game_text += "\nRunner at first advances, batter is out.";
+                    state.bases.move_runner(1, 2);
                 }
             }
             // update out
@@ -1695,99 +1438,24 @@ fn productive_out2(mut state: GameState, mss_result: &i32, batter: Player) -> Ga
             let fielder = get_swing_position(mss_result);
             if fielder == 3 || fielder >= 7 {
                 state.game_text += "\nBall hit to 1B or OF, runners at 2nd and 3rd advance.";
-                match state.runners {
-                    RunnersOn::Runner000 => {}
-                    RunnersOn::Runner100 => {}
-                    RunnersOn::Runner010 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner001 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner011 => {
-                        state = runners_advance(state, &1);
-                    }
-                    RunnersOn::Runner110 => {
-                        // can't use normal runners advance function because
-                        // runner at first doesn't move
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                    }
-                    RunnersOn::Runner101 => {
-                        state.runners = RunnersOn::Runner100;
-                        state.runner3 = None;
-                        match state.inning_half {
-                            InningTB::Top => {
-                                state.away_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                            InningTB::Bottom => {
-                                state.home_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                        }
-                    }
-                    RunnersOn::Runner111 => {
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                        match state.inning_half {
-                            InningTB::Top => {
-                                state.away_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                            InningTB::Bottom => {
-                                state.home_state.runs[(state.inning - 1) as usize] += 1;
-                            }
-                        }
-                    }
-                }
-                //
+                let runs = state.bases.advance_from(2, 1);
+                state.score(runs);
             } else {
                 // advance batter to first and lead runner is out
                 // TODO: should this be done for force outs only
                 state.game_text += "\nFielder's choice.";
-                match state.runners {
-                    RunnersOn::Runner000 => {}
-                    RunnersOn::Runner100 => {
-                        // NOTE: special rules for GB+ pitchers
-                        if pitcher.groundball() && fielder == 2 {
-                            state.game_text += "\nGB+ automatic double play.";
-                            state.runners = RunnersOn::Runner000;
-                            state.runner1 = None;
-                            state.outs = increment_out(state.outs, 1); // 1 extra
-                        } else {
-                            state.runner1 = Some(batter);
-                        }
-                    }
-                    RunnersOn::Runner010 => {
-                        state.runners = RunnersOn::Runner100;
-                        state.runner2 = None;
-                        state.runner1 = Some(batter);
-                    }
-                    RunnersOn::Runner001 => {
-                        state.runners = RunnersOn::Runner100;
-                        state.runner3 = None;
-                        state.runner1 = Some(batter);
+                let first_and_second = state.bases.is_occupied(1) && state.bases.is_occupied(2);
+                if let (Some(lead), false) = (state.bases.lead_runner(), first_and_second) {
+                    // NOTE: special rules for GB+ pitchers
+                    if pitcher.groundball() && fielder == 2 && state.bases.is_occupied(1) {
+                        state.game_text += "\nGB+ automatic double play.";
+                        state.bases.remove(1);
+                        state.outs = increment_out(state.outs, 1); // 1 extra
+                    } else {
+                        state.bases.remove(lead);
+                        state.bases.advance(1);
+                        state.bases.place(1, batter);
                     }
-                    RunnersOn::Runner110 => {}
-                    RunnersOn::Runner011 => {
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                        state.runner1 = Some(batter);
-                    }
-                    RunnersOn::Runner101 => {
-                        // NOTE: special rules for GB+ pitchers
-                        if pitcher.groundball() && fielder == 2 {
-                            state.game_text += "\nGB+ automatic double play.";
-                            state.runners = RunnersOn::Runner001;
-                            state.runner1 = None;
-                            state.outs = increment_out(state.outs, 1); // 1 extra
-                        }
-                        state.runners = RunnersOn::Runner110;
-                        state.runner3 = None;
-                        state.runner1 = Some(batter);
-                    }
-                    RunnersOn::Runner111 => {}
                 }
             }
             state.outs = increment_out(state.outs, 1);
@@ -1810,33 +1478,12 @@ fn actual_out(mut state: GameState, mss_result: &i32) -> GameState {
             Outs::Two => {
                 state.outs = Outs::Three;
             }
-            _ => match state.runners {
-                RunnersOn::Runner100 => {
-                    state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-                    state.runners = RunnersOn::Runner000;
-                    state.runner1 = None;
-                    state.outs = increment_out(state.outs, 2);
-                }
-                RunnersOn::Runner110 => {
-                    state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-                    state.runners = RunnersOn::Runner010;
-                    state.runner1 = None;
-                    state.outs = increment_out(state.outs, 2);
-                }
-                RunnersOn::Runner101 => {
-                    state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-                    state.runners = RunnersOn::Runner001;
-                    state.runner1 = None;
-                    state.outs = increment_out(state.outs, 2);
-                }
-                RunnersOn::Runner111 => {
-                    state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-                    state.runners = RunnersOn::Runner011;
-                    state.runner1 = None;
-                    state.outs = increment_out(state.outs, 2);
-                }
-                _ => state.outs = increment_out(state.outs, 1),
-            },
+            _ if state.bases.is_occupied(1) => {
+                state.game_text += "\nDouble Play!  Runner at first and batter are out.";
+                state.bases.remove(1);
+                state.outs = increment_out(state.outs, 2);
+            }
+            _ => state.outs = increment_out(state.outs, 1),
         }
     } else {
         state.outs = increment_out(state.outs, 1);
@@ -1850,29 +1497,16 @@ fn mega_out(mut state: GameState) -> GameState {
     // triple play if no outs and runners on first and second
     // check for triple play, otherwise same as previous branch
     state.game_text += "\nOut!";
-    match state.runners {
-        RunnersOn::Runner110 => {
-            state.game_text += "\nTriple play!";
-            state.outs = Outs::Three;
-            // TODO: only say it's a triple play if no outs
-        }
-        RunnersOn::Runner111 => {
-            state.game_text += "\nTriple play!";
-            state.outs = Outs::Three;
-        }
-        RunnersOn::Runner100 => {
-            state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-            state.runners = RunnersOn::Runner000;
-            state.runner1 = None;
-            state.outs = increment_out(state.outs, 2);
-        }
-        RunnersOn::Runner101 => {
-            state.game_text += "\nDouble Play!  Runner at first and batter are out.";
-            state.runners = RunnersOn::Runner001;
-            state.runner1 = None;
-            state.outs = increment_out(state.outs, 2);
-        }
-        _ => state.outs = increment_out(state.outs, 1),
+    if state.bases.is_occupied(1) && state.bases.is_occupied(2) {
+        state.game_text += "\nTriple play!";
+        state.outs = Outs::Three;
+        // TODO: only say it's a triple play if no outs
+    } else if state.bases.is_occupied(1) {
+        state.game_text += "\nDouble Play!  Runner at first and batter are out.";
+        state.bases.remove(1);
+        state.outs = increment_out(state.outs, 2);
+    } else {
+        state.outs = increment_out(state.outs, 1);
     }
 
     state
@@ -1880,56 +1514,36 @@ fn mega_out(mut state: GameState) -> GameState {
 
 /// takes a game state and processes steals of the indicated type
 /// includes rules for S+/S-
-/// (!) assumes you have checked for valid steal scenarios before calling it
+/// fails without touching the game if the runners aren't there for [steal_type] (see check_bases())
 pub fn process_steals(
     steal_type: StealType,
     mut state: GameState,
-    mut debug: DebugConfig,
+    debug: &mut DebugConfig,
     catcher: &Player,
-) -> GameState {
+) -> Result<GameState, ActionError> {
+    state.check_bases(&Action::Steal(steal_type.clone()))?;
+    let before = state.clone();
     let catcher_mod = catcher.defense();
     match steal_type {
         StealType::Second => {
             let mut steal_mod = catcher_mod;
-            let stealer = state.runner1.clone().unwrap(); // TODO: error proof?
+            let stealer = state.bases.runner(1).cloned().expect("checked above");
             if stealer.speedy() {
                 steal_mod = 1;
             }
             if stealer.slow() {
                 steal_mod = -2;
             }
-            let steal_result = combined_roll(&mut debug, 8) + steal_mod;
+            let steal_result = combined_roll(debug, 8, "steal") + steal_mod;
 
             if steal_result > 3 {
                 // successful steal
-                match state.runners {
-                    RunnersOn::Runner100 => {
-                        state.runners = RunnersOn::Runner010;
-                        state.runner2 = state.runner1.clone();
-                        state.runner1 = None;
-                    }
-                    RunnersOn::Runner101 => {
-                        state.runners = RunnersOn::Runner011;
-                        state.runner2 = state.runner1.clone();
-                        state.runner1 = None;
-                    }
-                    _ => {} // only valid configurations
-                }
+                state.bases.move_runner(1, 2);
                 state.game_text +=
                     &format!("\n{} {} stole 2B!", stealer.first_name, stealer.last_name);
             } else {
                 // runner is out
-                match state.runners {
-                    RunnersOn::Runner100 => {
-                        state.runners = RunnersOn::Runner000;
-                        state.runner1 = None;
-                    }
-                    RunnersOn::Runner101 => {
-                        state.runners = RunnersOn::Runner001;
-                        state.runner1 = None;
-                    }
-                    _ => {}
-                }
+                state.bases.remove(1);
                 state.outs = increment_out(state.outs, 1);
                 state.game_text += &format!(
                     "\n{} {} thrown out stealing 2B!",
@@ -1939,43 +1553,21 @@ pub fn process_steals(
         }
         StealType::Third => {
             let mut steal_mod = catcher_mod;
-            let stealer = state.runner2.clone().unwrap(); // TODO: error proof?
+            let stealer = state.bases.runner(2).cloned().expect("checked above");
             if stealer.speedy() {
                 steal_mod = 1;
             }
             if stealer.slow() {
                 steal_mod = -2;
             }
-            let steal_result = combined_roll(&mut debug, 8) - 1 + steal_mod;
+            let steal_result = combined_roll(debug, 8, "steal") - 1 + steal_mod;
 
             if steal_result > 3 {
-                match state.runners {
-                    RunnersOn::Runner010 => {
-                        state.runners = RunnersOn::Runner001;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                    }
-                    RunnersOn::Runner110 => {
-                        state.runners = RunnersOn::Runner101;
-                        state.runner3 = state.runner2.clone();
-                        state.runner2 = None;
-                    }
-                    _ => {}
-                }
+                state.bases.move_runner(2, 3);
                 state.game_text +=
                     &format!("\n{} {} stole 3B!", stealer.first_name, stealer.last_name);
             } else {
-                match state.runners {
-                    RunnersOn::Runner010 => {
-                        state.runners = RunnersOn::Runner000;
-                        state.runner2 = None;
-                    }
-                    RunnersOn::Runner110 => {
-                        state.runners = RunnersOn::Runner100;
-                        state.runner2 = None;
-                    }
-                    _ => {}
-                }
+                state.bases.remove(2);
                 state.outs = increment_out(state.outs, 1);
                 state.game_text += &format!(
                     "\n{} {} thrown out stealing 3B!",
@@ -1985,34 +1577,13 @@ pub fn process_steals(
         }
         StealType::Home => {
             // NOTE: your runner should have S+ to end up here!
-            let stealer = state.runner3.clone().unwrap();
-            let steal_result = combined_roll(&mut debug, 8) + 1 + catcher_mod;
+            let stealer = state.bases.runner(3).cloned().expect("checked above");
+            let steal_result = combined_roll(debug, 8, "steal") + 1 + catcher_mod;
 
             // runner leaves 3rd no matter outcome of steal attempt
-            match state.runners {
-                RunnersOn::Runner001 => {
-                    state.runners = RunnersOn::Runner000;
-                    state.runner3 = None;
-                }
-                RunnersOn::Runner101 => {
-                    state.runners = RunnersOn::Runner100;
-                    state.runner3 = None;
-                }
-                RunnersOn::Runner011 => {
-                    state.runners = RunnersOn::Runner010;
-                    state.runner3 = None;
-                }
-                RunnersOn::Runner111 => {
-                    state.runners = RunnersOn::Runner110;
-                    state.runner3 = None;
-                }
-                _ => {}
-            }
+            state.bases.remove(3);
             if steal_result >= 8 {
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
+                state.score(1);
                 state.game_text +=
                     &format!("\n{} {} stole home!", stealer.first_name, stealer.last_name);
             } else {
@@ -2026,22 +1597,20 @@ pub fn process_steals(
         StealType::Double => {
             let mut steal_mod = catcher_mod;
             // look at traits of lead runner
-            let stealer = state.runner2.clone().unwrap(); // TODO: error proof?
-            let stealer2 = state.runner1.clone().unwrap();
+            let stealer = state.bases.runner(2).cloned().expect("checked above");
+            let stealer2 = state.bases.runner(1).cloned().expect("checked above");
             if stealer.speedy() {
                 steal_mod = 1;
             }
             if stealer.slow() {
                 steal_mod = -1; // see 2nd ed. pg. 31 - is it a typo?
             }
-            let steal_result = combined_roll(&mut debug, 8) + steal_mod;
+            let steal_result = combined_roll(debug, 8, "steal") + steal_mod;
 
             if steal_result <= 3 {
                 // lead runner is out - only valid condition is Runner110
-                state.runners = RunnersOn::Runner010;
-                state.runner3 = None;
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
+                state.bases.remove(2);
+                state.bases.move_runner(1, 2);
                 state.outs = increment_out(state.outs, 1);
                 state.game_text += &format!(
                     "\n{} {} thrown out at third",
@@ -2053,10 +1622,8 @@ pub fn process_steals(
                 );
             } else if steal_result > 3 && steal_result <= 5 {
                 // trailing runner is out
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-                state.runner1 = None;
+                state.bases.move_runner(2, 3);
+                state.bases.remove(1);
                 state.outs = increment_out(state.outs, 1);
                 state.game_text += &format!(
                     "\n{} {} steals 3B safely.",
@@ -2068,10 +1635,7 @@ pub fn process_steals(
                 );
             } else {
                 // both runners reach safely
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
+                state.bases.advance(1);
                 state.game_text +=
                     &format!("\n{} {} stole 3B!", stealer.first_name, stealer.last_name);
                 state.game_text +=
@@ -2079,16 +1643,22 @@ pub fn process_steals(
             }
         }
     }
-    state
+    let state = record_play(&before, state, PlayKind::Steal, None, &0);
+    debug_check_invariants(&state, "process_steals");
+
+    Ok(state)
 }
 
 /// process bunting
 pub fn bunt(
     mut state: GameState,
     game: &GameModern,
-    mut debug: DebugConfig,
+    debug: &mut DebugConfig,
     batter: Player,
 ) -> GameState {
+    let before = state.clone();
+    let bunter = batter.clone(); // batter gets moved onto the bases below
+    state.next_batter();
     // check traits, get bunt roll result
     let mut bunt_mod: i32 = 0;
     if batter.contact_hit() {
@@ -2097,111 +1667,30 @@ pub fn bunt(
     if batter.free_swing() {
         bunt_mod = -1;
     }
-    let bunt_result = combined_roll(&mut debug, 6) + bunt_mod;
+    let bunt_result = combined_roll(debug, 6, "bunt") + bunt_mod;
     state.game_text += &format!("\nBunting!  Bunt roll: {}", &bunt_result);
 
     // process result
     if bunt_result <= 2 {
         // lead runner out, batter safe
         state.game_text += "\nLead runner out, batter safe.";
-        match state.runners {
-            RunnersOn::Runner000 => state.game_text += "\nNo runners, no bunt.",
-            RunnersOn::Runner100 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runner1 = None;
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner010 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner001 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner110 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner101 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state.runner3 = None;
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner011 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-                state = add_runner(state, &1, batter);
-            }
-            RunnersOn::Runner111 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-            }
+        if state.bases.is_empty() {
+            state.game_text += "\nNo runners, no bunt.";
+        } else {
+            state = lead_runner_out(state, batter);
         }
     } else if bunt_result == 3 {
         // 1st & 2nd -> lead runner advances, batter out
         // 3rd -> lead runner out, batter safe
-        match state.runners {
-            RunnersOn::Runner000 => state.game_text += "\nNo runners, no bunt.", // TODO: allow bunt against shift
-            RunnersOn::Runner100 => {
-                state.outs = increment_out(state.outs, 1);
-                state = runners_advance(state, &1);
-                state.game_text += "\nLead runner advances, batter out.";
-            }
-            RunnersOn::Runner010 => {
-                state.outs = increment_out(state.outs, 1);
-                state = runners_advance(state, &1);
-                state.game_text += "\nLead runner advances, batter out.";
-            }
-            RunnersOn::Runner001 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                state = add_runner(state, &1, batter);
-                state.game_text += "\nLead runner out, batter safe.";
-            }
-            RunnersOn::Runner110 => {
-                state.outs = increment_out(state.outs, 1);
-                state = runners_advance(state, &1);
-                state.game_text += "\nLead runner advances, batter out.";
-            }
-            RunnersOn::Runner101 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state.runner3 = None;
-                state = add_runner(state, &1, batter);
-                state.game_text += "\nLead runner out, batter safe.";
-            }
-            RunnersOn::Runner011 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-                state = add_runner(state, &1, batter);
-                state.game_text += "\nLead runner out, batter safe.";
-            }
-            RunnersOn::Runner111 => {
-                state.outs = increment_out(state.outs, 1);
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-                state.game_text += "\nLead runner out, batter safe.";
-            }
+        if state.bases.is_empty() {
+            state.game_text += "\nNo runners, no bunt."; // TODO: allow bunt against shift
+        } else if state.bases.is_occupied(3) {
+            state = lead_runner_out(state, batter);
+            state.game_text += "\nLead runner out, batter safe.";
+        } else {
+            state.outs = increment_out(state.outs, 1);
+            state = runners_advance(state, &1);
+            state.game_text += "\nLead runner advances, batter out.";
         }
     } else if bunt_result == 4 || bunt_result == 5 {
         // lead runner advances, batter out
@@ -2213,7 +1702,7 @@ pub fn bunt(
         // S+ -> Single, DEF 3B
         // lead runner advances, batter out
         if batter.speedy() {
-            state = hit_table(&5, state, game, &mut debug, false);
+            (state, _) = hit_table(&5, state, game, debug, false);
             state.game_text += "\nLead runner advances, bunter races for first!";
         } else {
             state.outs = increment_out(state.outs, 1);
@@ -2222,9 +1711,23 @@ pub fn bunt(
         }
     }
 
+    let mss_result = 0; // bunts don't roll an MSS
+    let state = record_play(&before, state, PlayKind::Bunt, Some(&bunter), &mss_result);
+    debug_check_invariants(&state, "bunt");
+
     state
 }
 
+// lead runner is thrown out, everyone else moves up and the batter takes first
+fn lead_runner_out(mut state: GameState, batter: Player) -> GameState {
+    state.outs = increment_out(state.outs, 1);
+    if let Some(lead) = state.bases.lead_runner() {
+        state.bases.remove(lead);
+    }
+    state = runners_advance(state, &1);
+    add_runner(state, &1, batter)
+}
+
 /// increment outs
 pub fn increment_out(current: Outs, mut increment: u32) -> Outs {
     let mut outs = Outs::None;
@@ -2263,16 +1766,18 @@ pub fn increment_out(current: Outs, mut increment: u32) -> Outs {
     outs
 }
 
-/// hit and run - should be RUnner100 otherwise can't do it
+/// hit and run - should be Runner100, fails without touching the game otherwise
 pub fn hit_and_run(
     mut state: GameState,
     game: &GameModern,
     debug: &mut DebugConfig,
     batter: Player,
-) -> GameState {
+) -> Result<GameState, ActionError> {
+    state.check_bases(&Action::HitAndRun)?;
+    let start = state.clone(); // steal attempt and swing are logged as one play
     state.game_text += "\n\nThe hit and run is on!";
     // first roll a steal like normal
-    let stealer = state.runner1.clone().unwrap();
+    let stealer = state.bases.runner(1).cloned().expect("checked above");
     let mut steal_mod = 0;
     if stealer.speedy() {
         steal_mod = 1;
@@ -2280,7 +1785,7 @@ pub fn hit_and_run(
     if stealer.slow() {
         steal_mod = -1;
     }
-    let steal_result = combined_roll(debug, 8) + steal_mod;
+    let steal_result = combined_roll(debug, 8, "steal") + steal_mod;
     state.game_text += &format!("\nSteal result: {} -> ", steal_result);
     let steal_success: bool;
     if steal_result >= 4 {
@@ -2312,7 +1817,7 @@ pub fn hit_and_run(
         }
     }
     // NOTE: special rules for GB+
-    if state.runners == RunnersOn::Runner111 {
+    if runnerson(&state) == 3 {
         pd = change_pitch_die(pd, 1);
     }
     // NOTE: handedness check
@@ -2321,12 +1826,12 @@ pub fn hit_and_run(
         // TODO: make distinction between starting pitcher and reliever
     }
     let pitch_result: i32 = if pd > 0 {
-        combined_roll(debug, pd)
+        combined_roll(debug, pd, "pitch")
     } else {
-        -combined_roll(debug, pd.abs())
+        -combined_roll(debug, pd.abs(), "pitch")
     };
     state.game_text += &format!("\nPitch result: {}", &pitch_result);
-    let mss_result = pitch_result + combined_roll(debug, 100);
+    let mss_result = pitch_result + combined_roll(debug, 100, "swing");
     state.game_text += &format!("\nMSS: {}", &mss_result);
     let mut hit_bonus = 5;
     if batter.contact_hit() {
@@ -2342,21 +1847,8 @@ pub fn hit_and_run(
         game.oddity,
     );
     state.game_text += &format!(" -> {:?}", swing_result);
-    match state.inning_half {
-        InningTB::Top => {
-            state.away_state.current_batter =
-                bo_wrap(state.away_state.current_batter, 1, false) as u32;
-        }
-        InningTB::Bottom => {
-            if state.home_state.current_batter == 8 {
-                state.home_state.current_batter = 0;
-            } else {
-                state.home_state.current_batter += 1;
-            }
-            state.home_state.current_batter =
-                bo_wrap(state.home_state.current_batter, 1, false) as u32;
-        }
-    }
+    let before = state.clone();
+    state.next_batter();
     let hnr: HitAndRun;
     let out_type = get_swing_position(&mss_result);
     match swing_result {
@@ -2393,7 +1885,7 @@ pub fn hit_and_run(
             if defender.is_some() {
                 defense_bonus += defender.unwrap().defense();
             }
-            let def_roll = combined_roll(debug, 12) + defense_bonus;
+            let def_roll = combined_roll(debug, 12, "possible error") + defense_bonus;
             if def_roll <= 2 {
                 hnr = HitAndRun::Hit;
             } else if out_type <= 3 || out_type >= 7 {
@@ -2425,15 +1917,13 @@ pub fn hit_and_run(
             if steal_success {
                 // runners at 1st and 3rd
                 state.game_text += "\nRunners on 1st and 3rd!";
-                state.runners = RunnersOn::Runner101;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = Some(batter);
+                state.bases.move_runner(1, 3);
+                state.bases.place(1, batter.clone());
             } else {
                 // runners at 1st and 2nd
                 state.game_text += "\nRunners on 1st and 2nd!";
-                state.runners = RunnersOn::Runner110;
-                state.runner2 = state.runner1.clone();
-                state.runner1 = Some(batter);
+                state.bases.move_runner(1, 2);
+                state.bases.place(1, batter.clone());
             }
         }
         HitAndRun::PopUpK => {
@@ -2445,9 +1935,7 @@ pub fn hit_and_run(
                 // double play
                 state.game_text += "\nDouble play!";
                 state.outs = increment_out(state.outs, 2);
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                state.runner1 = None;
+                state.bases.remove(1);
             }
         }
         HitAndRun::Groundball => {
@@ -2455,26 +1943,32 @@ pub fn hit_and_run(
                 // batter out, runner reaches 2nd
                 state.game_text += "\nBatter out, runner reaches 2nd.";
                 state.outs = increment_out(state.outs, 1);
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-                state.runners = RunnersOn::Runner010;
+                state.bases.move_runner(1, 2);
             } else {
                 // double play
                 state.game_text += "\nDouble play!";
                 state.outs = increment_out(state.outs, 2);
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                state.runner1 = None;
+                state.bases.remove(1);
             }
         }
     }
+    // the hit and run table doesn't add to the hit line either, so the batter isn't credited
+    state = record_plate_appearance(&before, state, &batter, &swing_result, &mss_result, 0);
+    state = record_play(
+        &start,
+        state,
+        PlayKind::HitAndRun(swing_result),
+        Some(&batter),
+        &mss_result,
+    );
+    debug_check_invariants(&state, "hit_and_run");
 
-    state
+    Ok(state)
 }
 
 /// function to generate random animal on the field
 pub fn animal(debug: &mut DebugConfig) -> Animal {
-    let animal_result = combined_roll(debug, 4);
+    let animal_result = combined_roll(debug, 4, "animal");
     let animal: Animal;
     if animal_result == 1 {
         animal = Animal::Bird;
@@ -2489,191 +1983,17 @@ pub fn animal(debug: &mut DebugConfig) -> Animal {
     animal
 }
 
-/// advances only runners that are "forced", used for things like walks/balks/HBB
+/// advances only runners that are "forced" by the batter taking [advance] bases, used for things
+/// like walks/balks/HBB
 pub fn force_advance(mut state: GameState, advance: u32) -> GameState {
-    match state.runners {
-        RunnersOn::Runner000 => {}
-        RunnersOn::Runner100 => {
-            if advance == 1 {
-                state.runners = RunnersOn::Runner010;
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-            } else if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = None;
-            } else if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            }
-        }
-        RunnersOn::Runner010 => {
-            if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-            } else if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            }
-        }
-        RunnersOn::Runner001 => {
-            if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            }
-        }
-        RunnersOn::Runner110 => {
-            if advance == 1 {
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-            } else if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner2 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            } else if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner2 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 2,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 2,
-                }
-            }
-        }
-        RunnersOn::Runner101 => {
-            if advance == 1 {
-                state.runners = RunnersOn::Runner011;
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-            } else if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            } else if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 2,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 2,
-                }
-            }
-        }
-        RunnersOn::Runner011 => {
-            if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            } else if advance >= 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                state.runner2 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 2,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 2,
-                }
-            }
-        }
-        RunnersOn::Runner111 => {
-            if advance == 1 {
-                state.runners = RunnersOn::Runner011;
-                state.runner3 = state.runner2.clone();
-                state.runner2 = state.runner1.clone();
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 1,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 1,
-                }
-            } else if advance == 2 {
-                state.runners = RunnersOn::Runner001;
-                state.runner3 = state.runner1.clone();
-                state.runner2 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 2,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 2,
-                }
-            } else if advance == 3 {
-                state.runners = RunnersOn::Runner000;
-                state.runner3 = None;
-                state.runner2 = None;
-                state.runner1 = None;
-                match state.inning_half {
-                    InningTB::Top => state.away_state.runs[(state.inning - 1) as usize] += 3,
-                    InningTB::Bottom => state.home_state.runs[(state.inning - 1) as usize] += 3,
-                }
-            }
-        }
-    }
+    let runs = state.bases.force(advance);
+    state.score(runs);
 
     state
 }
 
 /// special function for walks (only force advances)
-pub fn walk_advance(mut state: GameState) -> GameState {
-    match state.runners {
-        RunnersOn::Runner100 => {
-            state.runners = RunnersOn::Runner010;
-            state.runner2 = state.runner1.clone();
-            state.runner1 = None;
-        }
-        RunnersOn::Runner110 => {
-            state.runners = RunnersOn::Runner011;
-            state.runner3 = state.runner2.clone();
-            state.runner2 = state.runner1.clone();
-            state.runner1 = None;
-            return state;
-        }
-        RunnersOn::Runner101 => {
-            // NOTE: this is different from runners_advance()
-            state.runners = RunnersOn::Runner011;
-            state.runner2 = state.runner1.clone();
-            state.runner1 = None;
-        }
-        RunnersOn::Runner111 => {
-            state.runners = RunnersOn::Runner011;
-            state.runner3 = state.runner2.clone();
-            state.runner2 = state.runner1.clone();
-            state.runner1 = None;
-            match state.inning_half {
-                InningTB::Top => {
-                    state.away_state.runs[(state.inning - 1) as usize] += 1;
-                }
-                InningTB::Bottom => {
-                    state.home_state.runs[(state.inning - 1) as usize] += 1;
-                }
-            }
-        }
-        _ => {} // no changes if no runner on 1st
-    }
-
-    state
+pub fn walk_advance(state: GameState) -> GameState {
+    // NOTE: this is different from runners_advance(), a runner on 3rd only moves if forced
+    force_advance(state, 1)
 }
@@ -0,0 +1,101 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use crate::characters::{players::Player, teams::TeamState};
+
+use super::game_functions::{GameState, GameStatus, InningTB};
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// checks the rules every game state should follow between engine steps, returns one message per
+/// broken rule (empty if the state is fine)
+pub fn check_invariants(state: &GameState) -> Vec<String> {
+    let mut broken = vec![];
+
+    // the same player can't be standing on two bases
+    let runners = state.bases.runners();
+    for (i, runner) in runners.iter().enumerate() {
+        for (j, other) in runners.iter().enumerate().skip(i + 1) {
+            if runner.is_some() && runner == other {
+                broken.push(format!(
+                    "{} is on base {} and base {}",
+                    runner_name(runner),
+                    i + 1,
+                    j + 1
+                ));
+            }
+        }
+    }
+
+    // Outs can't go past three, but the play log counts them as plain numbers
+    for play in state.plays.iter().filter(|play| play.outs > 3) {
+        broken.push(format!(
+            "play in inning {} made {} outs",
+            play.inning, play.outs
+        ));
+    }
+
+    // home has a line score entry for every inning started, away gets its next one when the top
+    // half ends - a game ends after a bottom half once the 9th is done and the totals differ,
+    // without moving on to the next inning, so both lines are one entry ahead once it's over
+    let over = u32::from(state.status == GameStatus::Over);
+    let bottom = u32::from(state.inning_half == InningTB::Bottom);
+    for (side, team, innings) in [
+        ("home", &state.home_state, state.inning + over),
+        ("away", &state.away_state, state.inning + bottom.max(over)),
+    ] {
+        broken.extend(check_team(side, team, innings as usize));
+    }
+
+    broken
+}
+
+/// panics with every broken rule in debug builds, [step] is the engine function that just ran -
+/// release builds skip the check
+pub fn debug_check_invariants(state: &GameState, step: &str) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let broken = check_invariants(state);
+    assert!(
+        broken.is_empty(),
+        "game state broken after {}:\n    {}",
+        step,
+        broken.join("\n    ")
+    );
+}
+
+fn check_team(side: &str, team: &TeamState, innings: usize) -> Vec<String> {
+    let mut broken = vec![];
+    for (line, values) in [
+        ("runs", &team.runs),
+        ("hits", &team.hits),
+        ("errors", &team.errors),
+    ] {
+        if values.len() != innings {
+            broken.push(format!(
+                "{} {} line has {} innings, expected {}",
+                side,
+                line,
+                values.len(),
+                innings
+            ));
+        }
+    }
+    if team.current_batter > 8 {
+        broken.push(format!(
+            "{} batter index is {}, should be 0-8",
+            side, team.current_batter
+        ));
+    }
+
+    broken
+}
+
+fn runner_name(runner: &Option<Player>) -> String {
+    match runner {
+        Some(player) => format!("{} {}", player.first_name, player.last_name),
+        None => "empty".to_string(),
+    }
+}
@@ -0,0 +1,569 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::characters::{
+    ballparks::{
+        Ballpark, BallparkAncient, BallparkModern, Condition, Quirks, Roof, StadiumTypeAncient,
+        StadiumTypeModern, Turf,
+    },
+    players::{write_player, Handedness, InjuryLocation, InjurySeverity, Player, Position, Traits},
+    teams::{write_team, Era, Fanbase, Location, Makeup, ManagerLeague, Priority, Team},
+};
+
+use super::file_locations::{
+    split_csv_line, ParseError, BALLPARK_FOLDER, PLAYER_FOLDER, TEAM_FOLDER,
+};
+use super::stats::REST_GAMES;
+
+/*========================================================
+CONSTANTS
+========================================================*/
+// ERA -> pitch die, first row the ERA is under wins, anything worse gets -d4
+const ERA_PITCH_DIE: [(f32, i32); 4] = [(2.0, 20), (3.0, 12), (3.5, 8), (4.0, 4)];
+const WORST_PITCH_DIE: i32 = -4;
+const HITTER_PITCH_DIE: i32 = -8; // same as generate_pitch_die() for position players
+/// first season imported for the modern era, 19th century seasons get the ancient era rules
+pub const MODERN_ERA_START: u32 = 1901;
+
+// not enough at bats/innings to judge, no traits and (for hitters) pitcher-ish targets
+const MIN_AT_BATS: u32 = 100;
+const MIN_OUTS: u32 = 150; // 50 innings
+const NO_BAT_TARGETS: (i32, i32) = (10, 12);
+
+// batting trait thresholds, home run rates are per at bat
+const ELITE_POWER_RATE: f32 = 40.0 / 550.0;
+const POWER_RATE: f32 = 25.0 / 550.0;
+const WEAK_RATE: f32 = 3.0 / 550.0;
+const CONTACT_SO_RATE: f32 = 0.06;
+const FREE_SWINGER_SO_RATE: f32 = 0.20;
+const SPEEDY_STEALS: u32 = 25;
+
+// pitching trait thresholds, per 9 innings
+const STRIKEOUT_ARTIST_K9: f32 = 8.0;
+const CONTROL_BB9: f32 = 2.0;
+const WILD_BB9: f32 = 4.5;
+const STAMINA_OUTS_PER_START: f32 = 22.5; // 7.5 innings
+
+// pitchers with the most starts make the rotation, sized so each one gets their rest
+const ROTATION_SIZE: usize = REST_GAMES as usize + 1;
+
+/*========================================================
+STRUCTS
+========================================================*/
+/// season batting totals from Batting.csv, summed over stints
+#[derive(Debug, Default, Clone)]
+pub struct LahmanBatting {
+    pub games: u32,
+    pub at_bats: u32,
+    pub hits: u32,
+    pub home_runs: u32,
+    pub walks: u32,
+    pub hit_by_pitch: u32,
+    pub sacrifice_flies: u32,
+    pub stolen_bases: u32,
+    pub strikeouts: u32,
+}
+
+/// season pitching totals from Pitching.csv, summed over stints
+#[derive(Debug, Default, Clone)]
+pub struct LahmanPitching {
+    pub games: u32,
+    pub starts: u32,
+    pub outs: u32,
+    pub earned_runs: u32,
+    pub walks: u32,
+    pub strikeouts: u32,
+}
+
+/// everything made from one season
+pub struct LahmanImport {
+    pub season: u32,
+    pub players: Vec<Player>,
+    pub teams: Vec<Team>,
+    pub ballparks: Vec<Ballpark>, // Lahman has no park ratings, every team gets a plain placeholder
+    pub skipped: Vec<String>,     // Lahman IDs with stats but no People.csv entry
+}
+
+// one row of People.csv
+struct Person {
+    first_name: String,
+    last_name: String,
+    bats: String,
+    throws: String,
+}
+
+// one season of one player, with where they played
+#[derive(Default)]
+struct SeasonLine {
+    batting: LahmanBatting,
+    pitching: LahmanPitching,
+    team_games: BTreeMap<String, u32>, // teamID -> games, player goes on the team they played the most for
+    position_games: BTreeMap<String, u32>, // Fielding.csv POS -> games
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// batter target and on base target from batting average and OBP (.300 -> 30)
+pub fn batter_targets(batting: &LahmanBatting) -> (i32, i32) {
+    if batting.at_bats == 0 {
+        return NO_BAT_TARGETS;
+    }
+    let average = batting.hits as f32 / batting.at_bats as f32;
+    let on_base_chances =
+        batting.at_bats + batting.walks + batting.hit_by_pitch + batting.sacrifice_flies;
+    let on_base =
+        (batting.hits + batting.walks + batting.hit_by_pitch) as f32 / on_base_chances as f32;
+    let bt = (average * 100.0).round() as i32;
+    let obt = (on_base * 100.0).round() as i32;
+
+    (bt, obt.max(bt))
+}
+
+/// pitch die from ERA, pitchers who never got an out get the worst die
+pub fn pitch_die_from_era(era: f32) -> i32 {
+    ERA_PITCH_DIE
+        .iter()
+        .find(|(limit, _)| era < *limit)
+        .map(|(_, die)| *die)
+        .unwrap_or(WORST_PITCH_DIE)
+}
+
+/// hitting traits from home run, strikeout and stolen base rates
+pub fn batting_traits(batting: &LahmanBatting) -> Vec<Traits> {
+    let mut traits = vec![];
+    if batting.at_bats >= MIN_AT_BATS {
+        let at_bats = batting.at_bats as f32;
+        let hr_rate = batting.home_runs as f32 / at_bats;
+        if hr_rate >= ELITE_POWER_RATE {
+            traits.push(Traits::ElitePowerHitter);
+        } else if hr_rate >= POWER_RATE {
+            traits.push(Traits::PowerHitter);
+        } else if batting.home_runs == 0 && batting.at_bats >= 3 * MIN_AT_BATS {
+            traits.push(Traits::ExtraWeakHitter);
+        } else if hr_rate <= WEAK_RATE {
+            traits.push(Traits::WeakHitter);
+        }
+        let so_rate = batting.strikeouts as f32 / at_bats;
+        if so_rate <= CONTACT_SO_RATE {
+            traits.push(Traits::ContactHitter);
+        } else if so_rate >= FREE_SWINGER_SO_RATE {
+            traits.push(Traits::FreeSwinger);
+        }
+    }
+    if batting.stolen_bases >= SPEEDY_STEALS {
+        traits.push(Traits::SpeedyRunner);
+    }
+    if traits.is_empty() {
+        traits.push(Traits::None);
+    }
+
+    traits
+}
+
+/// pitching traits from strikeout/walk rates and innings per start
+pub fn pitching_traits(pitching: &LahmanPitching) -> Vec<Traits> {
+    let mut traits = vec![];
+    if pitching.outs >= MIN_OUTS {
+        let innings = pitching.outs as f32 / 3.0;
+        if pitching.strikeouts as f32 * 9.0 / innings >= STRIKEOUT_ARTIST_K9 {
+            traits.push(Traits::StrikeoutArtist);
+        }
+        let bb9 = pitching.walks as f32 * 9.0 / innings;
+        if bb9 <= CONTROL_BB9 {
+            traits.push(Traits::ControlPitcher);
+        } else if bb9 >= WILD_BB9 {
+            traits.push(Traits::Wild);
+        }
+        // NOTE: counts relief outs too, close enough for starters
+        if pitching.starts > 0
+            && pitching.outs as f32 / pitching.starts as f32 >= STAMINA_OUTS_PER_START
+        {
+            traits.push(Traits::GreatStamina);
+        }
+    }
+    if traits.is_empty() {
+        traits.push(Traits::None);
+    }
+
+    traits
+}
+
+/// era whose rules a Lahman season is played under
+pub fn season_era(season: u32) -> Era {
+    if season < MODERN_ERA_START {
+        Era::Ancient
+    } else {
+        Era::Modern
+    }
+}
+
+/// reads Batting.csv, Pitching.csv and People.csv (and Fielding.csv for positions if it's
+/// there) from [lahman_dir] and builds players and teams for [season]
+/// player IDs are "<playerID>_<season>", team IDs "<teamID>_<season>" and ballpark IDs
+/// "<teamID>_<season>_park" so re-importing a season overwrites the same files
+pub fn import_lahman(lahman_dir: &Path, season: u32) -> Result<LahmanImport, ParseError> {
+    let mut lines: BTreeMap<String, SeasonLine> = BTreeMap::new();
+
+    for (file, row) in read_lahman_csv(lahman_dir, "Batting.csv")? {
+        if row.number(&file, "yearID")? != season {
+            continue;
+        }
+        let line = lines.entry(row.text(&file, "playerID")?).or_default();
+        let games = row.number(&file, "G")?;
+        *line
+            .team_games
+            .entry(row.text(&file, "teamID")?)
+            .or_default() += games;
+        let batting = &mut line.batting;
+        batting.games += games;
+        batting.at_bats += row.number(&file, "AB")?;
+        batting.hits += row.number(&file, "H")?;
+        batting.home_runs += row.number(&file, "HR")?;
+        batting.walks += row.number(&file, "BB")?;
+        batting.hit_by_pitch += row.number(&file, "HBP")?;
+        batting.sacrifice_flies += row.number(&file, "SF")?;
+        batting.stolen_bases += row.number(&file, "SB")?;
+        batting.strikeouts += row.number(&file, "SO")?;
+    }
+    for (file, row) in read_lahman_csv(lahman_dir, "Pitching.csv")? {
+        if row.number(&file, "yearID")? != season {
+            continue;
+        }
+        let line = lines.entry(row.text(&file, "playerID")?).or_default();
+        // pitchers missing from Batting.csv still need a team
+        line.team_games
+            .entry(row.text(&file, "teamID")?)
+            .or_insert(0);
+        let pitching = &mut line.pitching;
+        pitching.games += row.number(&file, "G")?;
+        pitching.starts += row.number(&file, "GS")?;
+        pitching.outs += row.number(&file, "IPouts")?;
+        pitching.earned_runs += row.number(&file, "ER")?;
+        pitching.walks += row.number(&file, "BB")?;
+        pitching.strikeouts += row.number(&file, "SO")?;
+    }
+    if lahman_dir.join("Fielding.csv").exists() {
+        for (file, row) in read_lahman_csv(lahman_dir, "Fielding.csv")? {
+            if row.number(&file, "yearID")? != season {
+                continue;
+            }
+            if let Some(line) = lines.get_mut(&row.text(&file, "playerID")?) {
+                *line
+                    .position_games
+                    .entry(row.text(&file, "POS")?)
+                    .or_default() += row.number(&file, "G")?;
+            }
+        }
+    }
+    let mut people: BTreeMap<String, Person> = BTreeMap::new();
+    for (file, row) in read_lahman_csv(lahman_dir, "People.csv")? {
+        let id = row.text(&file, "playerID")?;
+        if lines.contains_key(&id) {
+            people.insert(
+                id,
+                Person {
+                    first_name: row.text(&file, "nameFirst")?,
+                    last_name: row.text(&file, "nameLast")?,
+                    bats: row.text(&file, "bats")?,
+                    throws: row.text(&file, "throws")?,
+                },
+            );
+        }
+    }
+
+    // build players, then split them up by team
+    let mut rosters: BTreeMap<String, Vec<(Player, SeasonLine)>> = BTreeMap::new();
+    let mut skipped = vec![];
+    for (lahman_id, line) in lines {
+        // one missing person shouldn't cost the whole season, the caller can warn about them
+        let Some(person) = people.get(&lahman_id) else {
+            skipped.push(lahman_id);
+            continue;
+        };
+        let team_id = line
+            .team_games
+            .iter()
+            .max_by_key(|(_, games)| **games)
+            .map(|(team, _)| team.clone())
+            .unwrap_or_default();
+        let player = season_player(&lahman_id, season, person, &line);
+        rosters.entry(team_id).or_default().push((player, line));
+    }
+
+    let mut import = LahmanImport {
+        season,
+        players: vec![],
+        teams: vec![],
+        ballparks: vec![],
+        skipped,
+    };
+    for (team_id, mut players) in rosters {
+        let team = season_team(&team_id, season, &mut players);
+        import.ballparks.push(placeholder_park(&team));
+        import.teams.push(team);
+        import
+            .players
+            .extend(players.into_iter().map(|(player, _)| player));
+    }
+
+    Ok(import)
+}
+
+/// writes an import into the players/, teams/ and ballparks/ folders of [data_dir]
+pub fn write_import(import: &LahmanImport, data_dir: &Path) -> Result<(), std::io::Error> {
+    for folder in [PLAYER_FOLDER, TEAM_FOLDER, BALLPARK_FOLDER] {
+        fs::create_dir_all(data_dir.join(folder))?;
+    }
+    for player in import.players.iter() {
+        let path = data_dir
+            .join(PLAYER_FOLDER)
+            .join(format!("{}.dbp", player.id));
+        write_player(player, &path.to_string_lossy())?;
+    }
+    for team in import.teams.iter() {
+        let path = data_dir.join(TEAM_FOLDER).join(format!("{}.dbt", team.id));
+        write_team(team.clone(), &path.to_string_lossy())?;
+    }
+    for ballpark in import.ballparks.iter() {
+        let path = data_dir
+            .join(BALLPARK_FOLDER)
+            .join(format!("{}.dbb", ballpark.id()));
+        ballpark.write(&path.to_string_lossy())?;
+    }
+
+    Ok(())
+}
+
+// ratings for one player-season
+fn season_player(lahman_id: &str, season: u32, person: &Person, line: &SeasonLine) -> Player {
+    let is_pitcher = line.pitching.games > line.batting.games / 2 && line.pitching.games > 0;
+    let (batter_target, on_base_target) = batter_targets(&line.batting);
+    let mut traits = batting_traits(&line.batting);
+    let (position, pitch_die) = if is_pitcher {
+        let era = if line.pitching.outs > 0 {
+            line.pitching.earned_runs as f32 * 27.0 / line.pitching.outs as f32
+        } else {
+            f32::MAX
+        };
+        // hitting traits still count for the odd pitcher that got enough at bats
+        traits.extend(pitching_traits(&line.pitching));
+        traits.retain(|t| *t != Traits::None);
+        if traits.is_empty() {
+            traits.push(Traits::None);
+        }
+        (Position::Pitcher, pitch_die_from_era(era))
+    } else {
+        (fielding_position(&line.position_games), HITTER_PITCH_DIE)
+    };
+    // pitchers get the arm they throw with
+    let hand = if is_pitcher {
+        &person.throws
+    } else {
+        &person.bats
+    };
+    let handedness = match hand.as_str() {
+        "R" => Handedness::Right,
+        "L" => Handedness::Left,
+        "B" | "S" => Handedness::Switch,
+        _ => Handedness::None,
+    };
+
+    Player {
+        id: format!("{}_{}", lahman_id, season),
+        first_name: person.first_name.clone(),
+        last_name: person.last_name.clone(),
+        nickname: "".to_string(),
+        position,
+        handedness,
+        batter_target,
+        on_base_target,
+        pitch_die,
+        traits,
+        injury_location: vec![InjuryLocation::None],
+        injury_severity: vec![InjurySeverity::Uninjured],
+    }
+}
+
+// position he played the most games at, old Fielding.csv lumps outfielders into "OF"
+fn fielding_position(position_games: &BTreeMap<String, u32>) -> Position {
+    let position = position_games
+        .iter()
+        .filter(|(pos, _)| pos.as_str() != "P")
+        .max_by_key(|(_, games)| **games)
+        .map(|(pos, _)| pos.as_str());
+    match position {
+        Some("C") => Position::Catcher,
+        Some("1B") => Position::Firstbase,
+        Some("2B") => Position::Secondbase,
+        Some("3B") => Position::Thirdbase,
+        Some("SS") => Position::Shortstop,
+        Some("LF") => Position::Leftfield,
+        Some("CF") | Some("OF") => Position::Centerfield,
+        Some("RF") => Position::Rightfield,
+        _ => Position::Bench,
+    }
+}
+
+// starters by at bats with one player per position, then bench/rotation/bullpen
+// starters and bench players get the position they're used at, like generate_team()
+fn season_team(team_id: &str, season: u32, players: &mut [(Player, SeasonLine)]) -> Team {
+    // hitters by at bats, then pitchers by starts
+    players.sort_by_key(|(player, line)| {
+        let is_hitter = player.position != Position::Pitcher;
+        let at_bats = if is_hitter { line.batting.at_bats } else { 0 };
+        std::cmp::Reverse((is_hitter, at_bats, line.pitching.starts, line.pitching.outs))
+    });
+    let starters = players
+        .iter()
+        .filter(|(player, line)| player.position == Position::Pitcher && line.pitching.starts > 0)
+        .count();
+
+    let mut roster: Vec<String> = vec![];
+    let mut bench: Vec<String> = vec![];
+    let mut pitchers: Vec<String> = vec![];
+    let mut outfield = [
+        Position::Leftfield,
+        Position::Centerfield,
+        Position::Rightfield,
+    ]
+    .into_iter();
+    let mut filled: Vec<Position> = vec![];
+    for (player, _) in players.iter_mut() {
+        if player.position == Position::Pitcher {
+            pitchers.push(player.id.clone());
+            continue;
+        }
+        // extra center fielders ("OF") slide over to the corners
+        if player.position == Position::Centerfield && filled.contains(&player.position) {
+            if let Some(open) = outfield.find(|pos| !filled.contains(pos)) {
+                player.position = open;
+            }
+        }
+        if roster.len() < 8
+            && player.position != Position::Bench
+            && !filled.contains(&player.position)
+        {
+            filled.push(player.position.clone());
+            roster.push(player.id.clone());
+        } else {
+            player.position = Position::Bench;
+            bench.push(player.id.clone());
+        }
+    }
+    // a staff with no starts at all still needs someone to start
+    let rotation = starters.clamp(1, ROTATION_SIZE).min(pitchers.len());
+    let bullpen = pitchers.split_off(rotation);
+    let pitcher = pitchers;
+
+    Team {
+        id: format!("{}_{}", team_id, season),
+        name: format!("{} {}", season, team_id),
+        ballpark: format!("{}_{}_park", team_id, season),
+        manager: "".to_string(),
+        logo: "".to_string(),
+        era: season_era(season),
+        location: Location::None,
+        mascot: "".to_string(),
+        priority: Priority::None,
+        makeup: Makeup::None,
+        years: 0,
+        championship: 0,
+        fanbase: Fanbase::None,
+        manager_position: Position::None,
+        manager_league: ManagerLeague::None,
+        retired: 0,
+        personality: "".to_string(),
+        daring: 0,
+        motto: "".to_string(),
+        owner_background: "".to_string(),
+        owner_personality: "".to_string(),
+        roster,
+        bench,
+        pitcher,
+        bullpen,
+    }
+}
+
+// the ballpark [team] points at, no quirks and nothing else that changes a game
+fn placeholder_park(team: &Team) -> Ballpark {
+    let id = team.ballpark.clone();
+    let name = format!("{} Ballpark", team.name);
+    match team.era {
+        Era::Ancient => Ballpark::Ancient(BallparkAncient {
+            id,
+            name,
+            location: Location::None,
+            park_type: StadiumTypeAncient::None,
+            capacity: 0,
+            condition: Condition::None,
+            quirks: vec![Quirks::None],
+        }),
+        Era::Modern | Era::None => Ballpark::Modern(BallparkModern {
+            id,
+            name,
+            location: Location::None,
+            park_type: StadiumTypeModern::None,
+            capacity: 0,
+            turf: Turf::Good,
+            roof: Roof::No,
+            condition: Condition::None,
+            quirks: vec![Quirks::None],
+        }),
+    }
+}
+
+// a CSV row looked up by header name
+struct CsvRow {
+    line: usize,
+    headers: Rc<Vec<String>>,
+    values: Vec<String>,
+}
+
+impl CsvRow {
+    fn text(&self, file: &str, column: &str) -> Result<String, ParseError> {
+        match self.headers.iter().position(|h| h == column) {
+            Some(i) => Ok(self.values.get(i).cloned().unwrap_or_default()),
+            None => Err(ParseError::new(file, 1, column, "", "missing column")),
+        }
+    }
+
+    // empty cells (common in old seasons, e.g. no SF before 1954) count as 0
+    fn number(&self, file: &str, column: &str) -> Result<u32, ParseError> {
+        let value = self.text(file, column)?;
+        if value.is_empty() {
+            return Ok(0);
+        }
+        value
+            .parse()
+            .map_err(|_| ParseError::new(file, self.line, column, &value, "expected a number"))
+    }
+}
+
+fn read_lahman_csv(lahman_dir: &Path, name: &str) -> Result<Vec<(String, CsvRow)>, ParseError> {
+    let path = lahman_dir.join(name);
+    let file = path.display().to_string();
+    let contents = fs::read_to_string(&path).map_err(|err| ParseError::io(&file, err))?;
+    let mut lines = contents.lines();
+    let headers = Rc::new(split_csv_line(lines.next().unwrap_or_default()));
+
+    Ok(lines
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            (
+                file.clone(),
+                CsvRow {
+                    line: i + 2,
+                    headers: headers.clone(),
+                    values: split_csv_line(line),
+                },
+            )
+        })
+        .collect())
+}
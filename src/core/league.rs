@@ -0,0 +1,360 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::characters::{
+    ballparks::{
+        load_park_ancient, load_park_modern, write_ballpark_ancient, write_ballpark_modern,
+        BallparkAncient, BallparkModern, Roof, Turf,
+    },
+    players::{load_player, write_player, Player},
+    teams::{load_team, write_team, Era, Team},
+};
+
+use super::{
+    file_locations::{
+        data_format, id_from_path, resolve_reference, DataFormat, ParseError, BALLPARK_FOLDER,
+        PLAYER_FOLDER, TEAM_FOLDER,
+    },
+    game_functions::check_modern_roster,
+};
+
+/*========================================================
+ENUM DEFINITIONS
+========================================================*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueKind {
+    Parse,           // file couldn't be read or has a malformed line
+    BrokenReference, // team points at a player/ballpark that doesn't exist
+    DuplicatePlayer, // same player on more than one roster slot
+    Roster,          // team would be rejected by create_modern_game()
+}
+
+/*========================================================
+STRUCTS
+========================================================*/
+/// one problem found in a league folder
+#[derive(Debug, Clone)]
+pub struct LeagueIssue {
+    pub kind: IssueKind,
+    pub file: String,
+    pub message: String,
+}
+
+/// results of checking (or converting) a league folder
+#[derive(Debug, Default)]
+pub struct LeagueReport {
+    pub players: usize,
+    pub teams: usize,
+    pub ballparks: usize,
+    pub converted: usize, // files rewritten by convert_league()
+    pub issues: Vec<LeagueIssue>,
+}
+
+impl LeagueReport {
+    fn issue(&mut self, kind: IssueKind, file: &str, message: String) {
+        self.issues.push(LeagueIssue {
+            kind,
+            file: file.to_string(),
+            message,
+        });
+    }
+
+    fn parse_issue(&mut self, err: ParseError) {
+        self.issue(IssueKind::Parse, &err.file.clone(), err.to_string());
+    }
+
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+}
+
+// everything that parsed, kept around so convert_league() can write it back out
+enum Ballpark {
+    Modern(BallparkModern),
+    Ancient(BallparkAncient),
+}
+
+#[derive(Default)]
+struct League {
+    players: Vec<(PathBuf, Player)>,
+    ballparks: Vec<(PathBuf, Ballpark)>,
+    teams: Vec<(PathBuf, Team)>,
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// parses every player/team/ballpark in [data_dir] and reports malformed files, broken
+/// references, players on more than one roster and teams that can't start a modern game
+pub fn validate_league(data_dir: &Path) -> LeagueReport {
+    let mut report = LeagueReport::default();
+    read_league(data_dir, &mut report);
+    report
+}
+
+/// rewrites every file that parses into [format] (the legacy KEY: value format is rewritten in
+/// place so old spellings/paths get cleaned up), team references become IDs where the file is
+/// inside the league folder - files that switch format replace the old file
+pub fn convert_league(data_dir: &Path, format: DataFormat) -> LeagueReport {
+    let mut report = LeagueReport::default();
+    let league = read_league(data_dir, &mut report);
+
+    // teams first, their references have to resolve against the files before they move
+    for (path, mut team) in league.teams {
+        if team.id.is_empty() {
+            team.id = id_from_path(&path);
+        }
+        team.ballpark = reference_to_id(&team.ballpark, data_dir, BALLPARK_FOLDER, "dbb");
+        for slot in [
+            &mut team.roster,
+            &mut team.bench,
+            &mut team.pitcher,
+            &mut team.bullpen,
+        ] {
+            for reference in slot.iter_mut() {
+                *reference = reference_to_id(reference, data_dir, PLAYER_FOLDER, "dbp");
+            }
+        }
+        let new_path = converted_path(&path, format, "dbt");
+        let result = write_team(team, &new_path.to_string_lossy());
+        finish_conversion(&mut report, &path, &new_path, result);
+    }
+    for (path, mut player) in league.players {
+        if player.id.is_empty() {
+            player.id = id_from_path(&path);
+        }
+        let new_path = converted_path(&path, format, "dbp");
+        let result = write_player(&player, &new_path.to_string_lossy());
+        finish_conversion(&mut report, &path, &new_path, result);
+    }
+    for (path, ballpark) in league.ballparks {
+        let new_path = converted_path(&path, format, "dbb");
+        let filename = new_path.to_string_lossy().to_string();
+        let result = match ballpark {
+            Ballpark::Modern(mut park) => {
+                if park.id.is_empty() {
+                    park.id = id_from_path(&path);
+                }
+                write_ballpark_modern(&park, &filename)
+            }
+            Ballpark::Ancient(mut park) => {
+                if park.id.is_empty() {
+                    park.id = id_from_path(&path);
+                }
+                write_ballpark_ancient(&park, &filename)
+            }
+        };
+        finish_conversion(&mut report, &path, &new_path, result);
+    }
+
+    report
+}
+
+// reads all three folders, checking each team against the players/ballparks it references
+fn read_league(data_dir: &Path, report: &mut LeagueReport) -> League {
+    let mut league = League::default();
+
+    for path in league_files(data_dir, PLAYER_FOLDER, "dbp", report) {
+        match read_file(&path).and_then(|(contents, file)| load_player(contents, &file)) {
+            Ok(player) => league.players.push((path, player)),
+            Err(err) => report.parse_issue(err),
+        }
+    }
+    report.players = league.players.len();
+
+    for path in league_files(data_dir, BALLPARK_FOLDER, "dbb", report) {
+        // ballpark files don't say which era they're for, modern parks have turf/roof
+        let parsed = read_file(&path).and_then(|(contents, file)| {
+            match load_park_modern(contents.clone(), &file) {
+                Ok(park) if !matches!((&park.turf, &park.roof), (Turf::None, Roof::None)) => {
+                    Ok(Ballpark::Modern(park))
+                }
+                Ok(park) => Ok(load_park_ancient(contents, &file)
+                    .map(Ballpark::Ancient)
+                    .unwrap_or(Ballpark::Modern(park))),
+                Err(err) => load_park_ancient(contents, &file)
+                    .map(Ballpark::Ancient)
+                    .map_err(|_| err),
+            }
+        });
+        match parsed {
+            Ok(ballpark) => league.ballparks.push((path, ballpark)),
+            Err(err) => report.parse_issue(err),
+        }
+    }
+    report.ballparks = league.ballparks.len();
+
+    // player file -> every team/slot it shows up in
+    let mut appearances: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    for path in league_files(data_dir, TEAM_FOLDER, "dbt", report) {
+        let team = match read_file(&path).and_then(|(contents, file)| load_team(contents, &file)) {
+            Ok(team) => team,
+            Err(err) => {
+                report.parse_issue(err);
+                continue;
+            }
+        };
+        let file = path.display().to_string();
+
+        let park_path = resolve_reference(&team.ballpark, data_dir, BALLPARK_FOLDER, "dbb");
+        if !park_path.exists() {
+            report.issue(
+                IssueKind::BrokenReference,
+                &file,
+                format!(
+                    "ballpark '{}' not found ({})",
+                    team.ballpark,
+                    park_path.display()
+                ),
+            );
+        } else if let Some((_, park)) = league.ballparks.iter().find(|(p, _)| *p == park_path) {
+            let park_era = match park {
+                Ballpark::Modern(_) => Era::Modern,
+                Ballpark::Ancient(_) => Era::Ancient,
+            };
+            if park_era != team.era {
+                report.issue(
+                    IssueKind::Roster,
+                    &file,
+                    format!("ballpark '{}' is for a different era", team.ballpark),
+                );
+            }
+        }
+
+        for (slot, references) in [
+            ("PLAYER", &team.roster),
+            ("BENCH", &team.bench),
+            ("PITCHER", &team.pitcher),
+            ("BULLPEN", &team.bullpen),
+        ] {
+            for reference in references.iter() {
+                let player_path = resolve_reference(reference, data_dir, PLAYER_FOLDER, "dbp");
+                if !player_path.exists() {
+                    report.issue(
+                        IssueKind::BrokenReference,
+                        &file,
+                        format!(
+                            "{} '{}' not found ({})",
+                            slot,
+                            reference,
+                            player_path.display()
+                        ),
+                    );
+                    continue;
+                }
+                appearances
+                    .entry(player_path)
+                    .or_default()
+                    .push(format!("{} {}", team.name, slot));
+            }
+        }
+
+        if let Err(err) = check_modern_roster(&team, &team.name) {
+            report.issue(IssueKind::Roster, &file, err.message);
+        }
+        league.teams.push((path, team));
+    }
+    report.teams = league.teams.len();
+
+    for (player_path, slots) in appearances.iter() {
+        if slots.len() > 1 {
+            report.issue(
+                IssueKind::DuplicatePlayer,
+                &player_path.display().to_string(),
+                format!(
+                    "player is listed {} times: {}",
+                    slots.len(),
+                    slots.join(", ")
+                ),
+            );
+        }
+    }
+
+    league
+}
+
+// every legacy/JSON/TOML file in [data_dir]/[folder], sorted so reports are stable
+fn league_files(
+    data_dir: &Path,
+    folder: &str,
+    extension: &str,
+    report: &mut LeagueReport,
+) -> Vec<PathBuf> {
+    let dir = data_dir.join(folder);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            report.parse_issue(ParseError::io(&dir.display().to_string(), err));
+            return vec![];
+        }
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .map(|ext| ext == extension || ext == "json" || ext == "toml")
+                == Some(true)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn read_file(path: &Path) -> Result<(String, String), ParseError> {
+    let file = path.display().to_string();
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok((contents, file)),
+        Err(err) => Err(ParseError::io(&file, err)),
+    }
+}
+
+// same file name with the extension for [format], [legacy] is the *.db? extension
+fn converted_path(path: &Path, format: DataFormat, legacy: &str) -> PathBuf {
+    let file = path.display().to_string();
+    let extension = match format {
+        DataFormat::Json => "json",
+        DataFormat::Toml => "toml",
+        // legacy files are rewritten in place, JSON/TOML ones go back to *.db?
+        DataFormat::Legacy if data_format(&file) == DataFormat::Legacy => return path.into(),
+        DataFormat::Legacy => legacy,
+    };
+    path.with_extension(extension)
+}
+
+// old style paths that point into the league folder become IDs, anything else is left alone
+fn reference_to_id(reference: &str, data_dir: &Path, folder: &str, extension: &str) -> String {
+    let path = resolve_reference(reference, data_dir, folder, extension);
+    let in_folder = path.parent() == Some(data_dir.join(folder).as_path());
+    if in_folder && path.exists() {
+        id_from_path(&path)
+    } else {
+        reference.to_string()
+    }
+}
+
+fn finish_conversion(
+    report: &mut LeagueReport,
+    old_path: &Path,
+    new_path: &Path,
+    result: Result<(), std::io::Error>,
+) {
+    let file = new_path.display().to_string();
+    match result {
+        Ok(()) => {
+            report.converted += 1;
+            if old_path != new_path {
+                if let Err(err) = fs::remove_file(old_path) {
+                    report.parse_issue(ParseError::io(&old_path.display().to_string(), err));
+                }
+            }
+        }
+        Err(err) => report.parse_issue(ParseError::io(&file, err)),
+    }
+}
@@ -1,21 +1,47 @@
 /*========================================================
 MODULE INCLUSIONS
 ========================================================*/
+pub mod actions; // manager actions, which ones the rules allow right now and running them
+pub mod bases; // runners on base and the advance/force/score moves every play uses
+pub mod calibration; // exact odds for the swing/hit/defense tables, checks the engine against the book
 pub mod file_locations;
 pub mod game_functions; // include things like at bat functinos, defense rolls, etc. // default file locations for teams/players/ballparks
+pub mod invariants; // game state consistency checks, run after every engine step in debug builds
+pub mod lahman; // builds players/teams from Lahman database season stats
+pub mod league; // league folder validation and format conversion
+pub mod replay; // seeded game records, replays a game from its manager decisions
+pub mod retrosheet; // play by play log and Retrosheet event file export
+pub mod settings; // user settings and recent files, saved in the config directory
+pub mod simulation; // batch game simulation for matchup odds
+pub mod stats; // season/career stats database and leaderboards
 
-use rand::Rng;
+use std::cell::RefCell;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+thread_local! {
+    // dice for this thread, reseeded by seed_rolls() so a game can be replayed exactly
+    static DICE: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
 
 /*========================================================
 FUNCTION DEFINITIONS
 ========================================================*/
 // TODO: this should probably be u32 right???
-/// returns a random integer between 1 and [side], used to simulate dice rolls with threed_rng()
+/// returns a random integer between 1 and [side] (inclusive), used to simulate dice rolls with
+/// threed_rng()
 pub fn roll(side: i32) -> i32 {
     if side == 1 {
         return 1;
     }
-    rand::thread_rng().gen_range(1..side)
+    DICE.with(|dice| dice.borrow_mut().gen_range(1..=side))
+}
+
+/// reseeds the dice for this thread, every roll() after this is the same for the same [seed]
+pub fn seed_rolls(seed: u64) {
+    DICE.with(|dice| *dice.borrow_mut() = StdRng::seed_from_u64(seed));
 }
 
 // TODO: function to wrap indexing batting order (0-8)
@@ -31,3 +57,32 @@ pub fn bo_wrap(original: u32, adjust: u32, subtract: bool) -> usize {
         (sum % 9) as usize
     }
 }
+
+/// creates a stable ID for a player/team/ballpark from its name plus a random number so two
+/// players with the same name don't collide - IDs are also used as file names
+/// NOTE: the number doesn't come from the game dice, making a team mid-game can't change a seeded
+/// game's rolls
+pub fn generate_id(name: &str) -> String {
+    let base: String = name
+        .trim()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '_' || *c == '-')
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+    format!("{}_{:04}", base, rand::thread_rng().gen_range(0..10000))
+}
+
+/// [id] with its number rerolled until there's no file for it in [folder], checks the
+/// [extension] file as well as the JSON/TOML versions
+pub fn unused_id(id: &str, folder: &Path, extension: &str) -> String {
+    let base = id.rsplit_once('_').map_or(id, |(base, _)| base);
+    let mut id = id.to_string();
+    while [extension, "json", "toml"]
+        .iter()
+        .any(|ext| folder.join(format!("{}.{}", id, ext)).exists())
+    {
+        id = format!("{}_{:04}", base, rand::thread_rng().gen_range(0..10000));
+    }
+
+    id
+}
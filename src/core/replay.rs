@@ -0,0 +1,206 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::characters::{
+    ballparks::BallparkModern,
+    teams::{ActiveTeam, Team},
+};
+use crate::gui::debug::DebugConfig;
+
+use super::actions::{apply_action, Action, ActionError};
+use super::file_locations::{deserialize_data, serialize_data, DataFormat, ParseError};
+use super::game_functions::{init_new_game_state, GameModern, GameState, GameStatus};
+use super::seed_rolls;
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// a manager decision and the plate appearance it was made during (0 is the game's first)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Decision {
+    pub at_bat: u32,
+    pub action: Action,
+    #[serde(default)]
+    pub rolls: Vec<i32>, // physical dice rolled for it, in the order the engine asked for them
+}
+
+/// everything needed to play a game again exactly - the rosters it started with, the dice seed
+/// and every decision the managers made (swings aren't stored unless they were rolled with
+/// physical dice, they fill the gaps)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub seed: u64,
+    pub home: Team,
+    pub away: Team,
+    pub ballpark: BallparkModern,
+    pub oddity: bool,
+    pub home_active: ActiveTeam,
+    pub away_active: ActiveTeam,
+    pub decisions: Vec<Decision>,
+    pub at_bats: u32, // swings (including the ones that end an inning), bunts and hit and runs
+    pub edited: bool, // debug rolls or hand edits were used, a replay won't match the game
+}
+
+/// one point in a replayed game, [label] is the action that got it there
+#[derive(Clone)]
+pub struct ReplayStep {
+    pub label: String,
+    pub state: GameState,
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl GameRecord {
+    /// starts a record for [game] with its current rosters
+    pub fn new(game: &GameModern, seed: u64) -> Self {
+        Self {
+            seed,
+            home: game.home.clone(),
+            away: game.away.clone(),
+            ballpark: game.ballpark.clone(),
+            oddity: game.oddity,
+            home_active: game.home_active.clone(),
+            away_active: game.away_active.clone(),
+            decisions: vec![],
+            at_bats: 0,
+            edited: false,
+        }
+    }
+
+    /// the game as it was when the record started
+    pub fn game(&self) -> GameModern {
+        GameModern {
+            home: self.home.clone(),
+            away: self.away.clone(),
+            home_active: self.home_active.clone(),
+            away_active: self.away_active.clone(),
+            ballpark: self.ballpark.clone(),
+            oddity: self.oddity,
+            quiet: true,
+        }
+    }
+
+    /// game state before the first pitch
+    pub fn start(&self) -> GameState {
+        let mut state = init_new_game_state(
+            self.home_active.pitching[0].clone(),
+            self.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Ongoing;
+        state
+    }
+
+    /// runs [action] and records it, the dice are seeded from the record first so a replay
+    /// rolls the same numbers - [debug] rolls still win if debug mode is on, physical dice rolls
+    /// are kept with the action so they can be rolled again
+    /// illegal actions aren't recorded
+    pub fn play(
+        &mut self,
+        game: &mut GameModern,
+        state: GameState,
+        action: Action,
+        debug: &mut DebugConfig,
+    ) -> Result<GameState, ActionError> {
+        seed_rolls(self.seed.wrapping_add(self.steps()));
+        let state = apply_action(game, state, &action, debug)?;
+        let rolls = if debug.physical {
+            debug.rolls.clone()
+        } else {
+            vec![]
+        };
+        if debug.mode && !debug.physical {
+            self.edited = true;
+        }
+        if action.ends_at_bat() {
+            self.at_bats += 1;
+        }
+        if !matches!(action, Action::Swing) || !rolls.is_empty() {
+            self.decisions.push(Decision {
+                at_bat: self.at_bats - u32::from(action.ends_at_bat()),
+                action,
+                rolls,
+            });
+        }
+
+        Ok(state)
+    }
+
+    // engine calls made so far, each one gets its own seed
+    fn steps(&self) -> u64 {
+        let decisions = self
+            .decisions
+            .iter()
+            .filter(|decision| !decision.action.ends_at_bat())
+            .count() as u64;
+        self.at_bats as u64 + decisions
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// plays [record] again from the first pitch, returns the game state at the start and after
+/// every swing and decision - stops early at a decision the rules don't allow (hand edited file)
+pub fn replay(record: &GameRecord) -> Vec<ReplayStep> {
+    let mut game = record.game();
+    let mut replayed = GameRecord::new(&game, record.seed);
+    let mut state = replayed.start();
+    let mut steps = vec![ReplayStep {
+        label: "Play ball!".to_string(),
+        state: state.clone(),
+    }];
+    let mut decisions = record.decisions.iter().peekable();
+    for at_bat in 0..=record.at_bats {
+        // decisions come before the swing, bunts/hit and runs take its place
+        let mut batted = false;
+        let mut actions = vec![];
+        while let Some(decision) = decisions.next_if(|decision| decision.at_bat == at_bat) {
+            batted |= decision.action.ends_at_bat();
+            actions.push((decision.action.clone(), decision.rolls.clone()));
+        }
+        if !batted && at_bat < record.at_bats {
+            actions.push((Action::Swing, vec![]));
+        }
+        for (action, rolls) in actions {
+            let label = format!("At bat {}: {}", at_bat + 1, action.label());
+            let mut debug = if rolls.is_empty() {
+                DebugConfig::default()
+            } else {
+                DebugConfig::physical(rolls)
+            };
+            state = match replayed.play(&mut game, state.clone(), action, &mut debug) {
+                Ok(state) => state,
+                Err(_) => return steps,
+            };
+            steps.push(ReplayStep {
+                label,
+                state: state.clone(),
+            });
+        }
+    }
+
+    steps
+}
+
+/// saves [record] as JSON in [folder], named after the teams and seed - returns the file path
+pub fn write_record(record: &GameRecord, folder: &str) -> Result<PathBuf, std::io::Error> {
+    fs::create_dir_all(folder)?;
+    let path = Path::new(folder).join(format!(
+        "{}_at_{}_{}.json",
+        record.away.id, record.home.id, record.seed
+    ));
+    serialize_data(record, &path.to_string_lossy(), DataFormat::Json)?;
+
+    Ok(path)
+}
+
+pub fn load_record(filename: &str) -> Result<GameRecord, ParseError> {
+    let contents = fs::read_to_string(filename).map_err(|err| ParseError::io(filename, err))?;
+    deserialize_data(&contents, filename, DataFormat::Json)
+}
@@ -0,0 +1,565 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::characters::{players::*, teams::Team};
+use crate::core::game_functions::{
+    get_swing_position, position_by_number, AtBatResults, GameModern, GameState, InningTB, Outs,
+};
+
+/*========================================================
+ENUM DEFINITIONS
+========================================================*/
+/// what the offense did on a logged play
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayKind {
+    AtBat(AtBatResults),
+    HitAndRun(AtBatResults),
+    Bunt,
+    Steal,
+}
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// one play, recorded as the difference between the game state before and after it
+#[derive(Debug, Clone)]
+pub struct PlayRecord {
+    pub inning: u32,
+    pub inning_half: InningTB,
+    pub slot: u32,              // batting order slot of the batter (0-8)
+    pub batter: Option<Player>, // None for steals, batter is whoever is in [slot]
+    pub pitcher: Player,
+    pub kind: PlayKind,
+    pub mss_result: i32,
+    pub runners_before: [Option<Player>; 3],
+    pub runners_after: [Option<Player>; 3],
+    pub outs: u32, // outs made on the play
+    pub runs: u32,
+    pub hits: u32,
+    pub errors: u32,
+}
+
+// where the batter ended up, used to pick hit types and B-x advances
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Destination {
+    Out,
+    Base(usize), // 1-3
+    Home,
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// compares the game state before and after a play and adds it to the play log
+pub fn record_play(
+    before: &GameState,
+    mut after: GameState,
+    kind: PlayKind,
+    batter: Option<&Player>,
+    mss_result: &i32,
+) -> GameState {
+    let (off_before, off_after, pitcher) = match before.inning_half {
+        InningTB::Top => (
+            &before.away_state,
+            &after.away_state,
+            &before.home_state.current_pitcher,
+        ),
+        InningTB::Bottom => (
+            &before.home_state,
+            &after.home_state,
+            &before.away_state.current_pitcher,
+        ),
+    };
+    let errors_before =
+        before.home_state.errors.iter().sum::<u32>() + before.away_state.errors.iter().sum::<u32>();
+    let errors_after =
+        after.home_state.errors.iter().sum::<u32>() + after.away_state.errors.iter().sum::<u32>();
+    let play = PlayRecord {
+        inning: before.inning,
+        inning_half: before.inning_half.clone(),
+        slot: off_before.current_batter,
+        batter: batter.cloned(),
+        pitcher: pitcher.clone(),
+        kind,
+        mss_result: *mss_result,
+        runners_before: runners(before),
+        runners_after: runners(&after),
+        outs: outs_number(&after.outs).saturating_sub(outs_number(&before.outs)),
+        runs: off_after.runs.iter().sum::<u32>() - off_before.runs.iter().sum::<u32>(),
+        hits: off_after.hits.iter().sum::<u32>() - off_before.hits.iter().sum::<u32>(),
+        errors: errors_after.saturating_sub(errors_before),
+    };
+    after.plays.push(play);
+
+    after
+}
+
+/// builds a Retrosheet event file entry (id, version, info, start, play, sub, com and data
+/// records) for a finished game, [date] is (year, month, day)
+/// NOTE: deadball doesn't track pitch counts, so every play has a count of ?? and no pitches
+pub fn export_game(
+    game: &GameModern,
+    state: &GameState,
+    date: (u32, u32, u32),
+    number: u32,
+) -> String {
+    let (year, month, day) = date;
+    let mut lines: Vec<String> = vec![
+        format!(
+            "id,{}{:04}{:02}{:02}{}",
+            team_code(&game.home),
+            year,
+            month,
+            day,
+            number
+        ),
+        "version,2".to_string(),
+        format!("info,visteam,{}", team_code(&game.away)),
+        format!("info,hometeam,{}", team_code(&game.home)),
+        format!("info,site,{}", clean_field(&game.ballpark.name)),
+        format!("info,date,{:04}/{:02}/{:02}", year, month, day),
+        format!("info,number,{}", number),
+        "info,usedh,false".to_string(),
+        format!(
+            "info,inputprogvers,\"deadball {}\"",
+            env!("CARGO_PKG_VERSION")
+        ),
+    ];
+
+    // lineups as they were when each slot first came up, 0 = visitors, 1 = home
+    let active = [&game.away_active, &game.home_active];
+    let mut lineups: [Vec<Player>; 2] = [
+        active[0].batting_order.clone(),
+        active[1].batting_order.clone(),
+    ];
+    let mut pitchers: [Player; 2] = [
+        state.away_state.current_pitcher.clone(),
+        state.home_state.current_pitcher.clone(),
+    ];
+    for (team, lineup) in lineups.iter_mut().enumerate() {
+        for (slot, starter) in lineup.iter_mut().enumerate() {
+            let first = state.plays.iter().find(|play| {
+                batting_team(play) == team && play.slot as usize == slot && play.batter.is_some()
+            });
+            if let Some(batter) = first.and_then(|play| play.batter.clone()) {
+                *starter = batter;
+            }
+        }
+        // games start with the first pitcher on the staff, see init_new_game_state()
+        if let Some(starter) = active[team].pitching.first() {
+            pitchers[team] = starter.clone();
+        }
+    }
+    for team in 0..2 {
+        for (slot, player) in lineups[team].iter().enumerate() {
+            lines.push(lineup_record("start", player, team, slot + 1));
+        }
+        if !lineups[team].contains(&pitchers[team]) {
+            lines.push(lineup_record("start", &pitchers[team], team, 0));
+        }
+    }
+
+    // earned runs by pitcher, everything counts as earned since errors aren't tracked per run
+    let mut earned_runs: Vec<(String, u32)> = vec![];
+    for play in state.plays.iter() {
+        let offense = batting_team(play);
+        let defense = 1 - offense;
+
+        if play.pitcher != pitchers[defense] {
+            let old = std::mem::replace(&mut pitchers[defense], play.pitcher.clone());
+            let order = match lineups[defense].iter().position(|player| *player == old) {
+                Some(slot) => {
+                    lineups[defense][slot] = play.pitcher.clone();
+                    slot + 1
+                }
+                None => 0,
+            };
+            lines.push(lineup_record("sub", &play.pitcher, defense, order));
+        }
+        let slot = (play.slot as usize).min(lineups[offense].len().saturating_sub(1));
+        if let Some(batter) = &play.batter {
+            if lineups[offense].get(slot) != Some(batter) {
+                lineups[offense][slot] = batter.clone();
+                lines.push(lineup_record("sub", batter, offense, slot + 1));
+            }
+        }
+        let batter = play
+            .batter
+            .clone()
+            .or_else(|| lineups[offense].get(slot).cloned())
+            .unwrap_or_default();
+
+        lines.push(format!(
+            "play,{},{},{},??,,{}",
+            play.inning,
+            offense,
+            player_code(&batter),
+            event(play)
+        ));
+        if matches!(play.kind, PlayKind::AtBat(AtBatResults::Oddity)) {
+            lines.push("com,\"deadball oddity, see the game log for details\"".to_string());
+        }
+
+        if play.runs > 0 {
+            let pitcher = player_code(&play.pitcher);
+            match earned_runs.iter_mut().find(|(id, _)| *id == pitcher) {
+                Some((_, runs)) => *runs += play.runs,
+                None => earned_runs.push((pitcher, play.runs)),
+            }
+        }
+    }
+    for (pitcher, runs) in earned_runs {
+        lines.push(format!("data,er,{},{}", pitcher, runs));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// appends a game to a Retrosheet event file, returns the game ID
+/// a single game on a date is number 0, a second one makes it a doubleheader so the first is
+/// renumbered 1 and the new one is 2
+pub fn write_event_file(
+    game: &GameModern,
+    state: &GameState,
+    filename: &str,
+) -> Result<String, std::io::Error> {
+    let date = today();
+    let existing = fs::read_to_string(filename).unwrap_or_default();
+    let id = format!(
+        "id,{}{:04}{:02}{:02}",
+        team_code(&game.home),
+        date.0,
+        date.1,
+        date.2
+    );
+    let same_day = existing
+        .lines()
+        .filter(|line| line.starts_with(&id))
+        .count() as u32;
+    let number = if same_day == 0 { 0 } else { same_day + 1 };
+
+    let single = format!("{}0", id);
+    let mut contents = String::new();
+    let mut in_single = false;
+    for line in existing.lines() {
+        if line.starts_with("id,") {
+            in_single = line == single;
+        }
+        if in_single && line == single {
+            contents.push_str(&format!("{}1\n", id));
+        } else if in_single && line == "info,number,0" {
+            contents.push_str("info,number,1\n");
+        } else {
+            contents.push_str(line);
+            contents.push('\n');
+        }
+    }
+    contents.push_str(&export_game(game, state, date, number));
+    fs::write(filename, contents)?;
+
+    Ok(format!("{}{}", &id[3..], number))
+}
+
+/// Retrosheet event code for a play, including runner advances
+fn event(play: &PlayRecord) -> String {
+    if play.kind == PlayKind::Steal {
+        return steal_event(play);
+    }
+
+    let destination = batter_destination(play);
+    // fielder the same way the game picks one, last digit of the MSS
+    let digit = get_swing_position(&play.mss_result);
+    let fielder = position_number(&position_by_number(digit));
+    let error_fielder = match fielder {
+        1 => 6, // possible_error() moves P/C plays to SS/2B
+        2 => 4,
+        _ => fielder,
+    };
+    let ground = fielder <= 6;
+    let out = match fielder {
+        3 => "3/G".to_string(),
+        f if ground => format!("{}3/G", f),
+        f => format!("{}/F", f),
+    };
+    let bunt = play.kind == PlayKind::Bunt;
+
+    let mut code = match &play.kind {
+        PlayKind::AtBat(AtBatResults::Oddity) => {
+            if play.runners_before == play.runners_after && play.outs == 0 && play.runs == 0 {
+                "NP".to_string()
+            } else {
+                "OA".to_string()
+            }
+        }
+        PlayKind::AtBat(AtBatResults::Walk) | PlayKind::HitAndRun(AtBatResults::Walk) => {
+            "W".to_string()
+        }
+        _ if play.hits > 0 => match destination {
+            Destination::Base(2) => "D".to_string(),
+            Destination::Base(3) => "T".to_string(),
+            Destination::Home => "HR".to_string(),
+            _ => "S".to_string(),
+        },
+        _ if play.errors > 0 && destination != Destination::Out => format!("E{}", error_fielder),
+        _ if destination != Destination::Out => "FC".to_string(),
+        _ if bunt && play.runs + runners_advanced(play) > 0 => "13/SH".to_string(),
+        _ if bunt => "13/BG".to_string(),
+        _ if (0..=2).contains(&digit) => "K".to_string(),
+        _ => out,
+    };
+    if bunt && !code.ends_with("SH") && !code.ends_with("BG") {
+        code += "/BG";
+    }
+    if destination == Destination::Out && play.outs >= 2 {
+        code += match (play.outs, ground) {
+            (2, true) => "/GDP",
+            (2, false) => "/DP",
+            _ => "/TP",
+        };
+    } else if destination == Destination::Out && !ground && play.runs > 0 && !bunt {
+        code += "/SF";
+    }
+
+    let advances = advances(play, destination, &code);
+    if advances.is_empty() {
+        code
+    } else {
+        format!("{}.{}", code, advances.join(";"))
+    }
+}
+
+// SB2/CS3/etc. for each runner that tried to move
+fn steal_event(play: &PlayRecord) -> String {
+    let mut events: Vec<String> = vec![];
+    for base in (0..3).rev() {
+        let Some(runner) = &play.runners_before[base] else {
+            continue;
+        };
+        let next = base_name(base + 2);
+        match play
+            .runners_after
+            .iter()
+            .position(|r| r.as_ref() == Some(runner))
+        {
+            Some(after) if after > base => events.push(format!("SB{}", base_name(after + 1))),
+            Some(_) => {}
+            None if play.runs > 0 => events.push("SBH".to_string()),
+            None => events.push(format!("CS{}", next)),
+        }
+    }
+    if events.is_empty() {
+        "NP".to_string()
+    } else {
+        events.join(";")
+    }
+}
+
+// runner advances in Retrosheet notation (1-3, 2-H, 1X2, B-2), lead runner first
+fn advances(play: &PlayRecord, destination: Destination, code: &str) -> Vec<String> {
+    let mut advances = vec![];
+    let mut runs = play.runs;
+    if destination == Destination::Home {
+        runs = runs.saturating_sub(1);
+    }
+    for base in (0..3).rev() {
+        let Some(runner) = &play.runners_before[base] else {
+            continue;
+        };
+        match play
+            .runners_after
+            .iter()
+            .position(|r| r.as_ref() == Some(runner))
+        {
+            Some(after) if after == base => {}
+            Some(after) => advances.push(format!("{}-{}", base + 1, after + 1)),
+            None if runs > 0 => {
+                runs -= 1;
+                advances.push(format!("{}-H", base + 1));
+            }
+            None => advances.push(format!("{}X{}", base + 1, base_name(base + 2))),
+        }
+    }
+
+    // batter only needs an advance when they end up past where the event puts them
+    let implied = if code.starts_with("HR") {
+        Destination::Home
+    } else if code.starts_with('D') {
+        Destination::Base(2)
+    } else if code.starts_with('T') {
+        Destination::Base(3)
+    } else if code.starts_with(['S', 'W', 'E', 'F']) {
+        Destination::Base(1)
+    } else {
+        Destination::Out
+    };
+    match destination {
+        Destination::Base(base) if destination != implied => advances.push(format!("B-{}", base)),
+        Destination::Home if implied != Destination::Home => advances.push("B-H".to_string()),
+        _ => {}
+    }
+
+    advances
+}
+
+// batter scores when more runs came in than runners left the bases
+fn batter_destination(play: &PlayRecord) -> Destination {
+    if let Some(batter) = &play.batter {
+        if let Some(base) = play
+            .runners_after
+            .iter()
+            .position(|r| r.as_ref() == Some(batter))
+        {
+            return Destination::Base(base + 1);
+        }
+    }
+    let runners_gone = play
+        .runners_before
+        .iter()
+        .flatten()
+        .filter(|runner| !play.runners_after.contains(&Some((*runner).clone())))
+        .count() as u32;
+    if play.batter.is_some() && play.runs > runners_gone {
+        Destination::Home
+    } else {
+        Destination::Out
+    }
+}
+
+// runners that moved up a base without scoring
+fn runners_advanced(play: &PlayRecord) -> u32 {
+    let mut advanced = 0;
+    for (base, runner) in play.runners_before.iter().enumerate() {
+        let Some(runner) = runner else {
+            continue;
+        };
+        let after = play
+            .runners_after
+            .iter()
+            .position(|r| r.as_ref() == Some(runner));
+        if after.is_some_and(|after| after > base) {
+            advanced += 1;
+        }
+    }
+    advanced
+}
+
+fn lineup_record(record: &str, player: &Player, team: usize, order: usize) -> String {
+    format!(
+        "{},{},\"{} {}\",{},{},{}",
+        record,
+        player_code(player),
+        clean_field(&player.first_name),
+        clean_field(&player.last_name),
+        team,
+        order,
+        position_number(&player.position)
+    )
+}
+
+// 0 = visitors batting, 1 = home batting
+fn batting_team(play: &PlayRecord) -> usize {
+    match play.inning_half {
+        InningTB::Top => 0,
+        InningTB::Bottom => 1,
+    }
+}
+
+/// Retrosheet fielding position number, bench players count as pinch hitters (11)
+pub fn position_number(position: &Position) -> u32 {
+    match position {
+        Position::Pitcher => 1,
+        Position::Catcher => 2,
+        Position::Firstbase => 3,
+        Position::Secondbase => 4,
+        Position::Thirdbase => 5,
+        Position::Shortstop => 6,
+        Position::Leftfield => 7,
+        Position::Centerfield => 8,
+        Position::Rightfield => 9,
+        Position::None | Position::Bench => 11,
+    }
+}
+
+// 2/3/H for the base number after a runner's current base
+fn base_name(base: usize) -> String {
+    if base >= 4 {
+        "H".to_string()
+    } else {
+        base.to_string()
+    }
+}
+
+fn runners(state: &GameState) -> [Option<Player>; 3] {
+    state.bases.runners().clone()
+}
+
+fn outs_number(outs: &Outs) -> u32 {
+    match outs {
+        Outs::None => 0,
+        Outs::One => 1,
+        Outs::Two => 2,
+        Outs::Three => 3,
+    }
+}
+
+// player ID, falls back to the name for players made before IDs existed
+fn player_code(player: &Player) -> String {
+    if player.id.is_empty() {
+        clean_field(&format!("{}{}", player.last_name, player.first_name)).replace(' ', "")
+    } else {
+        clean_field(&player.id)
+    }
+}
+
+// three letter team code from the start of the team ID (or the name for teams made before IDs),
+// game IDs only work with exactly three
+fn team_code(team: &Team) -> String {
+    let source = if team.id.is_empty() {
+        &team.name
+    } else {
+        &team.id
+    };
+    let mut code: String = source
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(3)
+        .collect::<String>()
+        .to_uppercase();
+    while code.len() < 3 {
+        code.push('X');
+    }
+    code
+}
+
+// fields can't have commas or quotes in them
+fn clean_field(value: &str) -> String {
+    value.replace([',', '"'], "")
+}
+
+/// today's date as (year, month, day), UTC
+pub fn today() -> (u32, u32, u32) {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0);
+    // days since 1970-01-01 to a civil date
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year as u32, month as u32, day as u32)
+}
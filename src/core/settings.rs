@@ -0,0 +1,156 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::characters::teams::Era;
+use crate::core::file_locations::{
+    deserialize_data, serialize_data, DataFormat, ParseError, DATA_LOCATION,
+};
+
+/*========================================================
+CONSTANTS
+========================================================*/
+pub const SETTINGS_FILE: &str = "settings.toml";
+/// files kept in each recent files list
+pub const RECENT_FILES: usize = 8;
+// files kept in the data directory next to the league folders
+const STATS_FILE: &str = "stats.dbs";
+const EVENT_FILE: &str = "deadball.EVN";
+const RECORD_FOLDER: &str = "records";
+
+/*========================================================
+ENUM DEFINITIONS
+========================================================*/
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    System,
+    Dark,
+    Light,
+}
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// user settings, kept as TOML in the config directory between launches
+/// missing fields fall back to the defaults so older settings files still load
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub data_dir: String, // league folder, also where stats/records/event files go
+    pub default_era: Era,
+    pub oddity: bool,
+    pub toast_seconds: f64,
+    pub theme: Theme,
+    pub window_size: [f32; 2],
+    pub window_pos: Option<[f32; 2]>, // None lets the OS place the window
+    pub console_window: bool,
+    pub recent_teams: Vec<String>,
+    pub recent_parks: Vec<String>,
+    pub recent_saves: Vec<String>, // game records
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            data_dir: DATA_LOCATION.to_string(),
+            default_era: Era::None,
+            oddity: false,
+            toast_seconds: 3.0,
+            theme: Theme::System,
+            window_size: [800.0, 600.0],
+            window_pos: None,
+            console_window: true,
+            recent_teams: vec![],
+            recent_parks: vec![],
+            recent_saves: vec![],
+        }
+    }
+}
+
+impl Settings {
+    pub fn stats_file(&self) -> String {
+        Path::new(&self.data_dir)
+            .join(STATS_FILE)
+            .display()
+            .to_string()
+    }
+
+    pub fn event_file(&self) -> String {
+        Path::new(&self.data_dir)
+            .join(EVENT_FILE)
+            .display()
+            .to_string()
+    }
+
+    pub fn record_dir(&self) -> String {
+        Path::new(&self.data_dir)
+            .join(RECORD_FOLDER)
+            .display()
+            .to_string()
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// puts [path] at the front of a recent files list, dropping any older entry for it and
+/// anything past RECENT_FILES
+pub fn add_recent(recent: &mut Vec<String>, path: &str) {
+    recent.retain(|entry| entry != path);
+    recent.insert(0, path.to_string());
+    recent.truncate(RECENT_FILES);
+}
+
+/// deadball folder in the user's config directory, None if there's no home directory to put it in
+/// XDG_CONFIG_HOME (or ~/.config) on Linux, Application Support on macOS and APPDATA on Windows
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| Path::new(&home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+    };
+    base.map(|dir| dir.join("deadball"))
+}
+
+/// where the settings file lives
+pub fn settings_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+pub fn load_settings(filename: &Path) -> Result<Settings, ParseError> {
+    let file = filename.display().to_string();
+    let contents = fs::read_to_string(filename).map_err(|err| ParseError::io(&file, err))?;
+    deserialize_data(&contents, &file, DataFormat::Toml)
+}
+
+/// writes the settings file, creating the config directory if it isn't there yet
+pub fn write_settings(settings: &Settings, filename: &Path) -> Result<(), std::io::Error> {
+    if let Some(dir) = filename.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    serialize_data(settings, &filename.display().to_string(), DataFormat::Toml)
+}
+
+/// settings from the config directory, defaults if there aren't any yet (or they can't be read)
+pub fn startup_settings() -> Settings {
+    match settings_path() {
+        Some(path) if path.exists() => load_settings(&path).unwrap_or_else(|err| {
+            eprintln!("Couldn't read settings, using the defaults: {}", err);
+            Settings::default()
+        }),
+        _ => Settings::default(),
+    }
+}
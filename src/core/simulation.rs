@@ -0,0 +1,210 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::collections::BTreeMap;
+use std::thread;
+
+use crate::gui::debug::DebugConfig;
+
+use super::game_functions::{
+    init_new_game_state, modern_game_flow, AtBatResults, GameModern, GameState, GameStatus,
+};
+use super::retrosheet::PlayKind;
+
+/*========================================================
+CONSTANTS
+========================================================*/
+/// calls to modern_game_flow() before a game is given up on and counted as a tie
+pub const MAX_GAME_STEPS: u32 = 5000;
+
+/// every swing result, in the order they're reported
+pub const AT_BAT_RESULTS: [AtBatResults; 9] = [
+    AtBatResults::Oddity,
+    AtBatResults::CriticalHit,
+    AtBatResults::Hit,
+    AtBatResults::Walk,
+    AtBatResults::PossibleError,
+    AtBatResults::ProductiveOut1,
+    AtBatResults::ProductiveOut2,
+    AtBatResults::Out,
+    AtBatResults::MegaOut,
+];
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// totals for one side over a batch of games
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SideTotals {
+    pub wins: u32,
+    pub runs: BTreeMap<u32, u32>, // runs scored in a game -> number of games
+    pub hits: u32,
+    pub errors: u32, // errors made by this side's defense
+}
+
+/// results of a batch of simulated games between the same two teams
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationReport {
+    pub games: u32,
+    pub ties: u32, // games that hit MAX_GAME_STEPS without a winner
+    pub home: SideTotals,
+    pub away: SideTotals,
+    pub at_bats: [u32; 9], // count of each AT_BAT_RESULTS entry
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl SideTotals {
+    fn add(&mut self, other: &SideTotals) {
+        self.wins += other.wins;
+        for (runs, games) in other.runs.iter() {
+            *self.runs.entry(*runs).or_default() += games;
+        }
+        self.hits += other.hits;
+        self.errors += other.errors;
+    }
+
+    fn total_runs(&self) -> u32 {
+        self.runs.iter().map(|(runs, games)| runs * games).sum()
+    }
+}
+
+impl SimulationReport {
+    /// combines the results of two batches
+    pub fn add(&mut self, other: &SimulationReport) {
+        self.games += other.games;
+        self.ties += other.ties;
+        self.home.add(&other.home);
+        self.away.add(&other.away);
+        for (count, other) in self.at_bats.iter_mut().zip(other.at_bats.iter()) {
+            *count += other;
+        }
+    }
+
+    /// adds one finished game
+    pub fn record(&mut self, state: &GameState) {
+        let home_runs = state.home_state.runs.iter().sum::<u32>();
+        let away_runs = state.away_state.runs.iter().sum::<u32>();
+        self.games += 1;
+        if state.status != GameStatus::Over || home_runs == away_runs {
+            self.ties += 1;
+        } else if home_runs > away_runs {
+            self.home.wins += 1;
+        } else {
+            self.away.wins += 1;
+        }
+        *self.home.runs.entry(home_runs).or_default() += 1;
+        *self.away.runs.entry(away_runs).or_default() += 1;
+        self.home.hits += state.home_state.hits.iter().sum::<u32>();
+        self.away.hits += state.away_state.hits.iter().sum::<u32>();
+        self.home.errors += state.home_state.errors.iter().sum::<u32>();
+        self.away.errors += state.away_state.errors.iter().sum::<u32>();
+        for play in state.plays.iter() {
+            if let PlayKind::AtBat(result) = &play.kind {
+                if let Some(i) = AT_BAT_RESULTS.iter().position(|r| r == result) {
+                    self.at_bats[i] += 1;
+                }
+            }
+        }
+    }
+
+    /// chance the home team wins, 0.0 - 1.0
+    pub fn home_win_pct(&self) -> f32 {
+        ratio(self.home.wins, self.games)
+    }
+
+    /// chance the away team wins, 0.0 - 1.0
+    pub fn away_win_pct(&self) -> f32 {
+        ratio(self.away.wins, self.games)
+    }
+
+    /// average (runs, hits, errors) per game for one side
+    pub fn means(&self, home: bool) -> (f32, f32, f32) {
+        let side = if home { &self.home } else { &self.away };
+        (
+            ratio(side.total_runs(), self.games),
+            ratio(side.hits, self.games),
+            ratio(side.errors, self.games),
+        )
+    }
+
+    /// share of plate appearances that ended in each AT_BAT_RESULTS entry
+    pub fn at_bat_frequencies(&self) -> Vec<(AtBatResults, f32)> {
+        let total = self.at_bats.iter().sum::<u32>();
+        AT_BAT_RESULTS
+            .iter()
+            .zip(self.at_bats.iter())
+            .map(|(result, count)| (result.clone(), ratio(*count, total)))
+            .collect()
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// plays one game to the end without any user input
+pub fn simulate_game(game: &GameModern) -> GameState {
+    let mut state = init_new_game_state(
+        game.home_active.pitching[0].clone(),
+        game.away_active.pitching[0].clone(),
+    );
+    let mut steps = 0;
+    while state.status != GameStatus::Over && steps < MAX_GAME_STEPS {
+        state = modern_game_flow(game, state, &mut DebugConfig::default());
+        steps += 1;
+    }
+
+    state
+}
+
+/// plays [games] games between the teams in [game], split across [threads] threads
+/// (0 uses one thread per core)
+pub fn simulate_games(game: &GameModern, games: u32, threads: usize) -> SimulationReport {
+    let threads = if threads == 0 {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        threads
+    }
+    .clamp(1, games.max(1) as usize);
+    // console output from thousands of games isn't useful to anyone
+    let mut game = game.clone();
+    game.quiet = true;
+    let game = &game;
+
+    let mut report = SimulationReport::default();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads as u32)
+            .map(|i| {
+                // spread the remainder over the first few threads
+                let count = games / threads as u32 + u32::from(i < games % threads as u32);
+                scope.spawn(move || {
+                    let mut report = SimulationReport::default();
+                    for _ in 0..count {
+                        report.record(&simulate_game(game));
+                    }
+                    report
+                })
+            })
+            .collect();
+        for worker in workers {
+            // a game that panicked is an engine bug, pass it on rather than report fewer games
+            match worker.join() {
+                Ok(partial) => report.add(&partial),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+    });
+
+    report
+}
+
+fn ratio(count: u32, total: u32) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f32 / total as f32
+    }
+}
@@ -0,0 +1,611 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+
+use crate::characters::players::Player;
+use crate::core::file_locations::{quote_csv_field, split_csv_line};
+use crate::core::game_functions::{
+    get_swing_position, AtBatResults, GameModern, GameState, InningTB, Outs,
+};
+
+/*========================================================
+CONSTANTS
+========================================================*/
+/// minimum plate appearances to show up on AVG/OBP/SLG leaderboards
+pub const QUALIFY_PA: u32 = 10;
+/// minimum outs recorded to show up on ERA/WHIP leaderboards (9 innings)
+pub const QUALIFY_OUTS: u32 = 27;
+/// team games a starting pitcher sits between starts, a five man rotation
+pub const REST_GAMES: u32 = 4;
+
+/*========================================================
+ENUM DEFINITIONS
+========================================================*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaderStat {
+    Average,
+    OnBase,
+    Slugging,
+    HomeRuns,
+    Era,
+    Whip,
+    Strikeouts,
+}
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// counting stats for a hitter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BattingLine {
+    pub games: u32,
+    pub plate_appearances: u32,
+    pub at_bats: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub home_runs: u32,
+    pub walks: u32,
+    pub strikeouts: u32,
+    pub rbi: u32,
+}
+
+/// counting stats for a pitcher
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PitchingLine {
+    pub games: u32,
+    pub outs: u32,
+    pub hits: u32,
+    pub runs: u32,
+    pub walks: u32,
+    pub strikeouts: u32,
+    pub home_runs: u32,
+}
+
+/// batting and pitching lines for one player in one season (or a career total)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerSeason {
+    pub batting: BattingLine,
+    pub pitching: PitchingLine,
+}
+
+/// team results for one season
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TeamSeason {
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub runs_scored: u32,
+    pub runs_allowed: u32,
+}
+
+/// stat lines for a single game, keyed by player_key()
+#[derive(Debug, Clone, Default)]
+pub struct BoxScore {
+    pub batting: BTreeMap<String, BattingLine>,
+    pub pitching: BTreeMap<String, PitchingLine>,
+    pub names: BTreeMap<String, String>, // player key -> name to show
+}
+
+/// season by season stats for every player and team, players are keyed by (player_key(), season)
+/// and teams by (name, season)
+#[derive(Debug, Clone, Default)]
+pub struct StatsDatabase {
+    pub loaded: bool,
+    pub players: BTreeMap<(String, u32), PlayerSeason>,
+    pub names: BTreeMap<String, String>, // player key -> most recent name, so renames keep their history
+    pub teams: BTreeMap<(String, u32), TeamSeason>,
+    pub starts: BTreeMap<(String, u32, String), u32>, // (team, season, pitcher) -> team game of the last start
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl BattingLine {
+    pub fn add(&mut self, other: &BattingLine) {
+        self.games += other.games;
+        self.plate_appearances += other.plate_appearances;
+        self.at_bats += other.at_bats;
+        self.hits += other.hits;
+        self.doubles += other.doubles;
+        self.triples += other.triples;
+        self.home_runs += other.home_runs;
+        self.walks += other.walks;
+        self.strikeouts += other.strikeouts;
+        self.rbi += other.rbi;
+    }
+
+    pub fn average(&self) -> f32 {
+        if self.at_bats == 0 {
+            return 0.0;
+        }
+        self.hits as f32 / self.at_bats as f32
+    }
+
+    pub fn on_base(&self) -> f32 {
+        if self.plate_appearances == 0 {
+            return 0.0;
+        }
+        (self.hits + self.walks) as f32 / self.plate_appearances as f32
+    }
+
+    pub fn slugging(&self) -> f32 {
+        if self.at_bats == 0 {
+            return 0.0;
+        }
+        // saturating so a hand edited stats file can't underflow
+        let singles = self
+            .hits
+            .saturating_sub(self.doubles + self.triples + self.home_runs);
+        let total_bases = singles + 2 * self.doubles + 3 * self.triples + 4 * self.home_runs;
+        total_bases as f32 / self.at_bats as f32
+    }
+}
+
+impl PitchingLine {
+    pub fn add(&mut self, other: &PitchingLine) {
+        self.games += other.games;
+        self.outs += other.outs;
+        self.hits += other.hits;
+        self.runs += other.runs;
+        self.walks += other.walks;
+        self.strikeouts += other.strikeouts;
+        self.home_runs += other.home_runs;
+    }
+
+    /// NOTE: no earned/unearned distinction yet, so this is really RA9
+    pub fn era(&self) -> f32 {
+        if self.outs == 0 {
+            return 0.0;
+        }
+        self.runs as f32 * 27.0 / self.outs as f32
+    }
+
+    pub fn whip(&self) -> f32 {
+        if self.outs == 0 {
+            return 0.0;
+        }
+        (self.walks + self.hits) as f32 * 3.0 / self.outs as f32
+    }
+
+    /// innings pitched in the usual 6.1/6.2 notation
+    pub fn innings(&self) -> String {
+        format!("{}.{}", self.outs / 3, self.outs % 3)
+    }
+}
+
+impl PlayerSeason {
+    pub fn add(&mut self, other: &PlayerSeason) {
+        self.batting.add(&other.batting);
+        self.pitching.add(&other.pitching);
+    }
+}
+
+impl StatsDatabase {
+    /// folds a finished game into the season totals for every player and both teams
+    pub fn record_game(&mut self, season: u32, game: &GameModern, state: &GameState) {
+        for (name, line) in state.box_score.batting.iter() {
+            let entry = self.players.entry((name.clone(), season)).or_default();
+            entry.batting.add(line);
+            entry.batting.games += 1;
+        }
+        for (name, line) in state.box_score.pitching.iter() {
+            let entry = self.players.entry((name.clone(), season)).or_default();
+            entry.pitching.add(line);
+            entry.pitching.games += 1;
+        }
+        for (key, name) in state.box_score.names.iter() {
+            self.names.insert(key.clone(), name.clone());
+        }
+        let home_runs = state.home_state.runs.iter().sum::<u32>();
+        let away_runs = state.away_state.runs.iter().sum::<u32>();
+        let home = self
+            .teams
+            .entry((game.home.name.clone(), season))
+            .or_default();
+        home.games += 1;
+        let home_game = home.games;
+        home.runs_scored += home_runs;
+        home.runs_allowed += away_runs;
+        if home_runs > away_runs {
+            home.wins += 1;
+        } else if away_runs > home_runs {
+            home.losses += 1;
+        }
+        let away = self
+            .teams
+            .entry((game.away.name.clone(), season))
+            .or_default();
+        away.games += 1;
+        let away_game = away.games;
+        away.runs_scored += away_runs;
+        away.runs_allowed += home_runs;
+        if away_runs > home_runs {
+            away.wins += 1;
+        } else if home_runs > away_runs {
+            away.losses += 1;
+        }
+        for (team, starter, team_game) in [
+            (&game.home.name, &state.home_state.starter, home_game),
+            (&game.away.name, &state.away_state.starter, away_game),
+        ] {
+            self.starts
+                .insert((team.clone(), season, player_key(starter)), team_game);
+            self.names.insert(player_key(starter), player_name(starter));
+        }
+    }
+
+    /// name to show for a player key, keys from before players had IDs are already names
+    pub fn name(&self, key: &str) -> String {
+        self.names
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// team games since [pitcher] last started for [team], None if they haven't started this season
+    pub fn games_rested(&self, team: &str, season: u32, pitcher: &Player) -> Option<u32> {
+        let last_start = self
+            .starts
+            .get(&(team.to_string(), season, player_key(pitcher)))?;
+        let games = self
+            .teams
+            .get(&(team.to_string(), season))
+            .map_or(0, |line| line.games);
+        Some(games.saturating_sub(*last_start))
+    }
+
+    pub fn is_rested(&self, team: &str, season: u32, pitcher: &Player) -> bool {
+        self.games_rested(team, season, pitcher)
+            .is_none_or(|rest| rest >= REST_GAMES)
+    }
+
+    /// index of the next starter in [rotation] - the first rested pitcher after whoever started
+    /// last, or the most rested one if nobody is
+    pub fn next_starter(&self, team: &str, season: u32, rotation: &[Player]) -> usize {
+        let rest: Vec<Option<u32>> = rotation
+            .iter()
+            .map(|pitcher| self.games_rested(team, season, pitcher))
+            .collect();
+        // the most recent start has the least rest
+        let last = rest
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rest)| rest.map(|rest| (i, rest)))
+            .min_by_key(|(_, rest)| *rest)
+            .map(|(i, _)| i);
+        let Some(last) = last else {
+            return 0;
+        };
+        (1..=rotation.len())
+            .map(|offset| (last + offset) % rotation.len())
+            .find(|i| rest[*i].is_none_or(|rest| rest >= REST_GAMES))
+            .unwrap_or_else(|| {
+                (0..rotation.len())
+                    .max_by_key(|i| rest[*i].unwrap_or(u32::MAX))
+                    .unwrap_or(0)
+            })
+    }
+
+    /// sorted list of every season on record
+    pub fn seasons(&self) -> Vec<u32> {
+        let mut seasons: Vec<u32> = self.players.keys().map(|(_, season)| *season).collect();
+        seasons.extend(self.teams.keys().map(|(_, season)| *season));
+        seasons.sort();
+        seasons.dedup();
+        seasons
+    }
+
+    /// every player key on record, sorted by the name shown for them
+    pub fn player_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.players.keys().map(|(key, _)| key.clone()).collect();
+        keys.dedup(); // keys are sorted by player first, so this is enough
+        keys.sort_by_cached_key(|key| self.name(key));
+        keys
+    }
+
+    /// returns each season for a player along with the career total
+    pub fn career(&self, key: &str) -> (Vec<(u32, PlayerSeason)>, PlayerSeason) {
+        let mut seasons = vec![];
+        let mut total = PlayerSeason::default();
+        for ((player, season), line) in self.players.iter() {
+            if player == key {
+                seasons.push((*season, line.clone()));
+                total.add(line);
+            }
+        }
+        (seasons, total)
+    }
+
+    /// returns the top [count] player keys for a stat, either for one season or for careers
+    pub fn leaderboard(
+        &self,
+        stat: LeaderStat,
+        season: Option<u32>,
+        count: usize,
+    ) -> Vec<(String, f32)> {
+        // combine seasons first so career leaderboards work the same way
+        let mut totals: BTreeMap<String, PlayerSeason> = BTreeMap::new();
+        for ((name, year), line) in self.players.iter() {
+            if season.is_none() || season == Some(*year) {
+                totals.entry(name.clone()).or_default().add(line);
+            }
+        }
+        let mut leaders: Vec<(String, f32)> = totals
+            .iter()
+            .filter_map(|(name, line)| {
+                let b = &line.batting;
+                let p = &line.pitching;
+                let value = match stat {
+                    LeaderStat::Average if b.plate_appearances >= QUALIFY_PA => b.average(),
+                    LeaderStat::OnBase if b.plate_appearances >= QUALIFY_PA => b.on_base(),
+                    LeaderStat::Slugging if b.plate_appearances >= QUALIFY_PA => b.slugging(),
+                    LeaderStat::HomeRuns if b.plate_appearances > 0 => b.home_runs as f32,
+                    LeaderStat::Era if p.outs >= QUALIFY_OUTS => p.era(),
+                    LeaderStat::Whip if p.outs >= QUALIFY_OUTS => p.whip(),
+                    LeaderStat::Strikeouts if p.outs > 0 => p.strikeouts as f32,
+                    _ => return None,
+                };
+                Some((name.clone(), value))
+            })
+            .collect();
+        // lower is better for ERA and WHIP
+        match stat {
+            LeaderStat::Era | LeaderStat::Whip => leaders.sort_by(|a, b| a.1.total_cmp(&b.1)),
+            _ => leaders.sort_by(|a, b| b.1.total_cmp(&a.1)),
+        }
+        leaders.truncate(count);
+        leaders
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// key used to track a player in the stats database, their ID so players who share a name stay
+/// apart and renaming someone keeps their history - players without one fall back to their name
+pub fn player_key(player: &Player) -> String {
+    if player.id.is_empty() {
+        player_name(player)
+    } else {
+        player.id.clone()
+    }
+}
+
+fn player_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+}
+
+fn outs_number(outs: &Outs) -> u32 {
+    match outs {
+        Outs::None => 0,
+        Outs::One => 1,
+        Outs::Two => 2,
+        Outs::Three => 3,
+    }
+}
+
+/// compares the game state before and after a plate appearance and adds the result to the box
+/// score for the batter and the current pitcher, [hit_bases] is what the hit table credited the
+/// batter with (0 for no hit, 4 for a home run)
+/// NOTE: outs with a last MSS digit of 0-2 are strikeouts, same as the out table in the rules
+pub fn record_plate_appearance(
+    before: &GameState,
+    mut after: GameState,
+    batter: &Player,
+    swing_result: &AtBatResults,
+    mss_result: &i32,
+    hit_bases: u32,
+) -> GameState {
+    // TODO: oddities can be hit by pitch, balks, etc. - skip them until the oddity table is done
+    if matches!(swing_result, AtBatResults::Oddity) {
+        return after;
+    }
+    let (off_before, off_after, pitcher) = match before.inning_half {
+        InningTB::Top => (
+            &before.away_state,
+            &after.away_state,
+            &before.home_state.current_pitcher,
+        ),
+        InningTB::Bottom => (
+            &before.home_state,
+            &after.home_state,
+            &before.away_state.current_pitcher,
+        ),
+    };
+    let runs = off_after.runs.iter().sum::<u32>() - off_before.runs.iter().sum::<u32>();
+    let outs = outs_number(&after.outs).saturating_sub(outs_number(&before.outs));
+    let walk = matches!(swing_result, AtBatResults::Walk);
+    let strikeout = matches!(swing_result, AtBatResults::Out | AtBatResults::MegaOut)
+        && get_swing_position(mss_result) <= 2;
+
+    let pitcher_key = player_key(pitcher);
+    for player in [batter, pitcher] {
+        after
+            .box_score
+            .names
+            .insert(player_key(player), player_name(player));
+    }
+    let batting = after
+        .box_score
+        .batting
+        .entry(player_key(batter))
+        .or_default();
+    batting.plate_appearances += 1;
+    if !walk {
+        batting.at_bats += 1;
+    }
+    if hit_bases > 0 {
+        batting.hits += 1;
+    }
+    match hit_bases {
+        2 => batting.doubles += 1,
+        3 => batting.triples += 1,
+        4 => batting.home_runs += 1,
+        _ => {}
+    }
+    if walk {
+        batting.walks += 1;
+    }
+    if strikeout {
+        batting.strikeouts += 1;
+    }
+    batting.rbi += runs;
+
+    let pitching = after.box_score.pitching.entry(pitcher_key).or_default();
+    pitching.outs += outs;
+    pitching.runs += runs;
+    if hit_bases > 0 {
+        pitching.hits += 1;
+    }
+    if hit_bases == 4 {
+        pitching.home_runs += 1;
+    }
+    if walk {
+        pitching.walks += 1;
+    }
+    if strikeout {
+        pitching.strikeouts += 1;
+    }
+
+    after
+}
+
+/// loads stats database from file, lines look like:
+/// PLAYER,key,season,G,PA,AB,H,2B,3B,HR,BB,K,RBI,G,OUTS,H,R,BB,K,HR
+/// TEAM,name,season,G,W,L,RS,RA
+/// NAME,key,name
+pub fn load_stats(filename: &str) -> Result<StatsDatabase, std::io::Error> {
+    let contents = fs::read_to_string(filename)?;
+    let mut db = StatsDatabase {
+        loaded: true,
+        ..Default::default()
+    };
+    for line in contents.lines() {
+        let fields = split_csv_line(line.trim());
+        if fields.len() < 3 {
+            continue;
+        }
+        let name = fields[1].clone();
+        if fields[0] == "NAME" && fields.len() == 3 {
+            db.names.insert(name, fields[2].clone());
+            continue;
+        }
+        let season = fields[2].parse::<u32>().unwrap_or(0);
+        if fields[0] == "START" && fields.len() == 5 {
+            let team_game = fields[4].trim().parse::<u32>().unwrap_or(0);
+            db.starts
+                .insert((name, season, fields[3].clone()), team_game);
+            continue;
+        }
+        let nums: Vec<u32> = fields[3..]
+            .iter()
+            .map(|x| x.trim().parse::<u32>().unwrap_or(0))
+            .collect();
+        if fields[0] == "PLAYER" && nums.len() == 17 {
+            let line = PlayerSeason {
+                batting: BattingLine {
+                    games: nums[0],
+                    plate_appearances: nums[1],
+                    at_bats: nums[2],
+                    hits: nums[3],
+                    doubles: nums[4],
+                    triples: nums[5],
+                    home_runs: nums[6],
+                    walks: nums[7],
+                    strikeouts: nums[8],
+                    rbi: nums[9],
+                },
+                pitching: PitchingLine {
+                    games: nums[10],
+                    outs: nums[11],
+                    hits: nums[12],
+                    runs: nums[13],
+                    walks: nums[14],
+                    strikeouts: nums[15],
+                    home_runs: nums[16],
+                },
+            };
+            db.players.insert((name, season), line);
+        } else if fields[0] == "TEAM" && nums.len() == 5 {
+            let line = TeamSeason {
+                games: nums[0],
+                wins: nums[1],
+                losses: nums[2],
+                runs_scored: nums[3],
+                runs_allowed: nums[4],
+            };
+            db.teams.insert((name, season), line);
+        }
+    }
+
+    Ok(db)
+}
+
+/// writes stats database to file
+pub fn write_stats(db: &StatsDatabase, filename: &str) -> Result<(), std::io::Error> {
+    let mut file = fs::File::create(filename)?;
+    for ((name, season), line) in db.players.iter() {
+        let b = &line.batting;
+        let p = &line.pitching;
+        writeln!(
+            file,
+            "PLAYER,{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            quote_csv_field(name),
+            season,
+            b.games,
+            b.plate_appearances,
+            b.at_bats,
+            b.hits,
+            b.doubles,
+            b.triples,
+            b.home_runs,
+            b.walks,
+            b.strikeouts,
+            b.rbi,
+            p.games,
+            p.outs,
+            p.hits,
+            p.runs,
+            p.walks,
+            p.strikeouts,
+            p.home_runs
+        )?;
+    }
+    for ((name, season), line) in db.teams.iter() {
+        writeln!(
+            file,
+            "TEAM,{},{},{},{},{},{},{}",
+            quote_csv_field(name),
+            season,
+            line.games,
+            line.wins,
+            line.losses,
+            line.runs_scored,
+            line.runs_allowed
+        )?;
+    }
+    for ((team, season, pitcher), team_game) in db.starts.iter() {
+        writeln!(
+            file,
+            "START,{},{},{},{}",
+            quote_csv_field(team),
+            season,
+            quote_csv_field(pitcher),
+            team_game
+        )?;
+    }
+    for (key, name) in db.names.iter() {
+        writeln!(
+            file,
+            "NAME,{},{}",
+            quote_csv_field(key),
+            quote_csv_field(name)
+        )?;
+    }
+
+    Ok(())
+}
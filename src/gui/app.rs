@@ -3,23 +3,32 @@ use crate::characters::ballparks::{BallparkAncient, BallparkModern};
  * IMPORTS
  * ===========================================================================================*/
 // LOCAL IMPORTS
+use super::diamond::{field_label, PlayAnimation, RunnerEnd, BASE_SPOTS, FIELDER_SPOTS, NAME_DROP};
 use super::draw_fn::*;
+use super::lineup::LineupPanel;
+use super::scoreboard::Scoreboard;
 use crate::characters::{players::*, teams::*};
-use crate::core::file_locations::{load_databases, DeadballDatabases};
+use crate::core::actions::{Action, ActionSide};
+use crate::core::file_locations::{
+    database_search_path, load_databases, DeadballDatabases, BALLPARK_FOLDER, DATA_LOCATION,
+    PLAYER_FOLDER, TEAM_FOLDER,
+};
+use crate::core::replay::{write_record, GameRecord};
+use crate::core::retrosheet::write_event_file;
+use crate::core::settings::{add_recent, Settings};
+use crate::core::stats::{load_stats, StatsDatabase};
 //use deadball::core::file_locations::*;
 use super::gui_functions::{
-    batter_tooltip, update_debug_textedits, BattingOrderWindow, CreateBallparkWindow,
-    CreatePlayerWindow, CreateTeamWindow, ToastData,
+    apply_settings, batter_tooltip, data_folder, get_player_name, play_action, redo_play,
+    save_settings, undo_play, update_debug_textedits, BallparkEditor, BattingOrderWindow,
+    CreatePlayerWindow, PhysicalDice, PlayerCard, PlayerEditor, ReplayWindow, SettingsWindow,
+    SimulationWindow, StatsWindow, TeamEditor, TeamWizard, ToastData, UndoHistory,
 };
 use crate::core::game_functions::{
-    bunt, find_by_position, hit_and_run, init_new_game_state, modern_game_flow,
-    new_game_state_struct, process_steals, GameModern, GameState, GameStatus, InningTB, Outs,
-    RunnersOn, StealType,
-};
-use crate::{
-    gui::debug::DebugConfig,
-    gui::gui_functions::{runners_on_bool, update_player_labels},
+    init_new_game_state, new_game_state_struct, GameModern, GameState, GameStatus, InningTB,
+    RunnersOn,
 };
+use crate::{gui::debug::DebugConfig, gui::gui_functions::update_player_labels};
 
 use eframe::egui::Image;
 // EXTERNAL IMPORTS
@@ -27,7 +36,7 @@ use eframe::{
     egui::{self, Context},
     epaint::{pos2, Color32},
 };
-use egui::{Rect, RichText};
+use egui::Rect;
 use egui_file::FileDialog;
 use egui_toast::{Toast, ToastKind, ToastOptions, ToastStyle, Toasts};
 use std::path::PathBuf;
@@ -41,6 +50,9 @@ pub const ABOUT_DEABALL: &str =
 pub const ABOUT_APP: &str = "This application was developed as practice with the Rust programming language.  All credit goes to the creator of Deadball, W.M. Akers.  Please purchase and consult the official rulebooks for questions about game mechanics.";
 
 pub const CUSTOM_TOAST: u32 = 0;
+/// where the diamond image sat when the player labels and helmets were laid out - the default
+/// 200px away panel plus margin, under the old two line score
+const FIELD_ORIGIN: egui::Pos2 = pos2(208.0, 50.0);
 fn custom_toast_contents(ui: &mut egui::Ui, toast: &mut Toast) -> egui::Response {
     egui::Frame::window(ui.style())
         .show(ui, |ui| {
@@ -63,33 +75,6 @@ enum Panel {
 /*==============================================================================================
  * STRUCTS
  * ===========================================================================================*/
-/// info for GUI scoreboard display
-pub struct Score {
-    pub current_inning: String,
-    pub current_outs: String,
-    pub away_hits: String,
-    pub away_errors: String,
-    pub away_runs: String,
-    pub home_hits: String,
-    pub home_errors: String,
-    pub home_runs: String,
-}
-
-impl Default for Score {
-    fn default() -> Self {
-        Self {
-            current_inning: "1^".to_string(),
-            current_outs: "0".to_string(),
-            away_hits: "0".to_string(),
-            away_errors: "0".to_string(),
-            away_runs: "0".to_string(),
-            home_hits: "0".to_string(),
-            home_errors: "0".to_string(),
-            home_runs: "0".to_string(),
-        }
-    }
-}
-
 /// images for GUI
 pub struct GuiImages<'a> {
     pub diamond_image: Image<'a>,
@@ -134,6 +119,24 @@ impl Default for DiamondLabels {
     }
 }
 
+impl DiamondLabels {
+    /// label for the fielder at [position], empty for players who aren't in the field
+    pub fn label(&self, position: &Position) -> &str {
+        match position {
+            Position::Pitcher => &self.pitcher_label,
+            Position::Catcher => &self.catcher_label,
+            Position::Firstbase => &self.firstbase_label,
+            Position::Secondbase => &self.secondbase_label,
+            Position::Shortstop => &self.shortstop_label,
+            Position::Thirdbase => &self.thirdbase_label,
+            Position::Rightfield => &self.rightfield_label,
+            Position::Centerfield => &self.centerfield_label,
+            Position::Leftfield => &self.leftfield_label,
+            Position::Bench | Position::None => "",
+        }
+    }
+}
+
 /// contains bools for controlling sub windows
 pub struct GuiWindows {
     pub version_window: bool,
@@ -146,6 +149,7 @@ pub struct GuiWindows {
     pub edit_roster_window: bool,
     pub team_info_window: bool,
     pub batting_order_window: bool,
+    pub diagnostics_window: bool,
 }
 
 impl Default for GuiWindows {
@@ -161,35 +165,7 @@ impl Default for GuiWindows {
             edit_roster_window: false,
             team_info_window: false,
             batting_order_window: false,
-        }
-    }
-}
-
-/// team batting order string structure
-pub struct BattingOrderStrings {
-    pub batter1: String,
-    pub batter2: String,
-    pub batter3: String,
-    pub batter4: String,
-    pub batter5: String,
-    pub batter6: String,
-    pub batter7: String,
-    pub batter8: String,
-    pub batter9: String,
-}
-
-impl Default for BattingOrderStrings {
-    fn default() -> Self {
-        Self {
-            batter1: "".to_string(),
-            batter2: "".to_string(),
-            batter3: "".to_string(),
-            batter4: "".to_string(),
-            batter5: "".to_string(),
-            batter6: "".to_string(),
-            batter7: "".to_string(),
-            batter8: "".to_string(),
-            batter9: "".to_string(),
+            diagnostics_window: false,
         }
     }
 }
@@ -201,6 +177,7 @@ pub struct DebugSettings {
     pub debug_inning_text: String,
     pub debug_inning_half_text: String,
     pub debug_outs_text: String,
+    pub debug_runners: RunnersOn,
     pub debug_runners_text: String,
     pub debug_batting1_text: String,
     pub debug_batting2_text: String,
@@ -225,6 +202,7 @@ impl Default for DebugSettings {
             debug_inning_text: "1".to_string(),
             debug_inning_half_text: "^".to_string(),
             debug_outs_text: "None".to_string(),
+            debug_runners: RunnersOn::Runner000,
             debug_runners_text: "000".to_string(),
             debug_batting1_text: "1".to_string(),
             debug_batting2_text: "1".to_string(),
@@ -270,10 +248,10 @@ impl Default for ActiveTeamEdit {
 
 pub struct DeadballApp<'a> {
     // score information
-    pub score: Score,
     // ballfield interface
     pub gui_images: GuiImages<'a>,
     pub diamond_labels: DiamondLabels,
+    pub play_animation: PlayAnimation,
     // batting order interface
     pub away_team_name: String,
     pub home_team_name: String,
@@ -290,36 +268,48 @@ pub struct DeadballApp<'a> {
     pub ballpark_file: Option<PathBuf>,
     pub ballpark_file_dialog: Option<FileDialog>,
     pub oddity: bool,
+    pub data_dir: String, // league folder for resolving player/ballpark IDs
     pub create_game_error: String,
     // game data
     pub away_team: Option<Team>,
     pub away_team_active: Option<ActiveTeam>,
-    pub away_bo: BattingOrderStrings,
     pub home_team: Option<Team>,
     pub home_team_active: Option<ActiveTeam>,
-    pub home_bo: BattingOrderStrings,
     pub ballpark_modern: Option<BallparkModern>,
     pub ballpark_ancient: Option<BallparkAncient>,
     pub game_modern: Option<GameModern>,
     pub game_state: Option<GameState>,
+    pub game_record: Option<GameRecord>, // seed and decisions, for replays
+    pub undo_history: UndoHistory,
+    pub stats_before_game: Option<StatsDatabase>, // stats without the finished game, for undo
+    pub physical_dice: PhysicalDice,
     pub active_team_edit: ActiveTeamEdit,
     pub batting_order_edit: BattingOrderWindow,
     // TODO: add ancient game
     // debug settings
     pub debug_settings: DebugSettings,
     pub toast_options: ToastData,
-    pub create_team: CreateTeamWindow,
+    pub team_wizard: TeamWizard,
     pub create_player: CreatePlayerWindow,
-    pub create_ballpark: CreateBallparkWindow,
+    pub ballpark_editor: BallparkEditor,
     pub databases: DeadballDatabases,
+    pub stats: StatsDatabase,
+    pub stats_window: StatsWindow,
+    pub team_editor: TeamEditor,
+    pub player_editor: PlayerEditor,
+    pub player_card: PlayerCard,
+    pub simulation_window: SimulationWindow,
+    pub replay_window: ReplayWindow,
+    pub settings: Settings,
+    pub settings_window: SettingsWindow,
 }
 
 impl Default for DeadballApp<'_> {
     fn default() -> Self {
         Self {
-            score: Score::default(),
             gui_images: GuiImages::default(),
             diamond_labels: DiamondLabels::default(),
+            play_animation: PlayAnimation::default(),
             away_team_name: "Away Team".to_owned(),
             home_team_name: "Home Team".to_owned(),
             bottom_panel: Panel::Menu,
@@ -332,29 +322,50 @@ impl Default for DeadballApp<'_> {
             ballpark_file: None,
             ballpark_file_dialog: None,
             oddity: false,
+            data_dir: DATA_LOCATION.to_owned(),
             create_game_error: "".to_owned(),
             away_team: None,
             away_team_active: None,
-            away_bo: BattingOrderStrings::default(),
             home_team: None,
             home_team_active: None,
-            home_bo: BattingOrderStrings::default(),
             ballpark_modern: None,
             ballpark_ancient: None,
             game_modern: None,
             game_state: None,
+            game_record: None,
+            undo_history: UndoHistory::default(),
+            stats_before_game: None,
+            physical_dice: PhysicalDice::default(),
             active_team_edit: ActiveTeamEdit::default(),
             batting_order_edit: BattingOrderWindow::default(),
             debug_settings: DebugSettings::default(),
             toast_options: ToastData::default(),
-            create_team: CreateTeamWindow::default(),
+            team_wizard: TeamWizard::default(),
             create_player: CreatePlayerWindow::default(),
-            create_ballpark: CreateBallparkWindow::default(),
+            ballpark_editor: BallparkEditor::default(),
             databases: DeadballDatabases::default(),
+            stats: StatsDatabase::default(),
+            stats_window: StatsWindow::default(),
+            team_editor: TeamEditor::default(),
+            player_editor: PlayerEditor::default(),
+            player_card: PlayerCard::default(),
+            simulation_window: SimulationWindow::default(),
+            replay_window: ReplayWindow::default(),
+            settings: Settings::default(),
+            settings_window: SettingsWindow::default(),
         }
     }
 }
 
+impl DeadballApp<'_> {
+    /// app set up from the saved settings, Default is the same with the built in ones
+    pub fn new(ctx: &egui::Context, settings: Settings) -> Self {
+        let mut app = Self::default();
+        apply_settings(&mut app, Some(ctx), settings);
+        app
+    }
+}
+
 impl eframe::App for DeadballApp<'_> {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // toast notification stuff
@@ -365,7 +376,53 @@ impl eframe::App for DeadballApp<'_> {
 
         // check if databases need to be loaded
         if !self.databases.loaded {
-            self.databases = load_databases(&mut toasts);
+            let search = database_search_path(&self.settings.data_dir);
+            self.databases = load_databases(&mut toasts, &search);
+        }
+        // same for the stats database, no file just means no games have been played yet
+        if !self.stats.loaded {
+            self.stats = load_stats(&self.settings.stats_file()).unwrap_or_default();
+            self.stats.loaded = true;
+        }
+
+        // window layout is saved on exit
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.settings.window_size = [rect.width(), rect.height()];
+        }
+        if let Some(rect) = ctx.input(|i| i.viewport().outer_rect) {
+            self.settings.window_pos = Some([rect.min.x, rect.min.y]);
+        }
+        self.settings.console_window = self.gui_windows.console_window;
+
+        // undo/redo shortcuts, text fields keep ctrl+z for themselves
+        if self.game_state.is_some() && !ctx.wants_keyboard_input() {
+            let undo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+            let redo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+            let redo_shift = egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::Z,
+            );
+            // shift version first, consume_shortcut ignores extra modifiers
+            let history = if ctx
+                .input_mut(|i| i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo))
+            {
+                redo_play(self)
+            } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+                undo_play(self)
+            } else {
+                Ok(false)
+            };
+            if let Err(err) = history {
+                toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: err.to_string().into(),
+                    style: ToastStyle::default(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(self.settings.toast_seconds)
+                        .show_progress(true)
+                        .show_icon(true),
+                });
+            }
         }
 
         // app state updates
@@ -381,168 +438,115 @@ impl eframe::App for DeadballApp<'_> {
         draw_create_new_game(ctx, self, &mut toasts);
         draw_debug_roll_window(ctx, self);
         draw_console_window(ctx, self);
-        draw_create_team_window(ctx, self, &mut toasts);
+        draw_diagnostics_window(ctx, self);
+        draw_team_wizard_window(ctx, self, &mut toasts);
         draw_create_player_window(ctx, self, &mut toasts);
-        draw_create_ballpark_window(ctx, self, &mut toasts);
+        draw_ballpark_editor_window(ctx, self, &mut toasts);
         draw_active_team_edit(ctx, self, &mut toasts);
         draw_batting_order_window(ctx, self, &mut toasts);
+        draw_stats_window(ctx, self);
+        draw_team_editor_window(ctx, self, &mut toasts);
+        draw_player_editor_window(ctx, self, &mut toasts);
+        draw_player_card_window(ctx, self);
+        draw_simulation_window(ctx, self);
+        draw_replay_window(ctx, self);
+        draw_physical_dice_window(ctx, self);
+        draw_settings_window(ctx, self, &mut toasts);
 
         // main window
         draw_bottom_panel(ctx, self, &mut toasts);
         draw_left_panel(ctx, self);
         draw_right_panel(ctx, self);
         egui::CentralPanel::default().show(ctx, |ui| {
-            // update GUI scoreboard values (if game is in progress)
-            let mut on_first = false;
-            let mut on_second = false;
-            let mut on_third = false;
-            if self.game_state.is_some() {
-                let inning_number = self.game_state.as_ref().unwrap().inning.to_string();
-                let inning_top_bottom: &str = match self.game_state.as_ref().unwrap().inning_half {
-                    InningTB::Top => "^",
-                    InningTB::Bottom => "v",
-                };
-                self.score.current_inning = inning_number + inning_top_bottom;
-                self.score.away_hits = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .away_state
-                    .hits
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                self.score.away_errors = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .away_state
-                    .errors
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                self.score.away_runs = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .away_state
-                    .runs
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                let out_string: String = match self.game_state.as_ref().unwrap().outs {
-                    Outs::None => "0".to_string(),
-                    Outs::One => "1".to_string(),
-                    Outs::Two => "2".to_string(),
-                    Outs::Three => "3".to_string(),
-                };
-                self.score.current_outs = out_string;
-                self.score.home_hits = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .home_state
-                    .hits
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                self.score.home_errors = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .home_state
-                    .errors
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                self.score.home_runs = self
-                    .game_state
-                    .as_ref()
-                    .unwrap()
-                    .home_state
-                    .runs
-                    .iter()
-                    .sum::<u32>()
-                    .to_string();
-                (on_first, on_second, on_third) =
-                    runners_on_bool(self.game_state.clone().unwrap().runners);
+            // line score, with the matchup once a game is loaded
+            if let Some(state) = &self.game_state {
+                let mut scoreboard = Scoreboard::new(state);
+                if let Some(game) = &self.game_modern {
+                    let (batting, bat_num, pitcher) = match state.inning_half {
+                        InningTB::Top => (
+                            &game.away_active,
+                            state.away_state.current_batter,
+                            &state.home_state.current_pitcher,
+                        ),
+                        InningTB::Bottom => (
+                            &game.home_active,
+                            state.home_state.current_batter,
+                            &state.away_state.current_pitcher,
+                        ),
+                    };
+                    scoreboard = scoreboard
+                        .teams(&game.away.name, &game.home.name)
+                        .matchup(batting.batting_order.get(bat_num as usize), Some(pitcher));
+                }
+                ui.add(scoreboard);
             }
-            // score line
-            ui.horizontal(|ui| {
-                ui.label("Inning:");
-                ui.label(&self.score.current_inning);
-                ui.label("AWAY");
-                ui.label("hits:");
-                ui.label(&self.score.away_hits);
-                ui.label("errors:");
-                ui.label(&self.score.away_errors);
-                ui.label("runs:");
-                ui.label(&self.score.away_runs);
-            });
-            ui.horizontal(|ui| {
-                ui.label("Outs:");
-                ui.label(&self.score.current_outs);
-                ui.label("HOME");
-                ui.label("hits:");
-                ui.label(&self.score.home_hits);
-                ui.label("errors:");
-                ui.label(&self.score.home_errors);
-                ui.label("runs:");
-                ui.label(&self.score.home_runs);
-            });
             // draw baseball field and label players
-            ui.add(
+            let field = ui.add(
                 self.gui_images
                     .diamond_image
                     .clone()
                     .max_size(egui::Vec2 { x: 511.8, y: 445.2 }),
             );
-            // draw helmets to indicate runners on base
-            if on_first {
-                // no error, game state should already exist
-                ui.put(
-                    Rect {
-                        min: pos2(490.0, 260.0),
-                        max: pos2(590.0, 360.0),
-                    },
-                    self.gui_images
-                        .helmet_image
-                        .clone()
-                        .max_size(egui::Vec2 { x: 51.2, y: 51.2 }),
-                )
-                .on_hover_text(batter_tooltip(
-                    &self.game_state.as_ref().unwrap().runner1.clone().unwrap(),
-                ));
+            // everything on the field is placed relative to where the diamond ended up
+            let offset = field.rect.min - FIELD_ORIGIN;
+            // pick up the last play for the moving runners and the fielder highlight
+            let now = ui.input(|i| i.time);
+            if let Some(state) = &self.game_state {
+                self.play_animation.update(state, now);
             }
-            if on_second {
-                ui.put(
-                    Rect {
-                        min: pos2(340.0, 120.0),
-                        max: pos2(440.0, 220.0),
-                    },
-                    self.gui_images
-                        .helmet_image
-                        .clone()
-                        .max_size(egui::Vec2 { x: 51.2, y: 51.2 }),
-                )
-                .on_hover_text(batter_tooltip(
-                    &self.game_state.as_ref().unwrap().runner2.clone().unwrap(),
-                ));
+            let animating = self.game_state.is_some() && self.play_animation.is_running(now);
+            if animating {
+                ui.ctx().request_repaint();
             }
-            if on_third {
-                ui.put(
-                    Rect {
-                        min: pos2(205.0, 270.0),
-                        max: pos2(305.0, 370.0),
-                    },
-                    self.gui_images
-                        .helmet_image
-                        .clone()
-                        .max_size(egui::Vec2 { x: 51.2, y: 51.2 }),
-                )
-                .on_hover_text(batter_tooltip(
-                    &self.game_state.as_ref().unwrap().runner3.clone().unwrap(),
-                ));
+            let helmet = self
+                .gui_images
+                .helmet_image
+                .clone()
+                .max_size(egui::Vec2 { x: 51.2, y: 51.2 });
+            if animating {
+                // runners on their way base to base, outs and runs marked on their names
+                let progress = self.play_animation.progress(now);
+                for runner_move in self.play_animation.moves.iter() {
+                    let spot = runner_move.position(progress) + offset;
+                    ui.put(
+                        Rect::from_center_size(spot, egui::vec2(100.0, 100.0)),
+                        helmet.clone(),
+                    )
+                    .on_hover_text(batter_tooltip(&runner_move.runner));
+                    let name = get_player_name(&runner_move.runner);
+                    let (text, background) = match runner_move.end {
+                        RunnerEnd::Out => (format!("{} - Out", name), Color32::LIGHT_RED),
+                        RunnerEnd::Scored => (format!("{} - Scores", name), Color32::LIGHT_GREEN),
+                        RunnerEnd::Base(_) => (name, Color32::WHITE),
+                    };
+                    ui.put(
+                        Rect::from_center_size(
+                            spot + egui::vec2(0.0, NAME_DROP[0]),
+                            egui::vec2(100.0, 20.0),
+                        ),
+                        field_label(&text, background),
+                    );
+                }
+            } else if let Some(state) = &self.game_state {
+                // draw helmets and names for runners on base
+                for base in 1..=3 {
+                    let Some(runner) = state.bases.runner(base) else {
+                        continue;
+                    };
+                    let spot = BASE_SPOTS[base as usize] + offset;
+                    ui.put(
+                        Rect::from_center_size(spot, egui::vec2(100.0, 100.0)),
+                        helmet.clone(),
+                    )
+                    .on_hover_text(batter_tooltip(runner));
+                    ui.put(
+                        Rect::from_center_size(
+                            spot + egui::vec2(0.0, NAME_DROP[base as usize]),
+                            egui::vec2(100.0, 20.0),
+                        ),
+                        field_label(&get_player_name(runner), Color32::WHITE),
+                    );
+                }
             }
             if self.game_state.is_some() {
                 // always draw batter
@@ -557,10 +561,7 @@ impl eframe::App for DeadballApp<'_> {
                     }
                 };
                 ui.put(
-                    Rect {
-                        min: pos2(340.0, 475.0),
-                        max: pos2(440.0, 495.0),
-                    },
+                    Rect::from_min_max(pos2(340.0, 475.0), pos2(440.0, 495.0)).translate(offset),
                     self.gui_images.helmet_image.clone(),
                 )
                 .on_hover_text(batter_tooltip(batter));
@@ -586,118 +587,30 @@ impl eframe::App for DeadballApp<'_> {
                 self.diamond_labels.rightfield_label = labels[7].clone();
                 self.diamond_labels.pitcher_label = labels[8].clone();
             }
-            // put player names
-            ui.put(
-                Rect {
-                    min: pos2(460.0, 260.0),
-                    max: pos2(560.0, 280.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.firstbase_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(400.0, 180.0),
-                    max: pos2(500.0, 200.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.secondbase_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(340.0, 305.0),
-                    max: pos2(440.0, 325.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.pitcher_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(340.0, 475.0),
-                    max: pos2(440.0, 495.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.catcher_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(200.0, 270.0),
-                    max: pos2(300.0, 290.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.thirdbase_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(240.0, 200.0),
-                    max: pos2(340.0, 220.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.shortstop_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(510.0, 100.0),
-                    max: pos2(610.0, 120.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.rightfield_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(330.0, 100.0),
-                    max: pos2(430.0, 120.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.centerfield_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
-            ui.put(
-                Rect {
-                    min: pos2(160.0, 100.0),
-                    max: pos2(260.0, 120.0),
-                },
-                eframe::egui::Label::new(
-                    RichText::new(&self.diamond_labels.leftfield_label)
-                        .color(Color32::BLACK)
-                        .strong()
-                        .background_color(Color32::WHITE), //.size(16.0),
-                ),
-            );
+            // put player names, the fielder on the last play is highlighted (red for an error)
+            for (position, spot) in FIELDER_SPOTS.iter() {
+                let label = self.diamond_labels.label(position);
+                let involved = self.game_state.is_some()
+                    && self.play_animation.fielder.as_ref() == Some(position);
+                let (text, background) = match (involved, self.play_animation.error) {
+                    (true, true) => (format!("E: {}", label), Color32::LIGHT_RED),
+                    (true, false) => (label.to_string(), Color32::YELLOW),
+                    (false, _) => (label.to_string(), Color32::WHITE),
+                };
+                ui.put(
+                    Rect::from_center_size(*spot + offset, egui::vec2(100.0, 20.0)),
+                    field_label(&text, background),
+                );
+            }
         });
         toasts.show(ctx);
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Err(err) = save_settings(self) {
+            eprintln!("Failed to save settings: {}", err);
+        }
+    }
 }
 
 /// renders the bottom panel
@@ -725,12 +638,19 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                                     app.home_team_active.clone().unwrap().pitching[0].clone(),
                                     app.away_team_active.clone().unwrap().pitching[0].clone(),
                                 ));
+                                // new seed every game, the record is what replays it
+                                app.game_record = app
+                                    .game_modern
+                                    .as_ref()
+                                    .map(|game| GameRecord::new(game, rand::random()));
+                                app.undo_history.clear();
+                                app.stats_before_game = None;
                                 toasts.add(Toast {
                                     kind: ToastKind::Info,
                                     text: "Play ball!".into(),
                                     style: ToastStyle::default(),
                                     options: ToastOptions::default()
-                                        .duration_in_seconds(3.0)
+                                        .duration_in_seconds(app.settings.toast_seconds)
                                         .show_progress(true)
                                         .show_icon(true),
                                 });
@@ -741,7 +661,7 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                                     text: "Create a game first.".into(),
                                     style: ToastStyle::default(),
                                     options: ToastOptions::default()
-                                        .duration_in_seconds(3.0)
+                                        .duration_in_seconds(app.settings.toast_seconds)
                                         .show_progress(true)
                                         .show_icon(true),
                                 });
@@ -756,6 +676,62 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                             // TODO: add save game feature
                             println!("Save game feature has not been added yet.");
                         }
+                        if ui.button("Simulate Matchup").clicked() {
+                            app.simulation_window.is_visible = true;
+                            ui.close_menu();
+                        }
+                        if ui.button("Save Game Record").clicked() {
+                            let text = match &app.game_record {
+                                Some(record) => {
+                                    match write_record(record, &app.settings.record_dir()) {
+                                        Ok(path) => {
+                                            let path = path.display().to_string();
+                                            add_recent(&mut app.settings.recent_saves, &path);
+                                            format!("Saved game record to {}", path)
+                                        }
+                                        Err(e) => format!("Failed to save game record: {}", e),
+                                    }
+                                }
+                                None => "Start a game first.".to_string(),
+                            };
+                            toasts.add(Toast {
+                                kind: ToastKind::Info,
+                                text: text.into(),
+                                style: ToastStyle::default(),
+                                options: ToastOptions::default()
+                                    .duration_in_seconds(app.settings.toast_seconds)
+                                    .show_progress(true)
+                                    .show_icon(true),
+                            });
+                            ui.close_menu();
+                        }
+                        if ui.button("Replay Game").clicked() {
+                            app.replay_window.is_visible = true;
+                            ui.close_menu();
+                        }
+                        if ui.button("Export Retrosheet").clicked() {
+                            // only finished games, event files are for complete play by play
+                            let text = match (&app.game_modern, &app.game_state) {
+                                (Some(game), Some(state)) if state.status == GameStatus::Over => {
+                                    let event_file = app.settings.event_file();
+                                    match write_event_file(game, state, &event_file) {
+                                        Ok(id) => format!("Exported {} to {}", id, event_file),
+                                        Err(e) => format!("Failed to export game: {}", e),
+                                    }
+                                }
+                                _ => "Finish a game before exporting it.".to_string(),
+                            };
+                            toasts.add(Toast {
+                                kind: ToastKind::Info,
+                                text: text.into(),
+                                style: ToastStyle::default(),
+                                options: ToastOptions::default()
+                                    .duration_in_seconds(app.settings.toast_seconds)
+                                    .show_progress(true)
+                                    .show_icon(true),
+                            });
+                            ui.close_menu();
+                        }
                     });
                     ui.menu_button("About", |ui| {
                         if ui.button("Version").clicked() {
@@ -779,7 +755,10 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                     ui.menu_button("Teams", |ui| {
                         // create/edit/find teams
                         if ui.button("Create New Team").clicked() {
-                            app.create_team.is_visible = true;
+                            if app.team_wizard.output_dir.is_empty() {
+                                app.team_wizard.output_dir = app.data_dir.clone();
+                            }
+                            app.team_wizard.is_visible = true;
                             ui.close_menu();
                         }
                     });
@@ -790,268 +769,153 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                             app.create_player.is_visible = true;
                             ui.close_menu();
                         }
+                        if ui.button("Edit Player").clicked() {
+                            if app.player_editor.file.is_empty() {
+                                app.player_editor.file = data_folder(&app.data_dir, PLAYER_FOLDER);
+                            }
+                            app.player_editor.is_visible = true;
+                            ui.close_menu();
+                        }
                     });
                     ui.menu_button("Ballparks", |ui| {
                         // create/edit/find ballparks
-                        if ui.button("Create New Ballpark").clicked() {
-                            // TODO: ballpark creation window - after figuring out file structure
-                            app.create_ballpark.is_visible = true;
+                        if ui.button("Ballpark Editor").clicked() {
+                            if app.ballpark_editor.save_location.is_empty() {
+                                app.ballpark_editor.save_location =
+                                    data_folder(&app.data_dir, BALLPARK_FOLDER);
+                            }
+                            app.ballpark_editor.is_visible = true;
+                            ui.close_menu();
+                        }
+                    });
+                    ui.menu_button("Stats", |ui| {
+                        if ui.button("Leaderboards").clicked() {
+                            app.stats_window.is_visible = true;
+                            app.stats_window.show_career = false;
+                            ui.close_menu();
+                        }
+                        if ui.button("Career Pages").clicked() {
+                            app.stats_window.is_visible = true;
+                            app.stats_window.show_career = true;
                             ui.close_menu();
                         }
                     });
+                    if ui.button("Settings").clicked() {
+                        app.settings_window.settings = app.settings.clone();
+                        app.settings_window.error.clear();
+                        app.settings_window.is_visible = true;
+                    }
                 });
             }
             Panel::Game => {
+                // what the rules allow right now decides which buttons can be pressed
+                let options = match (&app.game_state, &app.game_modern) {
+                    (Some(state), Some(game)) => state.action_options(game),
+                    _ => vec![],
+                };
+                let offense: Vec<_> = options
+                    .iter()
+                    .filter(|option| option.side == ActionSide::Offense)
+                    .collect();
+                let mut chosen = None;
+                let mut result = Ok(()); // errors from the play, undo or redo get a toast
                 ui.horizontal(|ui| {
                     if ui.button("Next At Bat").clicked() {
-                        // TODO: this could be cleaner
-                        if app.game_state.is_some() && app.game_modern.is_some() {
-                            // TODO: update with ancient game when ready
-                            match app.game_state.clone().unwrap().status {
-                                GameStatus::NotStarted => {
-                                    app.game_state.as_mut().unwrap().status = GameStatus::Ongoing
-                                }
-                                GameStatus::Ongoing => {
-                                    app.game_state = Some(modern_game_flow(
-                                        &app.game_modern.clone().unwrap(),
-                                        app.game_state.clone().unwrap(),
-                                        app.debug_settings.debug_roll_state.clone(),
-                                    ));
-                                }
-                                GameStatus::Over => {
-                                    toasts.add(Toast {
-                                        kind: ToastKind::Info,
-                                        text: "That's game!".into(),
-                                        style: ToastStyle::default(),
-                                        options: ToastOptions::default()
-                                            .duration_in_seconds(3.0)
-                                            .show_progress(true)
-                                            .show_icon(true),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    ui.menu_button("Steal", |ui| {
-                        // evaluate if steal conditions are met, show relevant options
-                        if app.game_state.is_some() {
-                            let mut steal2 = false;
-                            let mut steal3 = false;
-                            let mut steal4 = false;
-                            let mut double_steal = false;
-                            // runner on 1st can steal 2nd
-                            match app.game_state.as_ref().unwrap().runners {
-                                RunnersOn::Runner000 => {}
-                                RunnersOn::Runner100 => steal2 = true,
-                                RunnersOn::Runner010 => steal3 = true,
-                                RunnersOn::Runner001 => {
-                                    let runner3 = app.game_state.as_ref().unwrap().runner3.clone();
-                                    if runner3.is_some() && runner3.unwrap().speedy() {
-                                        steal4 = true;
-                                    }
-                                }
-                                RunnersOn::Runner110 => {
-                                    steal3 = true;
-                                    double_steal = true;
-                                }
-                                RunnersOn::Runner101 => {
-                                    steal2 = true;
-                                    let runner3 = app.game_state.as_ref().unwrap().runner3.clone();
-                                    if runner3.is_some() && runner3.unwrap().speedy() {
-                                        steal4 = true;
-                                    }
-                                }
-                                RunnersOn::Runner011 => {
-                                    let runner3 = app.game_state.as_ref().unwrap().runner3.clone();
-                                    if runner3.is_some() && runner3.unwrap().speedy() {
-                                        steal4 = true;
-                                    }
-                                }
-                                RunnersOn::Runner111 => {
-                                    let runner3 = app.game_state.as_ref().unwrap().runner3.clone();
-                                    if runner3.is_some() && runner3.unwrap().speedy() {
-                                        steal4 = true;
-                                    }
-                                }
-                            }
-                            // NOTE: I think it is okay to unwrap here, positions should exist when
-                            // game is created/roster is loaded
-                            let catcher: Player = match app.game_state.as_ref().unwrap().inning_half
-                            {
-                                InningTB::Top => find_by_position(
-                                    Position::Catcher,
-                                    &app.game_modern.as_ref().unwrap().home_active.roster,
-                                )
-                                .unwrap(),
-                                InningTB::Bottom => find_by_position(
-                                    Position::Catcher,
-                                    &app.game_modern.as_ref().unwrap().away_active.roster,
-                                )
-                                .unwrap(),
-                            };
-                            if steal2 && ui.button("Steal 2nd").clicked() {
-                                app.game_state = Some(process_steals(
-                                    StealType::Second,
-                                    app.game_state.clone().unwrap(),
-                                    app.debug_settings.debug_roll_state.clone(),
-                                    &catcher,
-                                ));
-                            }
-                            if steal3 && ui.button("Steal 3rd").clicked() {
-                                app.game_state = Some(process_steals(
-                                    StealType::Third,
-                                    app.game_state.clone().unwrap(),
-                                    app.debug_settings.debug_roll_state.clone(),
-                                    &catcher,
-                                ));
-                            }
-                            if steal4 && ui.button("Steal Home").clicked() {
-                                app.game_state = Some(process_steals(
-                                    StealType::Home,
-                                    app.game_state.clone().unwrap(),
-                                    app.debug_settings.debug_roll_state.clone(),
-                                    &catcher,
-                                ));
-                            }
-                            if double_steal && ui.button("Double Steal").clicked() {
-                                app.game_state = Some(process_steals(
-                                    StealType::Double,
-                                    app.game_state.clone().unwrap(),
-                                    app.debug_settings.debug_roll_state.clone(),
-                                    &catcher,
-                                ));
-                            }
-                            if !steal2 && !steal3 && !steal4 && !double_steal {
+                        // TODO: update with ancient game when ready
+                        match app.game_state.as_mut().map(|state| &mut state.status) {
+                            Some(status @ GameStatus::NotStarted) => *status = GameStatus::Ongoing,
+                            Some(GameStatus::Ongoing) => chosen = Some(Action::Swing),
+                            Some(GameStatus::Over) => {
                                 toasts.add(Toast {
                                     kind: ToastKind::Info,
-                                    text: "No runners on base.".into(),
+                                    text: "That's game!".into(),
                                     style: ToastStyle::default(),
                                     options: ToastOptions::default()
-                                        .duration_in_seconds(3.0)
+                                        .duration_in_seconds(app.settings.toast_seconds)
                                         .show_progress(true)
                                         .show_icon(true),
                                 });
                             }
-                        } else {
-                            // NOTE: I think the fact that these are in a menu means a bunch get
-                            // spammed, going to have to do something different
-                            toasts.add(Toast {
-                                kind: ToastKind::Info,
-                                text: "No active game.".into(),
-                                style: ToastStyle::default(),
-                                options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
-                                    .show_progress(true)
-                                    .show_icon(true),
-                            });
-                        }
-                    });
-                    if ui.button("Bunt").clicked() {
-                        if app.game_state.is_some() && app.game_modern.is_some() {
-                            // TODO: check and make sure base runners make sense
-                            if app.game_state.as_ref().unwrap().runners == RunnersOn::Runner000 {
+                            None => {
                                 toasts.add(Toast {
                                     kind: ToastKind::Info,
-                                    text: "No runners on, why bunt?".into(),
+                                    text: "No active game.".into(),
                                     style: ToastStyle::default(),
                                     options: ToastOptions::default()
-                                        .duration_in_seconds(3.0)
+                                        .duration_in_seconds(app.settings.toast_seconds)
                                         .show_progress(true)
                                         .show_icon(true),
                                 });
                             }
-                            let batter = match app.game_state.as_ref().unwrap().inning_half {
-                                InningTB::Top => {
-                                    let bat_num =
-                                        app.game_state.as_ref().unwrap().away_state.current_batter;
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [bat_num as usize]
-                                        .clone()
-                                }
-                                InningTB::Bottom => {
-                                    let bat_num =
-                                        app.game_state.as_ref().unwrap().home_state.current_batter;
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [bat_num as usize]
-                                        .clone()
-                                }
-                            };
-                            app.game_state = Some(bunt(
-                                app.game_state.clone().unwrap(),
-                                app.game_modern.as_ref().unwrap(),
-                                app.debug_settings.debug_roll_state.clone(),
-                                batter,
-                            ));
-                        } else {
-                            toasts.add(Toast {
-                                kind: ToastKind::Info,
-                                text: "No active game.".into(),
-                                style: ToastStyle::default(),
-                                options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
-                                    .show_progress(true)
-                                    .show_icon(true),
-                            });
                         }
                     }
-                    if ui.button("Hit & Run").clicked() {
-                        if app.game_state.is_some() && app.game_modern.is_some() {
-                            if app.game_state.as_ref().unwrap().runners == RunnersOn::Runner100 {
-                                let batter = match app.game_state.as_ref().unwrap().inning_half {
-                                    InningTB::Top => {
-                                        let bat_num = app
-                                            .game_state
-                                            .as_ref()
-                                            .unwrap()
-                                            .away_state
-                                            .current_batter;
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [bat_num as usize]
-                                            .clone()
-                                    }
-                                    InningTB::Bottom => {
-                                        let bat_num = app
-                                            .game_state
-                                            .as_ref()
-                                            .unwrap()
-                                            .home_state
-                                            .current_batter;
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [bat_num as usize]
-                                            .clone()
-                                    }
-                                };
-                                app.game_state = Some(hit_and_run(
-                                    app.game_state.clone().unwrap(),
-                                    app.game_modern.as_ref().unwrap(),
-                                    &mut app.debug_settings.debug_roll_state,
-                                    batter,
-                                ));
-                            } else {
-                                toasts.add(Toast {
-                                    kind: ToastKind::Info,
-                                    text: "Hit and run only available with a runner on first."
-                                        .into(),
-                                    style: ToastStyle::default(),
-                                    options: ToastOptions::default()
-                                        .duration_in_seconds(3.0)
-                                        .show_progress(true)
-                                        .show_icon(true),
-                                });
+                    ui.menu_button("Steal", |ui| {
+                        if options.is_empty() {
+                            ui.label("No active game.");
+                        }
+                        for option in &offense {
+                            if matches!(option.action, Action::Steal(_))
+                                && ui
+                                    .add_enabled(
+                                        option.legal,
+                                        egui::Button::new(option.action.label()),
+                                    )
+                                    .on_disabled_hover_text(option.precondition)
+                                    .clicked()
+                            {
+                                chosen = Some(option.action.clone());
+                                ui.close_menu();
                             }
-                        } else {
-                            toasts.add(Toast {
-                                kind: ToastKind::Info,
-                                text: "No active game.".into(),
-                                style: ToastStyle::default(),
-                                options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
-                                    .show_progress(true)
-                                    .show_icon(true),
-                            });
                         }
+                    });
+                    for option in &offense {
+                        if matches!(option.action, Action::Bunt | Action::HitAndRun)
+                            && ui
+                                .add_enabled(option.legal, egui::Button::new(option.action.label()))
+                                .on_disabled_hover_text(option.precondition)
+                                .clicked()
+                        {
+                            chosen = Some(option.action.clone());
+                        }
+                    }
+                    ui.separator();
+                    if ui
+                        .checkbox(&mut app.physical_dice.enabled, "Physical Dice")
+                        .on_hover_text("Enter the rolls from your own dice.")
+                        .changed()
+                        && !app.physical_dice.enabled
+                    {
+                        app.physical_dice = PhysicalDice::default();
+                    }
+                    if ui
+                        .add_enabled(app.undo_history.can_undo(), egui::Button::new("Undo"))
+                        .on_hover_text("Ctrl+Z")
+                        .clicked()
+                    {
+                        result = undo_play(app).map(|_| ());
+                    }
+                    if ui
+                        .add_enabled(app.undo_history.can_redo(), egui::Button::new("Redo"))
+                        .on_hover_text("Ctrl+Y")
+                        .clicked()
+                    {
+                        result = redo_play(app).map(|_| ());
                     }
                 });
+                if let Some(action) = chosen {
+                    result = play_action(app, action);
+                }
+                if let Err(err) = result {
+                    toasts.add(Toast {
+                        kind: ToastKind::Error,
+                        text: err.to_string().into(),
+                        style: ToastStyle::default(),
+                        options: ToastOptions::default()
+                            .duration_in_seconds(app.settings.toast_seconds)
+                            .show_progress(true)
+                            .show_icon(true),
+                    });
+                }
             }
             Panel::Roster => {
                 ui.horizontal(|ui| {
@@ -1081,7 +945,7 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                                 kind: ToastKind::Info,
                                 style: ToastStyle::default(),
                                 options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
+                                    .duration_in_seconds(app.settings.toast_seconds)
                                     .show_progress(true)
                                     .show_icon(true),
                             });
@@ -1112,7 +976,7 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                                 kind: ToastKind::Info,
                                 text: "No active game.".into(),
                                 options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
+                                    .duration_in_seconds(app.settings.toast_seconds)
                                     .show_progress(true)
                                     .show_icon(true),
                                 style: ToastStyle::default(),
@@ -1144,7 +1008,7 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                                 kind: ToastKind::Info,
                                 text: "No active game.".into(),
                                 options: ToastOptions::default()
-                                    .duration_in_seconds(3.0)
+                                    .duration_in_seconds(app.settings.toast_seconds)
                                     .show_progress(true)
                                     .show_icon(true),
                                 style: ToastStyle::default(),
@@ -1152,7 +1016,10 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                         }
                     });
                     if ui.button("View Team").clicked() {
-                        println!("View Team button placeholder.");
+                        if app.team_editor.file.is_empty() {
+                            app.team_editor.file = data_folder(&app.data_dir, TEAM_FOLDER);
+                        }
+                        app.team_editor.is_visible = true;
                     }
                 });
             }
@@ -1168,6 +1035,9 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
                     if ui.button("Console").clicked() {
                         app.gui_windows.console_window = true;
                     }
+                    if ui.button("Diagnostics").clicked() {
+                        app.gui_windows.diagnostics_window = true;
+                    }
                 });
             }
         }
@@ -1177,410 +1047,47 @@ fn draw_bottom_panel(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts)
 /// render left panel
 fn draw_left_panel(ctx: &Context, app: &mut DeadballApp) {
     egui::SidePanel::left("Away Team").show(ctx, |ui| {
-        ui.heading(&app.away_team_name);
-        let mut away_info1 = "".to_string();
-        let mut away_info2 = "".to_string();
-        let mut away_info3 = "".to_string();
-        let mut away_info4 = "".to_string();
-        let mut away_info5 = "".to_string();
-        let mut away_info6 = "".to_string();
-        let mut away_info7 = "".to_string();
-        let mut away_info8 = "".to_string();
-        let mut away_info9 = "".to_string();
-        if app.away_team.is_some() {
-            let away_team = app.away_team.as_ref().unwrap();
+        if let Some(away_team) = &app.away_team {
             app.away_team_name = away_team.name.to_string();
-            let batter1 = &app.game_modern.as_ref().unwrap().away_active.batting_order[0];
-            app.away_bo.batter1 = format!("{} {}", &batter1.first_name, &batter1.last_name);
-            let batter2 = &app.game_modern.as_ref().unwrap().away_active.batting_order[1];
-            app.away_bo.batter2 = format!("{} {}", &batter2.first_name, &batter2.last_name);
-            let batter3 = &app.game_modern.as_ref().unwrap().away_active.batting_order[2];
-            app.away_bo.batter3 = format!("{} {}", &batter3.first_name, &batter3.last_name);
-            let batter4 = &app.game_modern.as_ref().unwrap().away_active.batting_order[3];
-            app.away_bo.batter4 = format!("{} {}", &batter4.first_name, &batter4.last_name);
-            let batter5 = &app.game_modern.as_ref().unwrap().away_active.batting_order[4];
-            app.away_bo.batter5 = format!("{} {}", &batter5.first_name, &batter5.last_name);
-            let batter6 = &app.game_modern.as_ref().unwrap().away_active.batting_order[5];
-            app.away_bo.batter6 = format!("{} {}", &batter6.first_name, &batter6.last_name);
-            let batter7 = &app.game_modern.as_ref().unwrap().away_active.batting_order[6];
-            app.away_bo.batter7 = format!("{} {}", &batter7.first_name, &batter7.last_name);
-            let batter8 = &app.game_modern.as_ref().unwrap().away_active.batting_order[7];
-            app.away_bo.batter8 = format!("{} {}", &batter8.first_name, &batter8.last_name);
-            let batter9 = &app.game_modern.as_ref().unwrap().away_active.batting_order[8];
-            app.away_bo.batter9 = format!("{} {}", &batter9.first_name, &batter9.last_name);
-            away_info1 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter1.position,
-                batter1.handedness,
-                batter1.batter_target,
-                batter1.on_base_target,
-                batter1.traits,
-            );
-            away_info2 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter2.position,
-                batter2.handedness,
-                batter2.batter_target,
-                batter2.on_base_target,
-                batter2.traits,
-            );
-            away_info3 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter3.position,
-                batter3.handedness,
-                batter3.batter_target,
-                batter3.on_base_target,
-                batter3.traits,
-            );
-            away_info4 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter4.position,
-                batter4.handedness,
-                batter4.batter_target,
-                batter4.on_base_target,
-                batter4.traits,
-            );
-            away_info5 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter5.position,
-                batter5.handedness,
-                batter5.batter_target,
-                batter5.on_base_target,
-                batter5.traits,
-            );
-            away_info6 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter6.position,
-                batter6.handedness,
-                batter6.batter_target,
-                batter6.on_base_target,
-                batter6.traits,
-            );
-            away_info7 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter7.position,
-                batter7.handedness,
-                batter7.batter_target,
-                batter7.on_base_target,
-                batter7.traits,
-            );
-            away_info8 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter8.position,
-                batter8.handedness,
-                batter8.batter_target,
-                batter8.on_base_target,
-                batter8.traits,
-            );
-            away_info9 = format!(
-                "{:?} | {:?} | {} | {} | {:?} ",
-                batter9.position,
-                batter9.handedness,
-                batter9.batter_target,
-                batter9.on_base_target,
-                batter9.traits,
-            );
-        }
-        let mut away_at_bat = 1;
-        if app.game_state.is_some() {
-            away_at_bat = app.game_state.clone().unwrap().away_state.current_batter + 1;
-            // array indexing :/
         }
-        ui.horizontal(|ui| {
-            if away_at_bat == 1 {
-                ui.label(RichText::new("1. ").strong());
-                ui.label(RichText::new(app.away_bo.batter1.clone()).strong())
-                    .on_hover_text(away_info1);
-            // TODO: figure out a way to put baseball icon to indicate current batter
-            } else {
-                ui.label("1. ");
-                ui.label(&app.away_bo.batter1).on_hover_text(away_info1);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 2 {
-                ui.label(RichText::new("2. ").strong());
-                ui.label(RichText::new(app.away_bo.batter2.clone()).strong())
-                    .on_hover_text(away_info2);
-            } else {
-                ui.label("2. ");
-                ui.label(&app.away_bo.batter2).on_hover_text(away_info2);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 3 {
-                ui.label(RichText::new("3. ").strong());
-                ui.label(RichText::new(app.away_bo.batter3.clone()).strong())
-                    .on_hover_text(away_info3);
-            } else {
-                ui.label("3. ");
-                ui.label(&app.away_bo.batter3).on_hover_text(away_info3);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 4 {
-                ui.label(RichText::new("4. ").strong());
-                ui.label(RichText::new(app.away_bo.batter4.clone()).strong())
-                    .on_hover_text(away_info4);
-            } else {
-                ui.label("4. ");
-                ui.label(&app.away_bo.batter4).on_hover_text(away_info4);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 5 {
-                ui.label(RichText::new("5. ").strong());
-                ui.label(RichText::new(app.away_bo.batter5.clone()).strong())
-                    .on_hover_text(away_info5);
-            } else {
-                ui.label("5. ");
-                ui.label(&app.away_bo.batter5).on_hover_text(away_info5);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 6 {
-                ui.label(RichText::new("6. ").strong());
-                ui.label(RichText::new(app.away_bo.batter6.clone()).strong())
-                    .on_hover_text(away_info6);
-            } else {
-                ui.label("6. ");
-                ui.label(&app.away_bo.batter6).on_hover_text(away_info6);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 7 {
-                ui.label(RichText::new("7. ").strong());
-                ui.label(RichText::new(app.away_bo.batter7.clone()).strong())
-                    .on_hover_text(away_info7);
-            } else {
-                ui.label("7. ");
-                ui.label(&app.away_bo.batter7).on_hover_text(away_info7);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 8 {
-                ui.label(RichText::new("8. ").strong());
-                ui.label(RichText::new(app.away_bo.batter8.clone()).strong())
-                    .on_hover_text(away_info8);
-            } else {
-                ui.label("8. ");
-                ui.label(&app.away_bo.batter8).on_hover_text(away_info8);
-            }
-        });
-        ui.horizontal(|ui| {
-            if away_at_bat == 9 {
-                ui.label(RichText::new("9. ").strong());
-                ui.label(RichText::new(app.away_bo.batter9.clone()).strong())
-                    .on_hover_text(away_info9);
-            } else {
-                ui.label("9. ");
-                ui.label(&app.away_bo.batter9).on_hover_text(away_info9);
-            }
-        });
+        ui.heading(&app.away_team_name);
+        draw_lineup(ui, app, InningTB::Top);
     });
 }
 
 /// renders the right panel of the main interface
 fn draw_right_panel(ctx: &Context, app: &mut DeadballApp) {
     egui::SidePanel::right("Home Team").show(ctx, |ui| {
-        ui.heading(&app.home_team_name);
-        let mut home_info1 = "".to_string();
-        let mut home_info2 = "".to_string();
-        let mut home_info3 = "".to_string();
-        let mut home_info4 = "".to_string();
-        let mut home_info5 = "".to_string();
-        let mut home_info6 = "".to_string();
-        let mut home_info7 = "".to_string();
-        let mut home_info8 = "".to_string();
-        let mut home_info9 = "".to_string();
-        if app.home_team.is_some() {
-            let home_team = app.home_team.as_ref().unwrap();
+        if let Some(home_team) = &app.home_team {
             app.home_team_name = home_team.name.to_string();
-            let batter1 = &app.game_modern.as_ref().unwrap().home_active.batting_order[0];
-            app.home_bo.batter1 = format!("{} {}", &batter1.first_name, &batter1.last_name);
-            let batter2 = &app.game_modern.as_ref().unwrap().home_active.batting_order[1];
-            app.home_bo.batter2 = format!("{} {}", &batter2.first_name, &batter2.last_name);
-            let batter3 = &app.game_modern.as_ref().unwrap().home_active.batting_order[2];
-            app.home_bo.batter3 = format!("{} {}", &batter3.first_name, &batter3.last_name);
-            let batter4 = &app.game_modern.as_ref().unwrap().home_active.batting_order[3];
-            app.home_bo.batter4 = format!("{} {}", &batter4.first_name, &batter4.last_name);
-            let batter5 = &app.game_modern.as_ref().unwrap().home_active.batting_order[4];
-            app.home_bo.batter5 = format!("{} {}", &batter5.first_name, &batter5.last_name);
-            let batter6 = &app.game_modern.as_ref().unwrap().home_active.batting_order[5];
-            app.home_bo.batter6 = format!("{} {}", &batter6.first_name, &batter6.last_name);
-            let batter7 = &app.game_modern.as_ref().unwrap().home_active.batting_order[6];
-            app.home_bo.batter7 = format!("{} {}", &batter7.first_name, &batter7.last_name);
-            let batter8 = &app.game_modern.as_ref().unwrap().home_active.batting_order[7];
-            app.home_bo.batter8 = format!("{} {}", &batter8.first_name, &batter8.last_name);
-            let batter9 = &app.game_modern.as_ref().unwrap().home_active.batting_order[8];
-            app.home_bo.batter9 = format!("{} {}", &batter9.first_name, &batter9.last_name);
-            home_info1 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter1.position,
-                batter1.handedness,
-                batter1.batter_target,
-                batter1.on_base_target,
-                batter1.traits
-            );
-            home_info2 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter2.position,
-                batter2.handedness,
-                batter2.batter_target,
-                batter2.on_base_target,
-                batter2.traits
-            );
-            home_info3 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter3.position,
-                batter3.handedness,
-                batter3.batter_target,
-                batter3.on_base_target,
-                batter3.traits
-            );
-            home_info4 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter4.position,
-                batter4.handedness,
-                batter4.batter_target,
-                batter4.on_base_target,
-                batter4.traits
-            );
-            home_info5 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter5.position,
-                batter5.handedness,
-                batter5.batter_target,
-                batter5.on_base_target,
-                batter5.traits
-            );
-            home_info6 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter6.position,
-                batter6.handedness,
-                batter6.batter_target,
-                batter6.on_base_target,
-                batter6.traits
-            );
-            home_info7 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter7.position,
-                batter7.handedness,
-                batter7.batter_target,
-                batter7.on_base_target,
-                batter7.traits
-            );
-            home_info8 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter8.position,
-                batter8.handedness,
-                batter8.batter_target,
-                batter8.on_base_target,
-                batter8.traits
-            );
-            home_info9 = format!(
-                "{:?} | {:?} | {} | {} | {:?}",
-                batter9.position,
-                batter9.handedness,
-                batter9.batter_target,
-                batter9.on_base_target,
-                batter9.traits
-            );
-        }
-        let mut home_at_bat = 1;
-        if app.game_state.is_some() {
-            home_at_bat = app.game_state.clone().unwrap().home_state.current_batter + 1;
-            // array indexing :/
         }
-        ui.horizontal(|ui| {
-            if home_at_bat == 1 {
-                ui.label(RichText::new("1. ").strong());
-                ui.label(RichText::new(app.home_bo.batter1.clone()).strong())
-                    .on_hover_text(home_info1);
-            } else {
-                ui.label("1. ");
-                ui.label(&app.home_bo.batter1).on_hover_text(home_info1);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 2 {
-                ui.label(RichText::new("2. ").strong());
-                ui.label(RichText::new(app.home_bo.batter2.clone()).strong())
-                    .on_hover_text(home_info2);
-            } else {
-                ui.label("2. ");
-                ui.label(&app.home_bo.batter2).on_hover_text(home_info2);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 3 {
-                ui.label(RichText::new("3. ").strong());
-                ui.label(RichText::new(app.home_bo.batter3.clone()).strong())
-                    .on_hover_text(home_info3);
-            } else {
-                ui.label("3. ");
-                ui.label(&app.home_bo.batter3).on_hover_text(home_info3);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 4 {
-                ui.label(RichText::new("4. ").strong());
-                ui.label(RichText::new(app.home_bo.batter4.clone()).strong())
-                    .on_hover_text(home_info4);
-            } else {
-                ui.label("4. ");
-                ui.label(&app.home_bo.batter4).on_hover_text(home_info4);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 5 {
-                ui.label(RichText::new("5. ").strong());
-                ui.label(RichText::new(app.home_bo.batter5.clone()).strong())
-                    .on_hover_text(home_info5);
-            } else {
-                ui.label("5. ");
-                ui.label(&app.home_bo.batter5).on_hover_text(home_info5);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 6 {
-                ui.label(RichText::new("6. ").strong());
-                ui.label(RichText::new(app.home_bo.batter6.clone()).strong())
-                    .on_hover_text(home_info6);
-            } else {
-                ui.label("6. ");
-                ui.label(&app.home_bo.batter6).on_hover_text(home_info6);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 7 {
-                ui.label(RichText::new("7. ").strong());
-                ui.label(RichText::new(app.home_bo.batter7.clone()).strong())
-                    .on_hover_text(home_info7);
-            } else {
-                ui.label("7. ");
-                ui.label(&app.home_bo.batter7).on_hover_text(home_info7);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 8 {
-                ui.label(RichText::new("8. ").strong());
-                ui.label(RichText::new(app.home_bo.batter8.clone()).strong())
-                    .on_hover_text(home_info8);
-            } else {
-                ui.label("8. ");
-                ui.label(&app.home_bo.batter8).on_hover_text(home_info8);
-            }
-        });
-        ui.horizontal(|ui| {
-            if home_at_bat == 9 {
-                ui.label(RichText::new("9. ").strong());
-                ui.label(RichText::new(app.home_bo.batter9.clone()).strong())
-                    .on_hover_text(home_info9);
-            } else {
-                ui.label("9. ");
-                ui.label(&app.home_bo.batter9).on_hover_text(home_info9);
-            }
-        });
+        ui.heading(&app.home_team_name);
+        draw_lineup(ui, app, InningTB::Bottom);
     });
 }
+
+/// batting order for the team that bats in [half], clicking a name opens their player card
+fn draw_lineup(ui: &mut egui::Ui, app: &mut DeadballApp, half: InningTB) {
+    let Some(game) = &app.game_modern else {
+        return;
+    };
+    let (active, team_state) = match half {
+        InningTB::Top => (
+            &game.away_active,
+            app.game_state.as_ref().map(|state| &state.away_state),
+        ),
+        InningTB::Bottom => (
+            &game.home_active,
+            app.game_state.as_ref().map(|state| &state.home_state),
+        ),
+    };
+    let clicked = LineupPanel::new(&active.batting_order)
+        .current_batter(team_state.map(|team| team.current_batter as usize))
+        .pitcher(team_state.map(|team| &team.current_pitcher))
+        .box_score(app.game_state.as_ref().map(|state| &state.box_score))
+        .show(ui);
+    if let Some(player) = clicked {
+        app.player_card.player = Some(player);
+        app.player_card.is_visible = true;
+    }
+}
@@ -1,5 +1,3 @@
-use rand::Rng;
-
 use crate::core::roll;
 
 #[derive(Clone)]
@@ -7,6 +5,15 @@ pub struct DebugConfig {
     pub mode: bool,
     pub rolls: Vec<i32>,
     pub roll_index: usize,
+    pub physical: bool, // physical dice, running out of rolls asks the players instead of rng
+    pub missing: Option<RollRequest>, // first roll a physical dice play didn't have
+}
+
+/// a roll the engine needs from the players' dice
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollRequest {
+    pub side: i32,
+    pub purpose: String,
 }
 
 impl Default for DebugConfig {
@@ -15,11 +22,26 @@ impl Default for DebugConfig {
             mode: false,
             rolls: vec![0],
             roll_index: 0,
+            physical: false,
+            missing: None,
+        }
+    }
+}
+
+impl DebugConfig {
+    /// config for resolving a play with physical dice, [rolls] are the values entered so far
+    pub fn physical(rolls: Vec<i32>) -> Self {
+        Self {
+            mode: true,
+            rolls,
+            roll_index: 0,
+            physical: true,
+            missing: None,
         }
     }
 }
 
-pub fn debug_roll(config: &mut DebugConfig, side: i32) -> i32 {
+pub fn debug_roll(config: &mut DebugConfig, side: i32, purpose: &str) -> i32 {
     let roll: i32;
     if side == 1 {
         roll = 1;
@@ -28,17 +50,28 @@ pub fn debug_roll(config: &mut DebugConfig, side: i32) -> i32 {
         if config.rolls.len() > config.roll_index {
             roll = config.rolls[config.roll_index];
             config.roll_index += 1; // increment index for next roll
+        } else if config.physical {
+            // the play gets run again once the players have rolled this one
+            if config.missing.is_none() {
+                config.missing = Some(RollRequest {
+                    side,
+                    purpose: purpose.to_string(),
+                });
+            }
+            roll = 1;
         } else {
-            roll = rand::thread_rng().gen_range(1..side);
+            roll = crate::core::roll(side);
         }
     }
 
     roll
 }
 
-pub fn combined_roll(debug: &mut DebugConfig, side: i32) -> i32 {
+/// rolls a [side] sided die, [purpose] is what the roll is for (shown when asking for physical
+/// dice)
+pub fn combined_roll(debug: &mut DebugConfig, side: i32, purpose: &str) -> i32 {
     if debug.mode {
-        debug_roll(debug, side)
+        debug_roll(debug, side, purpose)
     } else {
         roll(side)
     }
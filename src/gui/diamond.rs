@@ -0,0 +1,220 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use eframe::egui::{pos2, Color32, Label, Pos2, RichText};
+
+use crate::characters::players::{Player, Position};
+use crate::core::game_functions::{
+    get_swing_position, position_by_number, AtBatResults, GameState,
+};
+use crate::core::retrosheet::{PlayKind, PlayRecord};
+
+/*========================================================
+CONSTANTS
+========================================================*/
+/// seconds it takes the runners to finish moving after a play
+pub const ANIMATION_SECONDS: f64 = 1.0;
+
+/// helmet spots for home, first, second and third, in the same layout coordinates as the
+/// fielder labels (before the field offset)
+pub const BASE_SPOTS: [Pos2; 4] = [
+    pos2(390.0, 485.0),
+    pos2(540.0, 310.0),
+    pos2(390.0, 170.0),
+    pos2(255.0, 320.0),
+];
+
+/// how far below each base a runner's name goes, second base puts it above so it misses the
+/// second baseman's label
+pub const NAME_DROP: [f32; 4] = [32.0, 32.0, -32.0, 32.0];
+
+/// where each fielder's name label is centered
+pub const FIELDER_SPOTS: [(Position, Pos2); 9] = [
+    (Position::Firstbase, pos2(510.0, 270.0)),
+    (Position::Secondbase, pos2(450.0, 190.0)),
+    (Position::Pitcher, pos2(390.0, 315.0)),
+    (Position::Catcher, pos2(390.0, 485.0)),
+    (Position::Thirdbase, pos2(250.0, 280.0)),
+    (Position::Shortstop, pos2(290.0, 210.0)),
+    (Position::Rightfield, pos2(560.0, 110.0)),
+    (Position::Centerfield, pos2(380.0, 110.0)),
+    (Position::Leftfield, pos2(210.0, 110.0)),
+];
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// where a runner finished a play
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunnerEnd {
+    Base(u32), // 1-3
+    Scored,
+    Out,
+}
+
+/// one runner's trip on a play, [from] is 0 for the batter
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunnerMove {
+    pub runner: Player,
+    pub from: u32,
+    pub end: RunnerEnd,
+}
+
+/// the last play shown on the diamond - runners moving, the fielder who made it and any error
+#[derive(Debug, Clone, Default)]
+pub struct PlayAnimation {
+    pub plays: usize, // length of the play log when the play was picked up
+    pub started: f64, // ui time in seconds
+    pub moves: Vec<RunnerMove>,
+    pub fielder: Option<Position>,
+    pub error: bool,
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl RunnerMove {
+    /// spot on the diamond [progress] (0-1) of the way through the play, base to base along the
+    /// baselines - runners who were put out stay where they started
+    pub fn position(&self, progress: f32) -> Pos2 {
+        let to = match self.end {
+            RunnerEnd::Base(base) => base,
+            RunnerEnd::Scored => 4,
+            RunnerEnd::Out => self.from,
+        };
+        let bases = to.saturating_sub(self.from);
+        if bases == 0 {
+            return base_spot(self.from);
+        }
+        let travelled = progress.clamp(0.0, 1.0) * bases as f32;
+        let leg = (travelled.floor() as u32).min(bases - 1);
+        base_spot(self.from + leg).lerp(base_spot(self.from + leg + 1), travelled - leg as f32)
+    }
+}
+
+impl PlayAnimation {
+    /// picks up a new play from the end of [state]'s play log, [now] is the ui time in seconds
+    /// undoing a play or starting a new game just clears the diamond, there's nothing to animate
+    pub fn update(&mut self, state: &GameState, now: f64) {
+        let plays = state.plays.len();
+        if plays == self.plays {
+            return;
+        }
+        *self = match state.plays.last() {
+            Some(play) if plays > self.plays => PlayAnimation {
+                plays,
+                started: now,
+                moves: runner_moves(play),
+                // the other team is in the field once the half inning is over
+                fielder: play_fielder(play).filter(|_| play.inning_half == state.inning_half),
+                error: play.errors > 0,
+            },
+            _ => PlayAnimation {
+                plays,
+                ..Default::default()
+            },
+        };
+    }
+
+    /// how far through the runners' animation it is at [now], 0-1
+    pub fn progress(&self, now: f64) -> f32 {
+        ((now - self.started) / ANIMATION_SECONDS).clamp(0.0, 1.0) as f32
+    }
+
+    /// true while runners are still moving
+    pub fn is_running(&self, now: f64) -> bool {
+        !self.moves.is_empty() && self.progress(now) < 1.0
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// where every runner went on [play], lead runner first and the batter last
+/// runners who left the bases scored if there are runs for them, otherwise they were put out
+pub fn runner_moves(play: &PlayRecord) -> Vec<RunnerMove> {
+    let ended_on = |runner: &Player| {
+        play.runners_after
+            .iter()
+            .position(|r| r.as_ref() == Some(runner))
+            .map(|i| i as u32 + 1)
+    };
+    let runners_gone = play
+        .runners_before
+        .iter()
+        .flatten()
+        .filter(|runner| ended_on(runner).is_none())
+        .count() as u32;
+    // same as the event file, the batter only scored if more runs came in than runners left
+    let batter = play.batter.as_ref().map(|batter| RunnerMove {
+        runner: batter.clone(),
+        from: 0,
+        end: match ended_on(batter) {
+            Some(base) => RunnerEnd::Base(base),
+            None if play.runs > runners_gone => RunnerEnd::Scored,
+            None => RunnerEnd::Out,
+        },
+    });
+    let mut runs = play.runs;
+    if batter.as_ref().is_some_and(|b| b.end == RunnerEnd::Scored) {
+        runs -= 1;
+    }
+
+    let mut moves = vec![];
+    for base in (0..3).rev() {
+        let Some(runner) = &play.runners_before[base] else {
+            continue;
+        };
+        let end = match ended_on(runner) {
+            Some(after) => RunnerEnd::Base(after),
+            None if runs > 0 => {
+                runs -= 1;
+                RunnerEnd::Scored
+            }
+            None => RunnerEnd::Out,
+        };
+        moves.push(RunnerMove {
+            runner: runner.clone(),
+            from: base as u32 + 1,
+            end,
+        });
+    }
+    moves.extend(batter);
+
+    moves
+}
+
+/// fielder who made [play], None for walks and oddities where nobody fielded anything
+pub fn play_fielder(play: &PlayRecord) -> Option<Position> {
+    let result = match &play.kind {
+        PlayKind::Steal => return Some(Position::Catcher),
+        PlayKind::Bunt => return Some(Position::Pitcher),
+        PlayKind::AtBat(result) | PlayKind::HitAndRun(result) => result,
+    };
+    let position = position_by_number(get_swing_position(&play.mss_result));
+    match result {
+        AtBatResults::Walk | AtBatResults::Oddity => None,
+        // possible_error() hands the pitcher and catcher's chances up the middle
+        AtBatResults::PossibleError => Some(match position {
+            Position::Pitcher => Position::Shortstop,
+            Position::Catcher => Position::Secondbase,
+            position => position,
+        }),
+        _ => Some(position),
+    }
+}
+
+/// black on [background] name label for the diamond
+pub fn field_label(text: &str, background: Color32) -> Label {
+    Label::new(
+        RichText::new(text)
+            .color(Color32::BLACK)
+            .strong()
+            .background_color(background),
+    )
+}
+
+// helmet spot for a base, 4 is home again
+fn base_spot(base: u32) -> Pos2 {
+    BASE_SPOTS[(base % 4) as usize]
+}
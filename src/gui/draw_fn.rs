@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::Path;
 
 use eframe::egui::{self, Color32, Context, RichText};
 use egui_dnd::dnd;
@@ -8,15 +9,33 @@ use egui_toast::{Toast, ToastKind, ToastOptions, ToastStyle, Toasts};
 use crate::{
     characters::{
         ballparks::{
-            generate_modern_ballpark, load_park_ancient, load_park_modern, write_ballpark_modern,
+            load_park_ancient, load_park_modern, Ballpark, Condition, Quirks, Roof,
+            StadiumTypeAncient, StadiumTypeModern, Turf,
         },
-        players::{generate_player, write_player, PlayerClass, Position},
-        teams::{generate_team, load_team, write_team, Era},
+        players::{
+            check_player, generate_player, load_player_ref, write_player, Handedness,
+            InjuryLocation, InjurySeverity, PlayerClass, Position, Traits, PITCH_DICE,
+        },
+        teams::{load_team, Era, Fanbase, Location, Makeup, ManagerLeague, Priority, RosterGroup},
     },
+    core::actions::Action,
+    core::bases::Bases,
+    core::bo_wrap,
+    core::file_locations::{DatabaseSource, ParseError},
     core::game_functions::{create_modern_game, GameStatus, InningTB, Outs, RunnersOn},
+    core::replay::{load_record, replay},
+    core::settings::{add_recent, settings_path, Theme},
+    core::simulation::{simulate_games, AT_BAT_RESULTS},
+    core::stats::{player_key, LeaderStat, PlayerSeason},
     DeadballApp, ABOUT_APP, ABOUT_DEABALL,
 };
 
+use super::gui_functions::{
+    apply_settings, enter_physical_roll, get_player_name, pick_rested_starters, pick_starter,
+    play_action, save_settings, GameSnapshot,
+};
+use super::lineup::{batting_summary, pitching_summary};
+
 /// populates ui for the version window
 pub fn draw_version_window(ctx: &Context, app: &mut DeadballApp) {
     egui::Window::new("Version")
@@ -66,8 +85,10 @@ pub fn draw_console_window(ctx: &Context, app: &mut DeadballApp) {
 
 /// renders the new game window
 pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    // picking starters needs the whole app, so the open flag is copied out and back
+    let mut is_visible = app.gui_windows.create_game_window;
     egui::Window::new("Create new game")
-        .open(&mut app.gui_windows.create_game_window)
+        .open(&mut is_visible)
         .show(ctx, |ui| {
             // selectable value for game era
             ui.horizontal(|ui| {
@@ -82,6 +103,11 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                 ui.selectable_value(&mut app.oddity, false, "Disabled");
                 ui.selectable_value(&mut app.oddity, true, "Enabled");
             });
+            // league folder, player IDs in the team files are looked up here
+            ui.horizontal(|ui| {
+                ui.label("League folder:");
+                ui.text_edit_singleline(&mut app.data_dir);
+            });
             // file dialog for away team
             ui.horizontal(|ui| {
                 ui.label("Away Team:");
@@ -102,6 +128,9 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                         }
                     }
                 }
+                if let Some(file) = recent_menu(ui, &app.settings.recent_teams) {
+                    app.away_team_file = Some(file);
+                }
             });
             // file dialog for home team
             ui.horizontal(|ui| {
@@ -123,6 +152,9 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                         }
                     }
                 }
+                if let Some(file) = recent_menu(ui, &app.settings.recent_teams) {
+                    app.home_team_file = Some(file);
+                }
             });
             // file dialog for ball park
             ui.horizontal(|ui| {
@@ -144,6 +176,9 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                         }
                     }
                 }
+                if let Some(file) = recent_menu(ui, &app.settings.recent_parks) {
+                    app.ballpark_file = Some(file);
+                }
             });
             ui.separator();
             // button to create game and return to main screen
@@ -155,67 +190,86 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                     && app.ballpark_file.is_some()
                 {
                     // try to load teams and ballpark files
-                    match fs::read_to_string(app.away_team_file.as_ref().unwrap().as_path()) {
-                        Ok(contents) => {
-                            app.away_team = Some(load_team(contents));
-                        }
+                    let away_path = app.away_team_file.as_ref().unwrap().display().to_string();
+                    match fs::read_to_string(&away_path) {
+                        Ok(contents) => match load_team(contents, &away_path) {
+                            Ok(team) => app.away_team = Some(team),
+                            Err(err) => {
+                                app.create_game_error =
+                                    app.create_game_error.clone() + &format!("{}\n", err);
+                                parse_error_toast(toasts, &err);
+                            }
+                        },
                         Err(err) => {
                             app.create_game_error = app.create_game_error.clone()
                                 + "Failed to read Away team file."
                                 + &format!("{:?}", err);
                         }
                     }
-                    match fs::read_to_string(app.home_team_file.as_ref().unwrap().as_path()) {
-                        Ok(contents) => {
-                            app.home_team = Some(load_team(contents));
-                        }
+                    let home_path = app.home_team_file.as_ref().unwrap().display().to_string();
+                    match fs::read_to_string(&home_path) {
+                        Ok(contents) => match load_team(contents, &home_path) {
+                            Ok(team) => app.home_team = Some(team),
+                            Err(err) => {
+                                app.create_game_error =
+                                    app.create_game_error.clone() + &format!("{}\n", err);
+                                parse_error_toast(toasts, &err);
+                            }
+                        },
                         Err(err) => {
                             app.create_game_error = app.create_game_error.clone()
                                 + "Failed to read Home team file."
                                 + &format!("{:?}", err);
                         }
                     }
+                    let park_path = app.ballpark_file.as_ref().unwrap().display().to_string();
                     match app.create_game_era {
-                        Era::Modern => {
-                            match fs::read_to_string(app.ballpark_file.as_ref().unwrap().as_path())
-                            {
-                                Ok(contents) => {
-                                    app.ballpark_modern = Some(load_park_modern(contents));
-                                }
+                        Era::Modern => match fs::read_to_string(&park_path) {
+                            Ok(contents) => match load_park_modern(contents, &park_path) {
+                                Ok(park) => app.ballpark_modern = Some(park),
                                 Err(err) => {
-                                    app.create_game_error = app.create_game_error.clone()
-                                        + "Failed to read Ballpark file."
-                                        + &format!("{:?}", err);
+                                    app.create_game_error =
+                                        app.create_game_error.clone() + &format!("{}\n", err);
+                                    parse_error_toast(toasts, &err);
                                 }
+                            },
+                            Err(err) => {
+                                app.create_game_error = app.create_game_error.clone()
+                                    + "Failed to read Ballpark file."
+                                    + &format!("{:?}", err);
                             }
-                        }
-                        Era::Ancient => {
-                            match fs::read_to_string(app.ballpark_file.as_ref().unwrap().as_path())
-                            {
-                                Ok(contents) => {
-                                    app.ballpark_ancient = Some(load_park_ancient(contents));
-                                }
+                        },
+                        Era::Ancient => match fs::read_to_string(&park_path) {
+                            Ok(contents) => match load_park_ancient(contents, &park_path) {
+                                Ok(park) => app.ballpark_ancient = Some(park),
                                 Err(err) => {
-                                    app.create_game_error = app.create_game_error.clone()
-                                        + "Failed to read Ballpark file."
-                                        + &format!("{:?}", err);
+                                    app.create_game_error =
+                                        app.create_game_error.clone() + &format!("{}\n", err);
+                                    parse_error_toast(toasts, &err);
                                 }
+                            },
+                            Err(err) => {
+                                app.create_game_error = app.create_game_error.clone()
+                                    + "Failed to read Ballpark file."
+                                    + &format!("{:?}", err);
                             }
-                        }
+                        },
                         Era::None => {
                             app.create_game_error =
                                 app.create_game_error.clone() + "Please select an Era.";
                         }
                     }
-                    toasts.add(Toast {
-                        text: "Game created.".into(),
-                        kind: ToastKind::Info,
-                        style: ToastStyle::default(),
-                        options: ToastOptions::default()
-                            .duration_in_seconds(3.0)
-                            .show_progress(true)
-                            .show_icon(true),
-                    });
+                    if app.create_game_error.is_empty() {
+                        toasts.add(Toast {
+                            text: "Game created.".into(),
+                            kind: ToastKind::Info,
+                            style: ToastStyle::default(),
+                            options: ToastOptions::default()
+                                .duration_in_seconds(app.settings.toast_seconds)
+                                .show_progress(true)
+                                .show_icon(true),
+                        });
+                    }
                 } else {
                     // update error message and display error window
                     if app.away_team_file.is_none() {
@@ -226,7 +280,7 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                             text: "Must select a *.dbt file for away team.".into(),
                             style: ToastStyle::default(),
                             options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
+                                .duration_in_seconds(app.settings.toast_seconds)
                                 .show_progress(true)
                                 .show_icon(true),
                         });
@@ -239,7 +293,7 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                             text: "Must select a *.dbt file for home team.".into(),
                             style: ToastStyle::default(),
                             options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
+                                .duration_in_seconds(app.settings.toast_seconds)
                                 .show_progress(true)
                                 .show_icon(true),
                         });
@@ -252,7 +306,7 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                             text: "Must select a *.dbb file for ballpark.".into(),
                             style: ToastStyle::default(),
                             options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
+                                .duration_in_seconds(app.settings.toast_seconds)
                                 .show_progress(true)
                                 .show_icon(true),
                         });
@@ -269,13 +323,22 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
                                 app.away_team.clone().unwrap(),
                                 app.ballpark_modern.clone().unwrap(),
                                 app.oddity,
+                                Path::new(&app.data_dir),
                             ) {
                                 Ok(game) => {
                                     app.game_modern = Some(game);
+                                    // files that made a game go to the top of the recent lists
+                                    for file in [&app.away_team_file, &app.home_team_file] {
+                                        let file = file.as_ref().unwrap().display().to_string();
+                                        add_recent(&mut app.settings.recent_teams, &file);
+                                    }
+                                    let park = app.ballpark_file.as_ref().unwrap().display();
+                                    add_recent(&mut app.settings.recent_parks, &park.to_string());
                                     app.home_team_active =
                                         Some(app.game_modern.clone().unwrap().home_active.clone());
                                     app.away_team_active =
                                         Some(app.game_modern.clone().unwrap().away_active.clone());
+                                    pick_rested_starters(app);
                                     // TODO: make the window close after successfully generating a game
                                 }
                                 Err(err) => {
@@ -301,82 +364,198 @@ pub fn draw_create_new_game(ctx: &Context, app: &mut DeadballApp, toasts: &mut T
             ui.add(eframe::egui::Label::new(
                 RichText::new(&app.create_game_error).color(Color32::RED),
             ));
+
+            // starters can be changed until the game starts
+            let started = app
+                .game_state
+                .as_ref()
+                .is_some_and(|state| state.status == GameStatus::Ongoing);
+            let Some(game) = app.game_modern.as_ref().filter(|_| !started) else {
+                return;
+            };
+            ui.separator();
+            ui.label("Starting pitchers:");
+            let season = app.stats_window.season;
+            let mut picked = None;
+            egui::Grid::new("starting pitchers").show(ui, |ui| {
+                for (home, team, active) in [
+                    (false, &game.away, &game.away_active),
+                    (true, &game.home, &game.home_active),
+                ] {
+                    ui.label(&team.name);
+                    let rest_text = |pitcher| {
+                        if app.stats.is_rested(&team.name, season, pitcher) {
+                            "rested".to_string()
+                        } else {
+                            let rest = app.stats.games_rested(&team.name, season, pitcher);
+                            format!("{} games rest", rest.unwrap_or(0))
+                        }
+                    };
+                    let Some(starter) = active.pitching.first() else {
+                        ui.end_row();
+                        continue;
+                    };
+                    egui::ComboBox::from_id_salt(("starter", home))
+                        .selected_text(format!(
+                            "{} ({})",
+                            get_player_name(starter),
+                            rest_text(starter)
+                        ))
+                        .show_ui(ui, |ui| {
+                            for (i, pitcher) in active.pitching.iter().enumerate() {
+                                let text = format!(
+                                    "{} ({})",
+                                    get_player_name(pitcher),
+                                    rest_text(pitcher)
+                                );
+                                if ui.selectable_label(i == 0, text).clicked() {
+                                    picked = Some((home, i));
+                                }
+                            }
+                        });
+                    ui.end_row();
+                }
+            });
+            if let Some((home, index)) = picked {
+                pick_starter(app, home, index);
+            }
         });
+    app.gui_windows.create_game_window = is_visible;
 }
 
-/// draws and handles logic for "Create Team" window
-pub fn draw_create_team_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
-    egui::Window::new("Create New Team")
-        .open(&mut app.create_team.is_visible)
+/// team generation wizard, rolls a whole team for a preview before anything is written
+pub fn draw_team_wizard_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    let mut is_visible = app.team_wizard.is_visible;
+    egui::Window::new("Team Generation Wizard")
+        .open(&mut is_visible)
+        .vscroll(true)
         .show(ctx, |ui| {
-            ui.heading("New Team");
-            // TODO: add Era selector
-            ui.horizontal(|ui| {
-                ui.label("Team Name: ");
-                ui.text_edit_singleline(&mut app.create_team.name);
-                ui.checkbox(&mut app.create_team.name_override, "override")
-                    .on_hover_text("will generate random name if unchecked");
-            });
-            ui.horizontal(|ui| {
-                ui.label("Location: ");
-                ui.text_edit_singleline(&mut app.create_team.location);
-                ui.checkbox(&mut app.create_team.location_override, "override")
+            let wizard = &mut app.team_wizard;
+            egui::Grid::new("team wizard setup").show(ui, |ui| {
+                enum_combo(ui, "Era", &mut wizard.era, &[Era::Modern, Era::Ancient]);
+                ui.label("Team Name:");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut wizard.name);
+                    ui.checkbox(&mut wizard.name_override, "override")
+                        .on_hover_text("will generate random name if unchecked");
+                });
+                ui.end_row();
+                ui.checkbox(&mut wizard.location_override, "Location override")
                     .on_hover_text("will generate random location if unchecked");
+                egui::ComboBox::from_id_salt("team wizard location")
+                    .selected_text(format!("{:?}", wizard.location))
+                    .show_ui(ui, |ui| {
+                        for location in [
+                            Location::MiddleOfNowhere,
+                            Location::SmallTown,
+                            Location::SmallCity,
+                            Location::MediumSizedCity,
+                            Location::Metropolis,
+                        ] {
+                            let text = format!("{:?}", location);
+                            ui.selectable_value(&mut wizard.location, location, text);
+                        }
+                    });
+                ui.end_row();
+                for group in RosterGroup::ALL {
+                    ui.label(group.label());
+                    let max = if group == RosterGroup::Pitcher { 5 } else { 15 };
+                    ui.add(egui::DragValue::new(&mut wizard.counts[group as usize]).range(0..=max));
+                    ui.end_row();
+                }
+                ui.label("Output directory:");
+                ui.text_edit_singleline(&mut wizard.output_dir)
+                    .on_hover_text("players, ballparks and teams go in their folders under here");
+                ui.end_row();
+                ui.label("First names:");
+                ui.text_edit_singleline(&mut wizard.first_name_db)
+                    .on_hover_text("name CSV to draw from, blank uses the loaded database");
+                ui.end_row();
+                ui.label("Last names:");
+                ui.text_edit_singleline(&mut wizard.last_name_db)
+                    .on_hover_text("name CSV to draw from, blank uses the loaded database");
+                ui.end_row();
             });
-            ui.horizontal(|ui| {
-                ui.label("Save location: ");
-                ui.text_edit_singleline(&mut app.create_team.save_location);
-            });
-            if ui.button("Create").clicked() {
-                // generate and write team
-                let name = if app.create_team.name_override {
-                    &app.create_team.name
-                } else {
-                    "New Team"
+            let generate_text = if wizard.preview.is_some() {
+                "Reroll"
+            } else {
+                "Generate"
+            };
+            if ui.button(generate_text).clicked() {
+                wizard.error = match wizard.generate(&app.databases) {
+                    Ok(()) => String::new(),
+                    Err(e) => e,
                 };
-                let new_team = generate_team(
-                    app.create_team.era.clone(),
-                    8,
-                    4,
-                    1,
-                    5,
-                    name,
-                    &app.databases.first_names,
-                    &app.databases.last_names,
-                    &app.databases.logos,
-                    &app.databases.mascots,
-                    &app.databases.mottos,
-                    &app.databases.personalities,
-                    &app.databases.backgrounds,
-                    &app.databases.park1,
-                    &app.databases.park2,
-                );
-                match write_team(new_team, &app.create_team.save_location) {
-                    Ok(()) => {
-                        toasts.add(Toast {
-                            kind: ToastKind::Info,
-                            text: "Team created!".into(),
-                            style: ToastStyle::default(),
-                            options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
-                                .show_progress(true)
-                                .show_icon(true),
-                        });
-                    }
-                    Err(e) => {
-                        toasts.add(Toast {
-                            kind: ToastKind::Info,
-                            text: format!("Create failed: {}", e).into(),
-                            style: ToastStyle::default(),
-                            options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
-                                .show_progress(true)
-                                .show_icon(true),
-                        });
-                    }
+            }
+            if !wizard.error.is_empty() {
+                ui.colored_label(Color32::RED, &wizard.error);
+            }
+            let Some(generated) = &wizard.preview else {
+                return;
+            };
+
+            ui.separator();
+            let team = &generated.team;
+            egui::Grid::new("team wizard preview").show(ui, |ui| {
+                for (label, value) in [
+                    ("Name", team.name.clone()),
+                    ("Location", format!("{:?}", team.location)),
+                    ("Ballpark", generated.ballpark.name().to_string()),
+                    ("Manager", team.manager.clone()),
+                    ("Mascot", team.mascot.clone()),
+                    ("Motto", team.motto.clone()),
+                    ("Priority", format!("{:?}", team.priority)),
+                ] {
+                    ui.label(label);
+                    ui.label(value);
+                    ui.end_row();
                 }
+            });
+            for group in RosterGroup::ALL {
+                let players = &generated.players[group as usize];
+                ui.collapsing(format!("{} ({})", group.label(), players.len()), |ui| {
+                    egui::Grid::new(format!("team wizard {}", group.label()))
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for player in players {
+                                ui.label(get_player_name(player));
+                                ui.label(format!("{:?}", player.position));
+                                ui.label(format!(
+                                    "{}/{}",
+                                    player.batter_target, player.on_base_target
+                                ));
+                                ui.label(format!("{:+}", player.pitch_die));
+                                let traits: Vec<&str> = player
+                                    .traits
+                                    .iter()
+                                    .filter(|t| **t != Traits::None)
+                                    .map(Traits::code)
+                                    .collect();
+                                ui.label(traits.join(" "));
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+
+            ui.separator();
+            if ui.button("Write Files").clicked() {
+                let (kind, text) = match wizard.save() {
+                    Ok(file) => (ToastKind::Info, format!("Team created! {}", file.display())),
+                    Err(e) => (ToastKind::Error, format!("Create failed: {}", e)),
+                };
+                toasts.add(Toast {
+                    kind,
+                    text: text.into(),
+                    style: ToastStyle::default(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(app.settings.toast_seconds)
+                        .show_progress(true)
+                        .show_icon(true),
+                });
             }
         });
+    app.team_wizard.is_visible = is_visible;
 }
 
 /// draws and handles logic for "Create Player" Window
@@ -471,7 +650,7 @@ pub fn draw_create_player_window(ctx: &Context, app: &mut DeadballApp, toasts: &
                             text: "Player created!".into(),
                             style: ToastStyle::default(),
                             options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
+                                .duration_in_seconds(app.settings.toast_seconds)
                                 .show_progress(true)
                                 .show_icon(true),
                         });
@@ -482,7 +661,7 @@ pub fn draw_create_player_window(ctx: &Context, app: &mut DeadballApp, toasts: &
                             text: format!("Create failed: {}", e).into(),
                             style: ToastStyle::default(),
                             options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
+                                .duration_in_seconds(app.settings.toast_seconds)
                                 .show_progress(true)
                                 .show_icon(true),
                         });
@@ -492,54 +671,182 @@ pub fn draw_create_player_window(ctx: &Context, app: &mut DeadballApp, toasts: &
         });
 }
 
-/// draws and handles logic for "Create Ballpark" window
-pub fn draw_create_ballpark_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
-    egui::Window::new("Create New Ballpark")
-        .open(&mut app.create_ballpark.is_visible)
+/// ballpark editor, generates a park for either era or opens one, then edits every field
+pub fn draw_ballpark_editor_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    let mut is_visible = app.ballpark_editor.is_visible;
+    egui::Window::new("Ballpark Editor")
+        .open(&mut is_visible)
+        .vscroll(true)
         .show(ctx, |ui| {
-            ui.heading("New Ballpark");
-            // TODO: add Era selector
-            ui.horizontal(|ui| {
+            let editor = &mut app.ballpark_editor;
+            egui::Grid::new("ballpark editor setup").show(ui, |ui| {
+                enum_combo(ui, "Era", &mut editor.era, &[Era::Modern, Era::Ancient]);
                 ui.label("Name:");
-                ui.text_edit_singleline(&mut app.create_ballpark.name);
-                ui.checkbox(&mut app.create_ballpark.name_override, "override")
-                    .on_hover_text("will generate random name if unchecked");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut editor.name);
+                    ui.checkbox(&mut editor.name_override, "override")
+                        .on_hover_text("will generate random name if unchecked");
+                });
+                ui.end_row();
+                ui.label("File:");
+                ui.text_edit_singleline(&mut editor.save_location);
+                ui.end_row();
             });
             ui.horizontal(|ui| {
-                ui.label("Save location:");
-                ui.text_edit_singleline(&mut app.create_ballpark.save_location);
+                if ui.button("Generate").clicked() {
+                    editor.generate(&app.databases.park1, &app.databases.park2);
+                }
+                if ui.button("Open").clicked() {
+                    if let Err(err) = editor.open() {
+                        editor.park = None;
+                        editor.error = err.to_string();
+                    }
+                }
             });
-            if ui.button("Create").clicked() {
-                let ballpark = if app.create_ballpark.name_override {
-                    generate_modern_ballpark(&[app.create_ballpark.name.clone()], &["".to_string()])
-                } else {
-                    generate_modern_ballpark(&app.databases.park1, &app.databases.park2)
-                };
-                match write_ballpark_modern(&ballpark, &app.create_ballpark.save_location) {
-                    Ok(()) => {
-                        toasts.add(Toast {
-                            kind: ToastKind::Info,
-                            text: "Ballpark created".into(),
-                            style: ToastStyle::default(),
-                            options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
-                                .show_progress(true)
-                                .show_icon(true),
-                        });
+            if !editor.error.is_empty() {
+                ui.colored_label(Color32::RED, &editor.error);
+            }
+
+            let condition_options = [
+                Condition::FallingApart,
+                Condition::Decrepit,
+                Condition::WellWorn,
+                Condition::Sparkling,
+                Condition::None,
+            ];
+            let location_options = [
+                Location::MiddleOfNowhere,
+                Location::SmallTown,
+                Location::SmallCity,
+                Location::MediumSizedCity,
+                Location::Metropolis,
+                Location::None,
+            ];
+            egui::Grid::new("ballpark editor park").show(ui, |ui| match &mut editor.park {
+                Some(Ballpark::Modern(park)) => {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut park.name);
+                    ui.end_row();
+                    enum_combo(ui, "Location", &mut park.location, &location_options);
+                    enum_combo(
+                        ui,
+                        "Type",
+                        &mut park.park_type,
+                        &[
+                            StadiumTypeModern::JewelBox,
+                            StadiumTypeModern::BaseballPalace,
+                            StadiumTypeModern::SpaceAge,
+                            StadiumTypeModern::ConcreteDonut,
+                            StadiumTypeModern::Retro,
+                            StadiumTypeModern::None,
+                        ],
+                    );
+                    ui.label("Capacity");
+                    ui.add(egui::DragValue::new(&mut park.capacity).range(0..=150000));
+                    ui.end_row();
+                    enum_combo(
+                        ui,
+                        "Turf",
+                        &mut park.turf,
+                        &[Turf::Ragged, Turf::Good, Turf::Artificial, Turf::None],
+                    );
+                    enum_combo(
+                        ui,
+                        "Roof",
+                        &mut park.roof,
+                        &[Roof::No, Roof::Permanent, Roof::Retractable, Roof::None],
+                    );
+                    enum_combo(ui, "Condition", &mut park.condition, &condition_options);
+                }
+                Some(Ballpark::Ancient(park)) => {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut park.name);
+                    ui.end_row();
+                    enum_combo(ui, "Location", &mut park.location, &location_options);
+                    enum_combo(
+                        ui,
+                        "Type",
+                        &mut park.park_type,
+                        &[
+                            StadiumTypeAncient::WoodFramePavilion,
+                            StadiumTypeAncient::JewelBox,
+                            StadiumTypeAncient::BaseballPalace,
+                            StadiumTypeAncient::None,
+                        ],
+                    );
+                    ui.label("Capacity");
+                    ui.add(egui::DragValue::new(&mut park.capacity).range(0..=150000));
+                    ui.end_row();
+                    enum_combo(ui, "Condition", &mut park.condition, &condition_options);
+                }
+                None => {}
+            });
+
+            let Some(quirks) = editor.quirks_mut() else {
+                return;
+            };
+            let mut removed = None;
+            let mut added = None;
+            ui.collapsing("Quirks", |ui| {
+                let quirk_options = [
+                    Quirks::CozyOutfield,
+                    Quirks::ExpansiveOutfield,
+                    Quirks::ShortLeft,
+                    Quirks::ShortRight,
+                    Quirks::OddLeft,
+                    Quirks::OddCenter,
+                    Quirks::OddRight,
+                    Quirks::FastInfield,
+                    Quirks::SlowInfield,
+                    Quirks::HighMound,
+                    Quirks::Beautiful,
+                    Quirks::Hideous,
+                ];
+                egui::Grid::new("ballpark editor quirks").show(ui, |ui| {
+                    for (i, quirk) in quirks.iter_mut().enumerate() {
+                        if *quirk == Quirks::None {
+                            continue;
+                        }
+                        if ui.small_button("Remove").clicked() {
+                            removed = Some(i);
+                        }
+                        enum_combo(ui, &format!("Quirk {}", i + 1), quirk, &quirk_options);
                     }
-                    Err(e) => {
-                        toasts.add(Toast {
-                            kind: ToastKind::Error,
-                            text: format!("Create failed: {}", e).into(),
-                            style: ToastStyle::default(),
-                            options: ToastOptions::default()
-                                .duration_in_seconds(3.0)
-                                .show_progress(true),
-                        });
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Add Quirk").clicked() {
+                        added = Some(Some(Quirks::CozyOutfield));
                     }
-                }
+                    if ui.button("Roll Quirk").clicked() {
+                        added = Some(None);
+                    }
+                });
+            });
+            if let Some(index) = removed {
+                editor.remove_quirk(index);
+            }
+            if let Some(quirk) = added {
+                editor.add_quirk(quirk);
+            }
+
+            ui.separator();
+            if ui.button("Save").clicked() {
+                let (kind, text) = match editor.save() {
+                    Ok(()) => (ToastKind::Info, "Ballpark saved!".to_string()),
+                    Err(e) => (ToastKind::Error, format!("Save failed: {}", e)),
+                };
+                toasts.add(Toast {
+                    kind,
+                    text: text.into(),
+                    style: ToastStyle::default(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(app.settings.toast_seconds)
+                        .show_progress(true)
+                        .show_icon(true),
+                });
             }
         });
+    app.ballpark_editor.is_visible = is_visible;
 }
 
 /// draws the debug roll window
@@ -602,6 +909,7 @@ pub fn draw_debug_window(ctx: &Context, app: &mut DeadballApp) {
             if app.game_state.is_some() && !app.debug_settings.debug_copied {
                 app.debug_settings.debug_state = app.game_state.clone().unwrap();
                 app.debug_settings.debug_copied = true;
+                app.debug_settings.debug_runners = app.debug_settings.debug_state.bases.occupancy();
                 app.debug_settings.debug_inning_text =
                     app.debug_settings.debug_state.inning.clone().to_string();
             }
@@ -681,42 +989,42 @@ pub fn draw_debug_window(ctx: &Context, app: &mut DeadballApp) {
                     .selected_text(app.debug_settings.debug_runners_text.clone())
                     .show_ui(ui, |ui| {
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner000,
                             "000",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner001,
                             "001",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner010,
                             "010",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner100,
                             "100",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner011,
                             "011",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner110,
                             "110",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner101,
                             "101",
                         );
                         ui.selectable_value(
-                            &mut app.debug_settings.debug_state.runners,
+                            &mut app.debug_settings.debug_runners,
                             RunnersOn::Runner111,
                             "111",
                         );
@@ -785,7 +1093,7 @@ pub fn draw_debug_window(ctx: &Context, app: &mut DeadballApp) {
                 Outs::Three => app.debug_settings.debug_outs_text = "Three".to_string(),
             }
             // update runners on text
-            match &app.debug_settings.debug_state.runners {
+            match &app.debug_settings.debug_runners {
                 RunnersOn::Runner000 => app.debug_settings.debug_runners_text = "000".to_string(),
                 RunnersOn::Runner001 => app.debug_settings.debug_runners_text = "001".to_string(),
                 RunnersOn::Runner010 => app.debug_settings.debug_runners_text = "010".to_string(),
@@ -798,299 +1106,49 @@ pub fn draw_debug_window(ctx: &Context, app: &mut DeadballApp) {
             // button to write changes to game state
             ui.separator();
             if ui.button("Write Changes").clicked() {
-                // put players in the runner fields to avoid crashes
-                let current_batter: i32;
-                match app.debug_settings.debug_state.inning_half {
-                    InningTB::Top => {
-                        current_batter =
-                            app.debug_settings.debug_state.away_state.current_batter as i32;
-                        match app.debug_settings.debug_state.runners {
-                            RunnersOn::Runner000 => {}
-                            RunnersOn::Runner100 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner1 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner1 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [(current_batter - 1) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner010 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner2 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner2 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [(current_batter - 2) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner001 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner3 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner3 = Some(
-                                        app.game_modern.as_ref().unwrap().away_active.batting_order
-                                            [(current_batter - 2) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner110 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner101 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner011 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner111 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                let mut batter3 = current_batter - 3;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                if batter3 < 0 {
-                                    batter3 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter3 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().away_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                        }
-                    }
-                    InningTB::Bottom => {
-                        current_batter =
-                            app.debug_settings.debug_state.home_state.current_batter as i32;
-                        match app.debug_settings.debug_state.runners {
-                            RunnersOn::Runner000 => {}
-                            RunnersOn::Runner100 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner1 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner1 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [(current_batter - 2) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner010 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner2 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner2 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [(current_batter - 2) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner001 => {
-                                if current_batter == 1 {
-                                    app.debug_settings.debug_state.runner3 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [8]
-                                        .clone(),
-                                    );
-                                } else {
-                                    app.debug_settings.debug_state.runner3 = Some(
-                                        app.game_modern.as_ref().unwrap().home_active.batting_order
-                                            [(current_batter - 2) as usize]
-                                            .clone(),
-                                    );
-                                }
-                            }
-                            RunnersOn::Runner110 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner101 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner011 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                            RunnersOn::Runner111 => {
-                                let mut batter1 = current_batter - 1;
-                                let mut batter2 = current_batter - 2;
-                                let mut batter3 = current_batter - 3;
-                                if batter1 < 0 {
-                                    batter1 += 9;
-                                }
-                                if batter2 < 0 {
-                                    batter2 += 9;
-                                }
-                                if batter3 < 0 {
-                                    batter3 += 9;
-                                }
-                                app.debug_settings.debug_state.runner3 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter3 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner2 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter2 - 1) as usize]
-                                        .clone(),
-                                );
-                                app.debug_settings.debug_state.runner1 = Some(
-                                    app.game_modern.as_ref().unwrap().home_active.batting_order
-                                        [(batter1 - 1) as usize]
-                                        .clone(),
-                                );
-                            }
-                        }
-                    }
+                // put players on the bases to avoid crashes, the batters just ahead of the current
+                // one in the order (closest base first) - runners already there are kept
+                let debug_state = &mut app.debug_settings.debug_state;
+                if debug_state.bases.occupancy() != app.debug_settings.debug_runners {
+                    let game = app.game_modern.as_ref().unwrap();
+                    let (batting_order, current_batter) = match debug_state.inning_half {
+                        InningTB::Top => (
+                            &game.away_active.batting_order,
+                            debug_state.away_state.current_batter,
+                        ),
+                        InningTB::Bottom => (
+                            &game.home_active.batting_order,
+                            debug_state.home_state.current_batter,
+                        ),
+                    };
+                    let mut behind = 0;
+                    debug_state.bases =
+                        Bases::from_occupancy(app.debug_settings.debug_runners, |_| {
+                            behind += 1;
+                            batting_order[bo_wrap(current_batter, behind, true)].clone()
+                        });
+                }
+                // hand edits can be undone like any other play
+                if let Some(state) = app.game_state.clone() {
+                    app.undo_history.push(GameSnapshot {
+                        state,
+                        game: app.game_modern.clone(),
+                        record: app.game_record.clone(),
+                    });
                 }
                 app.game_state = Some(app.debug_settings.debug_state.clone());
+                // hand edits can't be replayed from the seed
+                if let Some(record) = &mut app.game_record {
+                    record.edited = true;
+                }
             }
         });
 }
 
 /// renders the roster edit window to change lineup or current pitcher during game
 pub fn draw_active_team_edit(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    // swaps go through the game record after the window is drawn
+    let mut substitution = None;
     egui::Window::new("Edit Team")
         .open(&mut app.gui_windows.edit_roster_window)
         .show(ctx, |ui| {
@@ -1146,11 +1204,10 @@ pub fn draw_active_team_edit(ctx: &Context, app: &mut DeadballApp, toasts: &mut
                         team.roster[app.active_team_edit.current_num] =
                             app.active_team_edit.bench_select.clone();
                         team.bench[app.active_team_edit.bench_num] = temp_player;
-                        if app.active_team_edit.is_home {
-                            app.game_modern.as_mut().unwrap().home_active = team;
-                        } else {
-                            app.game_modern.as_mut().unwrap().away_active = team;
-                        }
+                        substitution = Some(Action::Substitution {
+                            home: app.active_team_edit.is_home,
+                            team,
+                        });
                     }
                 } else {
                     // TODO: show innings pitched, streak/slump, etc.
@@ -1190,15 +1247,27 @@ pub fn draw_active_team_edit(ctx: &Context, app: &mut DeadballApp, toasts: &mut
                             team.bullpen[app.active_team_edit.bench_num].clone();
                         team.pitching[0] = app.active_team_edit.bench_select.clone();
                         team.bullpen[app.active_team_edit.bench_num] = temp_player;
-                        if app.active_team_edit.is_home {
-                            app.game_modern.as_mut().unwrap().home_active = team;
-                        } else {
-                            app.game_modern.as_mut().unwrap().away_active = team;
-                        }
+                        substitution = Some(Action::Substitution {
+                            home: app.active_team_edit.is_home,
+                            team,
+                        });
                     }
                 }
             }
         });
+    if let Some(action) = substitution {
+        if let Err(err) = play_action(app, action) {
+            toasts.add(Toast {
+                kind: ToastKind::Error,
+                text: err.to_string().into(),
+                style: ToastStyle::default(),
+                options: ToastOptions::default()
+                    .duration_in_seconds(app.settings.toast_seconds)
+                    .show_progress(true)
+                    .show_icon(true),
+            });
+        }
+    }
 }
 
 pub fn draw_batting_order_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
@@ -1257,3 +1326,1196 @@ pub fn draw_batting_order_window(ctx: &Context, app: &mut DeadballApp, toasts: &
             }
         });
 }
+
+/// draws the stats window - season/career leaderboards and player career pages
+pub fn draw_stats_window(ctx: &Context, app: &mut DeadballApp) {
+    egui::Window::new("Stats")
+        .open(&mut app.stats_window.is_visible)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Record games to season:");
+                ui.add(egui::DragValue::new(&mut app.stats_window.season).range(1..=9999));
+            });
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut app.stats_window.show_career, false, "Leaderboards");
+                ui.selectable_value(&mut app.stats_window.show_career, true, "Career");
+            });
+            ui.separator();
+            if !app.stats_window.show_career {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::Average, "AVG");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::OnBase, "OBP");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::Slugging, "SLG");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::HomeRuns, "HR");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::Era, "ERA");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::Whip, "WHIP");
+                    ui.selectable_value(&mut app.stats_window.stat, LeaderStat::Strikeouts, "K");
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut app.stats_window.all_seasons, "All seasons");
+                    if !app.stats_window.all_seasons {
+                        ui.label("Season:");
+                        egui::ComboBox::from_id_salt("leader_season")
+                            .selected_text(app.stats_window.view_season.to_string())
+                            .show_ui(ui, |ui| {
+                                for season in app.stats.seasons() {
+                                    ui.selectable_value(
+                                        &mut app.stats_window.view_season,
+                                        season,
+                                        season.to_string(),
+                                    );
+                                }
+                            });
+                    }
+                });
+                let season = if app.stats_window.all_seasons {
+                    None
+                } else {
+                    Some(app.stats_window.view_season)
+                };
+                let leaders = app.stats.leaderboard(app.stats_window.stat, season, 10);
+                if leaders.is_empty() {
+                    ui.label("No qualified players yet.");
+                }
+                egui::Grid::new("leaderboard").striped(true).show(ui, |ui| {
+                    for (i, (key, value)) in leaders.iter().enumerate() {
+                        ui.label(format!("{}.", i + 1));
+                        // clicking a name jumps to their career page
+                        if ui.link(app.stats.name(key)).clicked() {
+                            app.stats_window.player = key.clone();
+                            app.stats_window.show_career = true;
+                        }
+                        let text = match app.stats_window.stat {
+                            LeaderStat::HomeRuns | LeaderStat::Strikeouts => {
+                                format!("{}", *value as u32)
+                            }
+                            LeaderStat::Era | LeaderStat::Whip => format!("{:.2}", value),
+                            _ => format!("{:.3}", value),
+                        };
+                        ui.label(text);
+                        ui.end_row();
+                    }
+                });
+            } else {
+                egui::ComboBox::from_label("Player")
+                    .selected_text(app.stats.name(&app.stats_window.player))
+                    .show_ui(ui, |ui| {
+                        for key in app.stats.player_keys() {
+                            let name = app.stats.name(&key);
+                            ui.selectable_value(&mut app.stats_window.player, key, name);
+                        }
+                    });
+                let (seasons, total) = app.stats.career(&app.stats_window.player);
+                if seasons.is_empty() {
+                    ui.label("No stats on record.");
+                    return;
+                }
+                ui.heading("Batting");
+                egui::Grid::new("career_batting")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [
+                            "Season", "G", "PA", "AB", "H", "2B", "3B", "HR", "BB", "K", "RBI",
+                            "AVG", "OBP", "SLG",
+                        ] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for (season, line) in seasons.iter() {
+                            ui.label(season.to_string());
+                            batting_row(ui, line);
+                        }
+                        ui.label(RichText::new("Career").strong());
+                        batting_row(ui, &total);
+                    });
+                if total.pitching.games > 0 {
+                    ui.heading("Pitching");
+                    egui::Grid::new("career_pitching")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "Season", "G", "IP", "H", "R", "BB", "K", "HR", "ERA", "WHIP",
+                            ] {
+                                ui.label(RichText::new(header).strong());
+                            }
+                            ui.end_row();
+                            for (season, line) in seasons.iter() {
+                                if line.pitching.games > 0 {
+                                    ui.label(season.to_string());
+                                    pitching_row(ui, line);
+                                }
+                            }
+                            ui.label(RichText::new("Career").strong());
+                            pitching_row(ui, &total);
+                        });
+                }
+            }
+        });
+}
+
+/// runs a batch of games between the teams in the current game and shows the odds
+pub fn draw_simulation_window(ctx: &Context, app: &mut DeadballApp) {
+    egui::Window::new("Simulate Matchup")
+        .open(&mut app.simulation_window.is_visible)
+        .show(ctx, |ui| {
+            let Some(game) = &app.game_modern else {
+                ui.label("Create a game first, the simulator uses its teams and ballpark.");
+                return;
+            };
+            ui.label(format!(
+                "{} at {} ({})",
+                game.away.name, game.home.name, game.ballpark.name
+            ));
+            ui.horizontal(|ui| {
+                ui.label("Games:");
+                ui.add(egui::DragValue::new(&mut app.simulation_window.games).range(1..=100000));
+                // runs on every core, but still blocks the window until it's done
+                if ui.button("Run").clicked() {
+                    app.simulation_window.report =
+                        Some(simulate_games(game, app.simulation_window.games, 0));
+                }
+            });
+            let Some(report) = &app.simulation_window.report else {
+                return;
+            };
+            ui.separator();
+            egui::Grid::new("simulation_teams")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["Team", "Win %", "R/G", "H/G", "E/G"] {
+                        ui.label(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for (name, is_home, win_pct) in [
+                        (&game.away.name, false, report.away_win_pct()),
+                        (&game.home.name, true, report.home_win_pct()),
+                    ] {
+                        let (runs, hits, errors) = report.means(is_home);
+                        ui.label(name);
+                        ui.label(format!("{:.1}", win_pct * 100.0));
+                        ui.label(format!("{:.2}", runs));
+                        ui.label(format!("{:.2}", hits));
+                        ui.label(format!("{:.2}", errors));
+                        ui.end_row();
+                    }
+                });
+            if report.ties > 0 {
+                ui.label(format!("{} games didn't finish.", report.ties));
+            }
+
+            ui.heading("Runs per game");
+            let most = report
+                .home
+                .runs
+                .keys()
+                .chain(report.away.runs.keys())
+                .max()
+                .copied()
+                .unwrap_or(0);
+            egui::Grid::new("simulation_runs")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new("R").strong());
+                    ui.label(RichText::new("Away").strong());
+                    ui.label(RichText::new("Home").strong());
+                    ui.end_row();
+                    for runs in 0..=most {
+                        ui.label(runs.to_string());
+                        for side in [&report.away.runs, &report.home.runs] {
+                            let share =
+                                *side.get(&runs).unwrap_or(&0) as f32 / report.games.max(1) as f32;
+                            ui.add(
+                                egui::ProgressBar::new(share)
+                                    .desired_width(120.0)
+                                    .text(format!("{:.1}%", share * 100.0)),
+                            );
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.heading("Swing results");
+            egui::Grid::new("simulation_swings")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (result, share) in report.at_bat_frequencies() {
+                        ui.label(format!("{:?}", result));
+                        ui.label(format!("{:.1}%", share * 100.0));
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// fills one row of the career batting grid
+fn batting_row(ui: &mut egui::Ui, line: &PlayerSeason) {
+    let b = &line.batting;
+    for value in [
+        b.games,
+        b.plate_appearances,
+        b.at_bats,
+        b.hits,
+        b.doubles,
+        b.triples,
+        b.home_runs,
+        b.walks,
+        b.strikeouts,
+        b.rbi,
+    ] {
+        ui.label(value.to_string());
+    }
+    ui.label(format!("{:.3}", b.average()));
+    ui.label(format!("{:.3}", b.on_base()));
+    ui.label(format!("{:.3}", b.slugging()));
+    ui.end_row();
+}
+
+/// fills one row of the career pitching grid
+fn pitching_row(ui: &mut egui::Ui, line: &PlayerSeason) {
+    let p = &line.pitching;
+    ui.label(p.games.to_string());
+    ui.label(p.innings());
+    for value in [p.hits, p.runs, p.walks, p.strikeouts, p.home_runs] {
+        ui.label(value.to_string());
+    }
+    ui.label(format!("{:.2}", p.era()));
+    ui.label(format!("{:.2}", p.whip()));
+    ui.end_row();
+}
+
+/// shows a malformed team/player/ballpark file as an error toast
+fn parse_error_toast(toasts: &mut Toasts, err: &ParseError) {
+    toasts.add(Toast {
+        kind: ToastKind::Error,
+        text: format!("{}", err).into(),
+        style: ToastStyle::default(),
+        options: ToastOptions::default()
+            .duration_in_seconds(6.0)
+            .show_progress(true)
+            .show_icon(true),
+    });
+}
+
+/// renders the replay window, steps back and forth through a recorded game
+pub fn draw_replay_window(ctx: &Context, app: &mut DeadballApp) {
+    egui::Window::new("Replay Game")
+        .open(&mut app.replay_window.is_visible)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Record:");
+                ui.text_edit_singleline(&mut app.replay_window.file);
+                if ui.button("Load").clicked() {
+                    match load_record(&app.replay_window.file) {
+                        Ok(record) => {
+                            add_recent(&mut app.settings.recent_saves, &app.replay_window.file);
+                            app.replay_window.steps = replay(&record);
+                            app.replay_window.record = Some(record);
+                            app.replay_window.index = 0;
+                            app.replay_window.error.clear();
+                        }
+                        Err(err) => app.replay_window.error = err.to_string(),
+                    }
+                }
+                if let Some(file) = recent_menu(ui, &app.settings.recent_saves) {
+                    app.replay_window.file = file.display().to_string();
+                }
+                if let Some(record) = &app.game_record {
+                    if ui.button("Current Game").clicked() {
+                        app.replay_window.steps = replay(record);
+                        app.replay_window.record = Some(record.clone());
+                        app.replay_window.index = 0;
+                        app.replay_window.error.clear();
+                    }
+                }
+            });
+            if !app.replay_window.error.is_empty() {
+                ui.colored_label(Color32::RED, &app.replay_window.error);
+            }
+            let Some(record) = &app.replay_window.record else {
+                ui.label("Load a game record, or replay the game in progress.");
+                return;
+            };
+            ui.label(format!(
+                "{} at {} | seed {} | {} decisions",
+                record.away.name,
+                record.home.name,
+                record.seed,
+                record.decisions.len()
+            ));
+            if record.edited {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    "Debug rolls or edits were used in this game, the replay won't match it.",
+                );
+            }
+            ui.separator();
+
+            let last = app.replay_window.steps.len().saturating_sub(1);
+            ui.horizontal(|ui| {
+                if ui.button("|<").clicked() {
+                    app.replay_window.index = 0;
+                }
+                if ui.button("<").clicked() {
+                    app.replay_window.index = app.replay_window.index.saturating_sub(1);
+                }
+                if ui.button(">").clicked() {
+                    app.replay_window.index = (app.replay_window.index + 1).min(last);
+                }
+                if ui.button(">|").clicked() {
+                    app.replay_window.index = last;
+                }
+                ui.add(egui::Slider::new(&mut app.replay_window.index, 0..=last).text("step"));
+            });
+            let Some(step) = app.replay_window.steps.get(app.replay_window.index) else {
+                return;
+            };
+            let state = &step.state;
+            ui.heading(&step.label);
+            egui::Grid::new("replay_state")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Inning:");
+                    ui.label(format!("{} {:?}", state.inning, state.inning_half));
+                    ui.end_row();
+                    ui.label("Outs:");
+                    ui.label(format!("{:?}", state.outs));
+                    ui.end_row();
+                    ui.label("Score:");
+                    ui.label(format!(
+                        "{} {} - {} {}",
+                        record.away.name,
+                        state.away_state.runs.iter().sum::<u32>(),
+                        state.home_state.runs.iter().sum::<u32>(),
+                        record.home.name
+                    ));
+                    ui.end_row();
+                    for (base, runner) in ["1st", "2nd", "3rd"]
+                        .iter()
+                        .zip(state.bases.runners().iter())
+                    {
+                        ui.label(format!("{}:", base));
+                        ui.label(runner.as_ref().map_or("-".to_string(), get_player_name));
+                        ui.end_row();
+                    }
+                });
+            // what happened since the step before
+            let before = match app.replay_window.index {
+                0 => "",
+                i => &app.replay_window.steps[i - 1].state.game_text,
+            };
+            let text = state
+                .game_text
+                .strip_prefix(before)
+                .unwrap_or(&state.game_text);
+            ui.label(text.trim());
+            ui.collapsing("Game State", |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.monospace(format!("{:#?}", state));
+                    });
+            });
+        });
+}
+
+/// asks for the next roll of a play being resolved with physical dice
+pub fn draw_physical_dice_window(ctx: &Context, app: &mut DeadballApp) {
+    let Some(request) = app.physical_dice.request.clone() else {
+        return;
+    };
+    egui::Window::new("Physical Dice")
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let action = app
+                .physical_dice
+                .pending
+                .as_ref()
+                .map(|action| action.label());
+            ui.label(format!("Play: {}", action.unwrap_or_default()));
+            ui.heading(format!(
+                "Roll a d{} for the {}",
+                request.side, request.purpose
+            ));
+            let mut roll = None;
+            ui.horizontal(|ui| {
+                let response = ui.text_edit_singleline(&mut app.physical_dice.text);
+                if ui.button("Enter").clicked()
+                    || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                {
+                    match app.physical_dice.text.trim().parse::<i32>() {
+                        Ok(value) => roll = Some(value),
+                        Err(_) => app.physical_dice.error = "Enter a number.".to_string(),
+                    }
+                    response.request_focus();
+                }
+                if ui
+                    .button("Roll For Me")
+                    .on_hover_text("Let the app roll this one.")
+                    .clicked()
+                {
+                    roll = Some(crate::core::roll(request.side));
+                }
+            });
+            if let Some(roll) = roll {
+                app.physical_dice.text.clear();
+                if let Err(err) = enter_physical_roll(app, roll) {
+                    app.physical_dice.error = err;
+                }
+            }
+            if !app.physical_dice.error.is_empty() {
+                ui.colored_label(Color32::RED, &app.physical_dice.error);
+            }
+            if !app.physical_dice.rolls.is_empty() {
+                ui.label(format!("Rolls so far: {:?}", app.physical_dice.rolls));
+            }
+            if ui.button("Cancel Play").clicked() {
+                app.physical_dice.pending = None;
+                app.physical_dice.request = None;
+                app.physical_dice.rolls.clear();
+            }
+        });
+}
+
+// combo box over every value of one of the data file enums
+fn enum_combo<T: Clone + PartialEq + std::fmt::Debug>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut T,
+    options: &[T],
+) {
+    ui.label(label);
+    egui::ComboBox::from_id_salt(label)
+        .selected_text(format!("{:?}", value))
+        .show_ui(ui, |ui| {
+            for option in options {
+                ui.selectable_value(value, option.clone(), format!("{:?}", option));
+            }
+        });
+    ui.end_row();
+}
+
+/// team editor, every field in a team file plus its player lists
+pub fn draw_team_editor_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    // the editor's methods need all of it, so the open flag is copied out and back
+    let mut is_visible = app.team_editor.is_visible;
+    egui::Window::new("Team Editor")
+        .open(&mut is_visible)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            let data_dir = Path::new(&app.data_dir);
+            ui.horizontal(|ui| {
+                ui.label("Team file:");
+                ui.text_edit_singleline(&mut app.team_editor.file);
+                if ui.button("Open").clicked() {
+                    let file = app.team_editor.file.clone();
+                    if let Err(err) = app.team_editor.open(&file, data_dir) {
+                        app.team_editor.team = None;
+                        app.team_editor.error = err.to_string();
+                    }
+                }
+            });
+            if !app.team_editor.error.is_empty() {
+                ui.colored_label(Color32::RED, &app.team_editor.error);
+            }
+            let Some(team) = &mut app.team_editor.team else {
+                return;
+            };
+
+            ui.collapsing("Team", |ui| {
+                egui::Grid::new("team editor team").show(ui, |ui| {
+                    ui.label("ID");
+                    ui.label(&team.id);
+                    ui.end_row();
+                    for (label, value) in [
+                        ("Name", &mut team.name),
+                        ("Ballpark", &mut team.ballpark),
+                        ("Logo", &mut team.logo),
+                        ("Mascot", &mut team.mascot),
+                    ] {
+                        ui.label(label);
+                        ui.text_edit_singleline(value);
+                        ui.end_row();
+                    }
+                    enum_combo(
+                        ui,
+                        "Era",
+                        &mut team.era,
+                        &[Era::Modern, Era::Ancient, Era::None],
+                    );
+                    enum_combo(
+                        ui,
+                        "Location",
+                        &mut team.location,
+                        &[
+                            Location::MiddleOfNowhere,
+                            Location::SmallTown,
+                            Location::SmallCity,
+                            Location::MediumSizedCity,
+                            Location::Metropolis,
+                            Location::None,
+                        ],
+                    );
+                    enum_combo(
+                        ui,
+                        "Priority",
+                        &mut team.priority,
+                        &[
+                            Priority::Power,
+                            Priority::Average,
+                            Priority::StartingPitching,
+                            Priority::Bullpen,
+                            Priority::Speed,
+                            Priority::Defense,
+                            Priority::None,
+                        ],
+                    );
+                    enum_combo(
+                        ui,
+                        "Makeup",
+                        &mut team.makeup,
+                        &[
+                            Makeup::MostlyProspects,
+                            Makeup::Balanced,
+                            Makeup::MostlyVeterans,
+                            Makeup::None,
+                        ],
+                    );
+                    enum_combo(
+                        ui,
+                        "Fanbase",
+                        &mut team.fanbase,
+                        &[
+                            Fanbase::Nonexistent,
+                            Fanbase::Indifferent,
+                            Fanbase::FairWeather,
+                            Fanbase::Loyal,
+                            Fanbase::Obsessive,
+                            Fanbase::None,
+                        ],
+                    );
+                    ui.label("Years in league");
+                    ui.add(egui::DragValue::new(&mut team.years).range(0..=200));
+                    ui.end_row();
+                    ui.label("Years since championship");
+                    ui.add(egui::DragValue::new(&mut team.championship).range(0..=200));
+                    ui.end_row();
+                });
+            });
+            ui.collapsing("Manager", |ui| {
+                egui::Grid::new("team editor manager").show(ui, |ui| {
+                    for (label, value) in [
+                        ("Name", &mut team.manager),
+                        ("Personality", &mut team.personality),
+                        ("Motto", &mut team.motto),
+                    ] {
+                        ui.label(label);
+                        ui.text_edit_singleline(value);
+                        ui.end_row();
+                    }
+                    enum_combo(
+                        ui,
+                        "Position",
+                        &mut team.manager_position,
+                        &[
+                            Position::Pitcher,
+                            Position::Catcher,
+                            Position::Firstbase,
+                            Position::Secondbase,
+                            Position::Shortstop,
+                            Position::Thirdbase,
+                            Position::Leftfield,
+                            Position::Centerfield,
+                            Position::Rightfield,
+                            Position::Bench,
+                            Position::None,
+                        ],
+                    );
+                    enum_combo(
+                        ui,
+                        "League",
+                        &mut team.manager_league,
+                        &[
+                            ManagerLeague::Major,
+                            ManagerLeague::Minor,
+                            ManagerLeague::None,
+                        ],
+                    );
+                    ui.label("Retired");
+                    ui.add(egui::DragValue::new(&mut team.retired).range(0..=100));
+                    ui.end_row();
+                    ui.label("Daring");
+                    ui.add(egui::DragValue::new(&mut team.daring).range(1..=20));
+                    ui.end_row();
+                });
+            });
+            ui.collapsing("Owner", |ui| {
+                egui::Grid::new("team editor owner").show(ui, |ui| {
+                    for (label, value) in [
+                        ("Background", &mut team.owner_background),
+                        ("Personality", &mut team.owner_personality),
+                    ] {
+                        ui.label(label);
+                        ui.text_edit_singleline(value);
+                        ui.end_row();
+                    }
+                });
+            });
+
+            // player lists, edits are applied after the lists are drawn
+            let mut moved = None;
+            let mut removed = None;
+            let mut regenerated = None;
+            let mut added = None;
+            for group in RosterGroup::ALL {
+                let players = &mut app.team_editor.groups[group as usize];
+                ui.collapsing(format!("{} ({})", group.label(), players.len()), |ui| {
+                    let response =
+                        dnd(ui, group.label()).show(players.iter(), |ui, entry, handle, state| {
+                            ui.horizontal(|ui| {
+                                handle.ui(ui, |ui| {
+                                    let player = &entry.player;
+                                    ui.label(format!(
+                                        "{} {} {:?} [{} {}]{}",
+                                        if state.dragged { ">" } else { "|" },
+                                        get_player_name(player),
+                                        player.position,
+                                        player.batter_target,
+                                        player.on_base_target,
+                                        if entry.reference.is_empty() {
+                                            " (new)"
+                                        } else {
+                                            ""
+                                        }
+                                    ));
+                                });
+                                ui.menu_button("Move", |ui| {
+                                    for to in RosterGroup::ALL {
+                                        if to != group && ui.button(to.label()).clicked() {
+                                            moved = Some((group, state.index, to));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                                if ui
+                                    .button("Regenerate")
+                                    .on_hover_text("Replace with a new random player.")
+                                    .clicked()
+                                {
+                                    regenerated = Some((group, state.index));
+                                }
+                                if ui.button("Remove").clicked() {
+                                    removed = Some((group, state.index));
+                                }
+                            });
+                        });
+                    if response.is_drag_finished() {
+                        response.update_vec(players);
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("New Player").clicked() {
+                            added = Some((group, None));
+                        }
+                        ui.text_edit_singleline(&mut app.team_editor.add_reference)
+                            .on_hover_text("Player ID or file");
+                        if ui.button("Add Existing").clicked() {
+                            added = Some((group, Some(app.team_editor.add_reference.clone())));
+                        }
+                    });
+                });
+            }
+            let editor = &mut app.team_editor;
+            if let Some((from, index, to)) = moved {
+                editor.move_player(from, index, to);
+            }
+            if let Some((group, index)) = removed {
+                editor.remove_player(group, index);
+            }
+            if let Some((group, index)) = regenerated {
+                editor.regenerate_player(
+                    group,
+                    index,
+                    &app.databases.first_names,
+                    &app.databases.last_names,
+                );
+            }
+            match added {
+                Some((group, None)) => {
+                    let position = match group {
+                        RosterGroup::Pitcher | RosterGroup::Bullpen => Position::Pitcher,
+                        _ => Position::None,
+                    };
+                    let player = generate_player(
+                        group.class(),
+                        position,
+                        &app.databases.first_names,
+                        &app.databases.last_names,
+                    );
+                    editor.add_player(group, "", player);
+                }
+                Some((group, Some(reference))) => match load_player_ref(&reference, data_dir) {
+                    Ok(player) => {
+                        editor.add_player(group, &reference, player);
+                        editor.add_reference.clear();
+                        editor.error.clear();
+                    }
+                    Err(err) => editor.error = err.to_string(),
+                },
+                None => {}
+            }
+
+            ui.separator();
+            if ui.button("Save").clicked() {
+                let (kind, text) = match editor.save(data_dir) {
+                    Ok(()) => (ToastKind::Info, "Team saved!".to_string()),
+                    Err(e) => (ToastKind::Error, format!("Save failed: {}", e)),
+                };
+                toasts.add(Toast {
+                    kind,
+                    text: text.into(),
+                    style: ToastStyle::default(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(app.settings.toast_seconds)
+                        .show_progress(true)
+                        .show_icon(true),
+                });
+            }
+        });
+    app.team_editor.is_visible = is_visible;
+}
+
+// combo box over the pitch dice, negative dice are shown as -d20 etc.
+fn pitch_die_combo(ui: &mut egui::Ui, label: &str, value: &mut i32) {
+    let die_text = |die: i32| format!("{}d{}", if die < 0 { "-" } else { "" }, die.abs());
+    ui.label(label);
+    egui::ComboBox::from_id_salt(label)
+        .selected_text(die_text(*value))
+        .show_ui(ui, |ui| {
+            for die in PITCH_DICE {
+                ui.selectable_value(value, die, die_text(die));
+            }
+        });
+    ui.end_row();
+}
+
+/// player editor, edits an existing player card with the rules checked as you go
+pub fn draw_player_editor_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    let mut is_visible = app.player_editor.is_visible;
+    egui::Window::new("Player Editor")
+        .open(&mut is_visible)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            let editor = &mut app.player_editor;
+            ui.horizontal(|ui| {
+                ui.label("Player file:");
+                ui.text_edit_singleline(&mut editor.file);
+                if ui.button("Open").clicked() {
+                    let file = editor.file.clone();
+                    if let Err(err) = editor.open(&file) {
+                        editor.player = None;
+                        editor.error = err.to_string();
+                    }
+                }
+            });
+            if !editor.error.is_empty() {
+                ui.colored_label(Color32::RED, &editor.error);
+            }
+            let Some(player) = &mut editor.player else {
+                return;
+            };
+
+            egui::Grid::new("player editor card").show(ui, |ui| {
+                ui.label("ID");
+                ui.label(&player.id);
+                ui.end_row();
+                for (label, value) in [
+                    ("First Name", &mut player.first_name),
+                    ("Nickname", &mut player.nickname),
+                    ("Last Name", &mut player.last_name),
+                ] {
+                    ui.label(label);
+                    ui.text_edit_singleline(value);
+                    ui.end_row();
+                }
+                enum_combo(
+                    ui,
+                    "Position",
+                    &mut player.position,
+                    &[
+                        Position::Pitcher,
+                        Position::Catcher,
+                        Position::Firstbase,
+                        Position::Secondbase,
+                        Position::Shortstop,
+                        Position::Thirdbase,
+                        Position::Leftfield,
+                        Position::Centerfield,
+                        Position::Rightfield,
+                        Position::Bench,
+                        Position::None,
+                    ],
+                );
+                enum_combo(
+                    ui,
+                    "Handedness",
+                    &mut player.handedness,
+                    &[
+                        Handedness::Right,
+                        Handedness::Left,
+                        Handedness::Switch,
+                        Handedness::None,
+                    ],
+                );
+                ui.label("Batter Target");
+                ui.add(egui::DragValue::new(&mut player.batter_target).range(1..=99));
+                ui.end_row();
+                ui.label("On Base Target");
+                ui.add(egui::DragValue::new(&mut player.on_base_target).range(1..=99));
+                ui.end_row();
+                pitch_die_combo(ui, "Pitch Die", &mut player.pitch_die);
+            });
+
+            ui.collapsing("Traits", |ui| {
+                for (heading, traits) in [
+                    ("Hitting", &Traits::HITTER[..]),
+                    ("Pitching", &Traits::PITCHER[..]),
+                ] {
+                    ui.label(heading);
+                    ui.horizontal_wrapped(|ui| {
+                        for player_trait in traits {
+                            let mut checked = player.traits.contains(player_trait);
+                            if ui.checkbox(&mut checked, player_trait.code()).changed() {
+                                player
+                                    .traits
+                                    .retain(|t| t != player_trait && *t != Traits::None);
+                                if checked {
+                                    player.traits.push(player_trait.clone());
+                                } else if player.traits.is_empty() {
+                                    // player files always list at least one trait
+                                    player.traits.push(Traits::None);
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+
+            ui.collapsing("Injuries", |ui| {
+                egui::Grid::new("player editor injuries").show(ui, |ui| {
+                    let injuries = player
+                        .injury_location
+                        .iter_mut()
+                        .zip(player.injury_severity.iter_mut());
+                    for (i, (location, severity)) in injuries.enumerate() {
+                        enum_combo(
+                            ui,
+                            &format!("Injury {} Location", i + 1),
+                            location,
+                            &[
+                                InjuryLocation::Head,
+                                InjuryLocation::Shoulder,
+                                InjuryLocation::Elbow,
+                                InjuryLocation::Forearm,
+                                InjuryLocation::Wrist,
+                                InjuryLocation::Hand,
+                                InjuryLocation::Back,
+                                InjuryLocation::Oblique,
+                                InjuryLocation::Hip,
+                                InjuryLocation::Hamstring,
+                                InjuryLocation::Knee,
+                                InjuryLocation::Ankle,
+                                InjuryLocation::Foot,
+                                InjuryLocation::None,
+                            ],
+                        );
+                        enum_combo(
+                            ui,
+                            &format!("Injury {} Severity", i + 1),
+                            severity,
+                            &[
+                                InjurySeverity::Catastrophic,
+                                InjurySeverity::Major,
+                                InjurySeverity::Minor,
+                                InjurySeverity::Superficial,
+                                InjurySeverity::Uninjured,
+                            ],
+                        );
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Add Injury").clicked() {
+                        player.injury_location.push(InjuryLocation::None);
+                        player.injury_severity.push(InjurySeverity::Uninjured);
+                    }
+                    if ui.button("Remove Last Injury").clicked() {
+                        player.injury_location.pop();
+                        player.injury_severity.pop();
+                    }
+                });
+            });
+
+            let problems = check_player(player).err().unwrap_or_default();
+            for problem in problems.iter() {
+                ui.colored_label(Color32::RED, problem);
+            }
+
+            ui.collapsing("At Bat Preview", |ui| {
+                egui::Grid::new("player editor preview die").show(ui, |ui| {
+                    pitch_die_combo(ui, "Against Pitch Die", &mut editor.preview_die);
+                });
+                if let Some(calibration) = editor.preview() {
+                    egui::Grid::new("player editor preview")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (result, odds) in AT_BAT_RESULTS.iter().zip(calibration.swings) {
+                                ui.label(format!("{:?}", result));
+                                ui.label(format!("{:.1}%", odds * 100.0));
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+            ui.separator();
+            if ui
+                .add_enabled(problems.is_empty(), egui::Button::new("Save"))
+                .clicked()
+            {
+                let (kind, text) = match editor.save() {
+                    Ok(()) => (ToastKind::Info, "Player saved!".to_string()),
+                    Err(e) => (ToastKind::Error, format!("Save failed: {}", e.join(" "))),
+                };
+                toasts.add(Toast {
+                    kind,
+                    text: text.into(),
+                    style: ToastStyle::default(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(app.settings.toast_seconds)
+                        .show_progress(true)
+                        .show_icon(true),
+                });
+            }
+        });
+    app.player_editor.is_visible = is_visible;
+}
+
+/// read only card for a player clicked in the lineup panels, with their line in the current game
+pub fn draw_player_card_window(ctx: &Context, app: &mut DeadballApp) {
+    let Some(player) = app.player_card.player.clone() else {
+        return;
+    };
+    let mut is_visible = app.player_card.is_visible;
+    egui::Window::new("Player Card")
+        .open(&mut is_visible)
+        .show(ctx, |ui| {
+            ui.heading(get_player_name(&player));
+            if !player.nickname.is_empty() {
+                ui.label(RichText::new(format!("\"{}\"", player.nickname)).italics());
+            }
+            egui::Grid::new("player card").show(ui, |ui| {
+                ui.label("Position");
+                ui.label(format!("{:?}", player.position));
+                ui.end_row();
+                ui.label("Bats/Throws");
+                ui.label(format!("{:?}", player.handedness));
+                ui.end_row();
+                ui.label("BT/OBT");
+                ui.label(format!(
+                    "{}/{}",
+                    player.batter_target, player.on_base_target
+                ));
+                ui.end_row();
+                if player.position == Position::Pitcher {
+                    ui.label("Pitch Die");
+                    ui.label(format!("d{}", player.pitch_die));
+                    ui.end_row();
+                }
+                ui.label("Traits");
+                let traits: Vec<&str> = player.traits.iter().map(|t| t.code()).collect();
+                ui.label(traits.join(" "));
+                ui.end_row();
+            });
+            // today's lines, pitchers who haven't batted only have the one
+            if let Some(state) = &app.game_state {
+                let key = player_key(&player);
+                if let Some(line) = state.box_score.batting.get(&key) {
+                    ui.label(format!("Today: {}", batting_summary(line)));
+                }
+                if let Some(line) = state.box_score.pitching.get(&key) {
+                    ui.label(format!("Today: {}", pitching_summary(line)));
+                }
+            }
+            if ui.button("Career Stats").clicked() {
+                app.stats_window.player = player_key(&player);
+                app.stats_window.show_career = true;
+                app.stats_window.is_visible = true;
+            }
+        });
+    app.player_card.is_visible = is_visible;
+}
+
+/// settings window, changes are made to a copy and only take effect once they're saved
+pub fn draw_settings_window(ctx: &Context, app: &mut DeadballApp, toasts: &mut Toasts) {
+    let mut is_visible = app.settings_window.is_visible;
+    egui::Window::new("Settings")
+        .open(&mut is_visible)
+        .show(ctx, |ui| {
+            let settings = &mut app.settings_window.settings;
+            egui::Grid::new("settings").show(ui, |ui| {
+                ui.label("Data folder");
+                ui.text_edit_singleline(&mut settings.data_dir);
+                ui.end_row();
+                ui.label("Default era");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut settings.default_era, Era::None, "None");
+                    ui.selectable_value(&mut settings.default_era, Era::Modern, "Modern");
+                    ui.selectable_value(&mut settings.default_era, Era::Ancient, "Ancient");
+                });
+                ui.end_row();
+                ui.label("Oddities");
+                ui.checkbox(&mut settings.oddity, "Enabled for new games");
+                ui.end_row();
+                ui.label("Notifications");
+                ui.add(
+                    egui::DragValue::new(&mut settings.toast_seconds)
+                        .range(1.0..=30.0)
+                        .speed(0.1)
+                        .suffix(" s"),
+                );
+                ui.end_row();
+                ui.label("Theme");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut settings.theme, Theme::System, "System");
+                    ui.selectable_value(&mut settings.theme, Theme::Dark, "Dark");
+                    ui.selectable_value(&mut settings.theme, Theme::Light, "Light");
+                });
+                ui.end_row();
+                ui.label("Window");
+                ui.label("Size, position and the console are saved on exit.");
+                ui.end_row();
+                ui.label("Recent files");
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} teams, {} ballparks, {} game records",
+                        settings.recent_teams.len(),
+                        settings.recent_parks.len(),
+                        settings.recent_saves.len()
+                    ));
+                    if ui.button("Clear").clicked() {
+                        settings.recent_teams.clear();
+                        settings.recent_parks.clear();
+                        settings.recent_saves.clear();
+                    }
+                });
+                ui.end_row();
+            });
+            if !app.settings_window.error.is_empty() {
+                ui.colored_label(Color32::RED, &app.settings_window.error);
+            }
+            ui.separator();
+            if ui.button("Save").clicked() {
+                let settings = app.settings_window.settings.clone();
+                apply_settings(app, Some(ctx), settings);
+                match save_settings(app) {
+                    Ok(path) => {
+                        app.settings_window.error.clear();
+                        toasts.add(Toast {
+                            kind: ToastKind::Info,
+                            text: format!("Settings saved to {}", path.display()).into(),
+                            style: ToastStyle::default(),
+                            options: ToastOptions::default()
+                                .duration_in_seconds(app.settings.toast_seconds)
+                                .show_progress(true)
+                                .show_icon(true),
+                        });
+                    }
+                    // still applied for this session
+                    Err(err) => app.settings_window.error = err,
+                }
+            }
+        });
+    app.settings_window.is_visible = is_visible;
+}
+
+// menu of recently used files, returns the one picked
+fn recent_menu(ui: &mut egui::Ui, recent: &[String]) -> Option<std::path::PathBuf> {
+    let mut picked = None;
+    ui.add_enabled_ui(!recent.is_empty(), |ui| {
+        ui.menu_button("Recent", |ui| {
+            for file in recent {
+                if ui.button(file).clicked() {
+                    picked = Some(file.into());
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+    picked
+}
+
+/// where each name/park database came from, the override search path and the settings file
+pub fn draw_diagnostics_window(ctx: &Context, app: &mut DeadballApp) {
+    egui::Window::new("Diagnostics")
+        .open(&mut app.gui_windows.diagnostics_window)
+        .show(ctx, |ui| {
+            let databases = &mut app.databases;
+            ui.label(RichText::new("Databases").strong());
+            egui::Grid::new("database diagnostics")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["File", "Status", "Entries", "Source"] {
+                        ui.label(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    let status = [
+                        databases.status_first_names,
+                        databases.status_last_names,
+                        databases.status_logos,
+                        databases.status_mascot,
+                        databases.status_motto,
+                        databases.status_personalities,
+                        databases.status_backgrounds,
+                        databases.status_park1,
+                        databases.status_park2,
+                    ];
+                    // reports are in the same order as the status flags
+                    for (report, loaded) in databases.reports.iter().zip(status) {
+                        ui.label(&report.file);
+                        if loaded {
+                            ui.colored_label(Color32::GREEN, "Loaded");
+                        } else {
+                            ui.colored_label(Color32::RED, "Missing");
+                        }
+                        ui.label(report.entries.to_string());
+                        let source = match &report.source {
+                            DatabaseSource::Embedded => "built in".to_string(),
+                            DatabaseSource::File(path) => path.display().to_string(),
+                        };
+                        match &report.error {
+                            Some(err) => {
+                                ui.colored_label(Color32::YELLOW, source).on_hover_text(err)
+                            }
+                            None => ui.label(source),
+                        };
+                        ui.end_row();
+                    }
+                });
+            if databases.reports.is_empty() {
+                ui.label("Databases haven't been loaded yet.");
+            }
+            ui.separator();
+            ui.label(RichText::new("Override search path").strong());
+            for dir in databases.search_path.iter() {
+                let found = if dir.is_dir() { "" } else { " (not found)" };
+                ui.label(format!("{}{}", dir.display(), found));
+            }
+            if ui.button("Reload Databases").clicked() {
+                databases.loaded = false;
+            }
+            ui.separator();
+            let settings_file = settings_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "no config directory".to_string());
+            ui.label(format!("Settings: {}", settings_file));
+            ui.label(format!("Data folder: {}", app.settings.data_dir));
+        });
+}
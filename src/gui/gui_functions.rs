@@ -1,18 +1,47 @@
 /*========================================================
 MODULE INCLUSIONS
 ========================================================*/
+use std::fs;
+use std::path::{Path, PathBuf};
+
 use eframe::egui;
 use egui::{Align2, Direction, Pos2};
 
 use crate::{
     characters::{
-        players::{Player, PlayerClass, Position},
-        teams::{ActiveTeam, Era},
+        ballparks::{
+            generate_ancient_ballpark, generate_modern_ballpark, load_park_ancient,
+            load_park_modern, roll_quirk, Ballpark, Quirks,
+        },
+        players::{
+            check_player, generate_player, load_player, load_player_ref, write_player, Player,
+            PlayerClass, Position,
+        },
+        teams::{
+            generate_team, load_team, write_team, ActiveTeam, Era, GeneratedTeam, Location,
+            RosterGroup, Team,
+        },
+    },
+    core::actions::{apply_action, Action, ActionError},
+    core::calibration::{calibrate, Calibration, CalibrationInput},
+    core::file_locations::{
+        load_csv, DeadballDatabases, ParseError, PLAYER_FOLDER, RECORD_LOCATION,
     },
-    core::game_functions::{find_by_position, RunnersOn},
+    core::game_functions::{find_by_position, GameModern, GameState, GameStatus},
+    core::replay::{GameRecord, ReplayStep},
+    core::settings::{settings_path, write_settings, Settings, Theme},
+    core::simulation::SimulationReport,
+    core::stats::{write_stats, LeaderStat},
+    gui::debug::{DebugConfig, RollRequest},
     DeadballApp,
 };
 
+/*========================================================
+CONSTANTS
+========================================================*/
+/// how many plays back the undo history goes, the oldest are dropped after that
+pub const UNDO_LIMIT: usize = 200;
+
 /*========================================================
 STRUCT DEFINITIONS
 ========================================================*/
@@ -33,18 +62,23 @@ impl Default for ToastData {
     }
 }
 
-/// state variables for the Create Team window
-pub struct CreateTeamWindow {
+/// state variables for the team generation wizard, nothing is written until the preview is saved
+pub struct TeamWizard {
     pub is_visible: bool,
     pub era: Era,
     pub name_override: bool,
     pub name: String,
     pub location_override: bool,
-    pub location: String,
-    pub save_location: String,
+    pub location: Location,
+    pub counts: [u32; 4], // players per RosterGroup
+    pub output_dir: String,
+    pub first_name_db: String, // empty uses the loaded name databases
+    pub last_name_db: String,
+    pub preview: Option<GeneratedTeam>,
+    pub error: String,
 }
 
-impl Default for CreateTeamWindow {
+impl Default for TeamWizard {
     fn default() -> Self {
         Self {
             is_visible: false,
@@ -52,8 +86,13 @@ impl Default for CreateTeamWindow {
             name_override: false,
             name: "".to_string(),
             location_override: false,
-            location: "".to_string(),
-            save_location: "".to_string(),
+            location: Location::SmallCity,
+            counts: [8, 4, 5, 5],
+            output_dir: "".to_string(),
+            first_name_db: "".to_string(),
+            last_name_db: "".to_string(),
+            preview: None,
+            error: "".to_string(),
         }
     }
 }
@@ -87,15 +126,18 @@ impl Default for CreatePlayerWindow {
     }
 }
 
-pub struct CreateBallparkWindow {
+/// state variables for the ballpark editor window, creates new parks and edits existing ones
+pub struct BallparkEditor {
     pub is_visible: bool,
     pub era: Era,
     pub name_override: bool,
     pub name: String,
     pub save_location: String,
+    pub park: Option<Ballpark>,
+    pub error: String,
 }
 
-impl Default for CreateBallparkWindow {
+impl Default for BallparkEditor {
     fn default() -> Self {
         Self {
             is_visible: false,
@@ -103,6 +145,8 @@ impl Default for CreateBallparkWindow {
             name_override: false,
             name: "".to_string(),
             save_location: "".to_string(),
+            park: None,
+            error: "".to_string(),
         }
     }
 }
@@ -124,9 +168,483 @@ impl Default for BattingOrderWindow {
     }
 }
 
+/// state variables for the Stats window (leaderboards and career pages)
+pub struct StatsWindow {
+    pub is_visible: bool,
+    pub season: u32, // season that finished games are recorded to
+    pub show_career: bool,
+    pub stat: LeaderStat,
+    pub all_seasons: bool,
+    pub view_season: u32,
+    pub player: String, // player_key() of the career page being shown
+}
+
+impl Default for StatsWindow {
+    fn default() -> Self {
+        Self {
+            is_visible: false,
+            season: 1,
+            show_career: false,
+            stat: LeaderStat::Average,
+            all_seasons: false,
+            view_season: 1,
+            player: "".to_string(),
+        }
+    }
+}
+
+/// state variables for the matchup simulator window
+pub struct SimulationWindow {
+    pub is_visible: bool,
+    pub games: u32,
+    pub report: Option<SimulationReport>,
+}
+
+impl Default for SimulationWindow {
+    fn default() -> Self {
+        Self {
+            is_visible: false,
+            games: 1000,
+            report: None,
+        }
+    }
+}
+
+/// state variables for the replay window
+pub struct ReplayWindow {
+    pub is_visible: bool,
+    pub file: String,
+    pub record: Option<GameRecord>,
+    pub steps: Vec<ReplayStep>,
+    pub index: usize,
+    pub error: String,
+}
+
+impl Default for ReplayWindow {
+    fn default() -> Self {
+        Self {
+            is_visible: false,
+            file: RECORD_LOCATION.to_string(),
+            record: None,
+            steps: vec![],
+            index: 0,
+            error: "".to_string(),
+        }
+    }
+}
+
+/// state variables for physical dice mode, a play waits here until every roll it needs has been
+/// entered
+#[derive(Default)]
+pub struct PhysicalDice {
+    pub enabled: bool,
+    pub pending: Option<Action>,
+    pub rolls: Vec<i32>, // entered so far, in the order the engine asks for them
+    pub request: Option<RollRequest>,
+    pub text: String,
+    pub error: String,
+}
+
+/// a player on the team being edited, [reference] is what the team file points at - empty for
+/// players that only exist in the editor so far
+#[derive(Clone, Hash)]
+pub struct EditorPlayer {
+    pub reference: String,
+    pub player: Player,
+}
+
+/// state variables for the team editor window, [groups] are indexed by RosterGroup
+#[derive(Default)]
+pub struct TeamEditor {
+    pub is_visible: bool,
+    pub file: String,
+    pub team: Option<Team>,
+    pub groups: [Vec<EditorPlayer>; 4],
+    pub add_reference: String,
+    pub error: String,
+}
+
+/// state variables for the player editor window
+pub struct PlayerEditor {
+    pub is_visible: bool,
+    pub file: String,
+    pub player: Option<Player>,
+    pub preview_die: i32, // pitch die the at bat preview is rolled against
+    pub error: String,
+}
+
+impl Default for PlayerEditor {
+    fn default() -> Self {
+        Self {
+            is_visible: false,
+            file: "".to_string(),
+            player: None,
+            preview_die: 8,
+            error: "".to_string(),
+        }
+    }
+}
+
+/// state variables for the player card opened from the lineup panels
+#[derive(Default)]
+pub struct PlayerCard {
+    pub is_visible: bool,
+    pub player: Option<Player>,
+}
+
+/// state variables for the settings window, changes are made to a copy until they're saved
+#[derive(Default)]
+pub struct SettingsWindow {
+    pub is_visible: bool,
+    pub settings: Settings,
+    pub error: String,
+}
+
+/// a point the game in progress can be rewound to
+#[derive(Clone)]
+pub struct GameSnapshot {
+    pub state: GameState,
+    pub game: Option<GameModern>, // substitutions change the active rosters
+    pub record: Option<GameRecord>,
+}
+
+/// undo/redo stacks for the game in progress, newest snapshot last
+#[derive(Default)]
+pub struct UndoHistory {
+    undo: Vec<GameSnapshot>,
+    redo: Vec<GameSnapshot>,
+}
+
+impl UndoHistory {
+    /// saves [snapshot] before a play, a new play means the redo stack is gone
+    pub fn push(&mut self, snapshot: GameSnapshot) {
+        self.undo.push(snapshot);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// swaps [current] for the last saved snapshot, None if there is nothing to undo
+    pub fn undo(&mut self, current: GameSnapshot) -> Option<GameSnapshot> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// swaps [current] for the last undone snapshot, None if there is nothing to redo
+    pub fn redo(&mut self, current: GameSnapshot) -> Option<GameSnapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl TeamEditor {
+    /// loads the team in [file] and every player it points at
+    pub fn open(&mut self, file: &str, data_dir: &Path) -> Result<(), ParseError> {
+        let contents = fs::read_to_string(file).map_err(|e| ParseError::io(file, e))?;
+        let team = load_team(contents, file)?;
+        let mut groups: [Vec<EditorPlayer>; 4] = Default::default();
+        for group in RosterGroup::ALL {
+            for reference in team.group(group) {
+                groups[group as usize].push(EditorPlayer {
+                    reference: reference.clone(),
+                    player: load_player_ref(reference, data_dir)?,
+                });
+            }
+        }
+        self.file = file.to_string();
+        self.team = Some(team);
+        self.groups = groups;
+        self.error.clear();
+
+        Ok(())
+    }
+
+    /// moves a player to the end of another group
+    pub fn move_player(&mut self, from: RosterGroup, index: usize, to: RosterGroup) {
+        if index < self.groups[from as usize].len() {
+            let player = self.groups[from as usize].remove(index);
+            self.groups[to as usize].push(player);
+        }
+    }
+
+    pub fn remove_player(&mut self, group: RosterGroup, index: usize) {
+        if index < self.groups[group as usize].len() {
+            self.groups[group as usize].remove(index);
+        }
+    }
+
+    /// swaps a player for a newly generated one at the same spot, the old player's file is kept
+    pub fn regenerate_player(
+        &mut self,
+        group: RosterGroup,
+        index: usize,
+        firstnames: &[String],
+        lastnames: &[String],
+    ) {
+        if let Some(entry) = self.groups[group as usize].get_mut(index) {
+            let position = match group {
+                RosterGroup::Roster => entry.player.position.clone(),
+                RosterGroup::Bench => Position::None,
+                RosterGroup::Pitcher | RosterGroup::Bullpen => Position::Pitcher,
+            };
+            *entry = EditorPlayer {
+                reference: String::new(),
+                player: generate_player(group.class(), position, firstnames, lastnames),
+            };
+        }
+    }
+
+    /// adds a player to the end of [group], [reference] is empty for new players
+    pub fn add_player(&mut self, group: RosterGroup, reference: &str, player: Player) {
+        self.groups[group as usize].push(EditorPlayer {
+            reference: reference.to_string(),
+            player,
+        });
+    }
+
+    /// writes the team back to its file, new players get their own files in [data_dir] first
+    pub fn save(&mut self, data_dir: &Path) -> Result<(), std::io::Error> {
+        let Some(team) = &mut self.team else {
+            return Ok(());
+        };
+        let folder = data_dir.join(PLAYER_FOLDER);
+        for group in RosterGroup::ALL {
+            for entry in self.groups[group as usize].iter_mut() {
+                if entry.reference.is_empty() {
+                    fs::create_dir_all(&folder)?;
+                    let file = folder.join(format!("{}.dbp", entry.player.id));
+                    write_player(&entry.player, &file.to_string_lossy())?;
+                    entry.reference = entry.player.id.clone();
+                }
+            }
+            *team.group_mut(group) = self.groups[group as usize]
+                .iter()
+                .map(|entry| entry.reference.clone())
+                .collect();
+        }
+
+        write_team(team.clone(), &self.file)
+    }
+}
+
+impl PlayerEditor {
+    /// loads the player card in [file]
+    pub fn open(&mut self, file: &str) -> Result<(), ParseError> {
+        let contents = fs::read_to_string(file).map_err(|e| ParseError::io(file, e))?;
+        self.player = Some(load_player(contents, file)?);
+        self.file = file.to_string();
+        self.error.clear();
+
+        Ok(())
+    }
+
+    /// exact swing odds for the player batting against [preview_die]
+    pub fn preview(&self) -> Option<Calibration> {
+        let player = self.player.as_ref()?;
+        Some(calibrate(&CalibrationInput {
+            bat_target: player.batter_target,
+            on_base_target: player.on_base_target,
+            pitch_die: self.preview_die,
+            oddity: false,
+            power_mod: player.power(),
+            // the ball goes to the other team's fielders, the batter's own glove doesn't matter
+            defense_mod: 0,
+        }))
+    }
+
+    /// writes the player back to its file, nothing is written if the card breaks the rules
+    pub fn save(&self) -> Result<(), Vec<String>> {
+        let Some(player) = &self.player else {
+            return Ok(());
+        };
+        check_player(player)?;
+        write_player(player, &self.file).map_err(|e| vec![e.to_string()])
+    }
+}
+
+impl TeamWizard {
+    /// rolls a team for the preview, [databases] fill in anything the wizard doesn't override
+    pub fn generate(&mut self, databases: &DeadballDatabases) -> Result<(), String> {
+        if self.counts[RosterGroup::Roster as usize] < 8 {
+            return Err("A team needs at least 8 starters, one for every position.".to_string());
+        }
+        if self.counts[RosterGroup::Pitcher as usize] == 0 {
+            return Err("A team needs at least 1 starting pitcher.".to_string());
+        }
+        let names = |file: &str, loaded: &Vec<String>| -> Result<Vec<String>, String> {
+            let names = if file.is_empty() {
+                loaded.clone()
+            } else {
+                load_csv(file, "\n").map_err(|e| format!("{}: {}", file, e))?
+            };
+            if names.is_empty() {
+                Err("No names to pick from, load the name databases first.".to_string())
+            } else {
+                Ok(names)
+            }
+        };
+        let first_names = names(&self.first_name_db, &databases.first_names)?;
+        let last_names = names(&self.last_name_db, &databases.last_names)?;
+        let name = if self.name_override {
+            &self.name
+        } else {
+            "New Team"
+        };
+        let [starters, bench, pitchers, bullpen] = self.counts;
+        let mut generated = generate_team(
+            self.era.clone(),
+            starters,
+            bench,
+            pitchers,
+            bullpen,
+            name,
+            &first_names,
+            &last_names,
+            &databases.logos,
+            &databases.mascots,
+            &databases.mottos,
+            &databases.personalities,
+            &databases.backgrounds,
+            &databases.park1,
+            &databases.park2,
+        );
+        if self.location_override {
+            generated.team.location = self.location.clone();
+        }
+        self.preview = Some(generated);
+
+        Ok(())
+    }
+
+    /// writes the previewed team into [output_dir], returns the team file
+    pub fn save(&self) -> Result<PathBuf, std::io::Error> {
+        match &self.preview {
+            Some(generated) => generated.write(Path::new(&self.output_dir)),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "nothing generated yet",
+            )),
+        }
+    }
+}
+
+impl BallparkEditor {
+    /// generates a new park for the selected era, named [name] when overridden
+    pub fn generate(&mut self, park1: &[String], park2: &[String]) {
+        let name_override = [self.name.clone()];
+        let (name1, name2) = if self.name_override {
+            (&name_override[..], &["".to_string()][..])
+        } else {
+            (park1, park2)
+        };
+        self.park = Some(match self.era {
+            Era::Ancient => Ballpark::Ancient(generate_ancient_ballpark(name1, name2)),
+            _ => Ballpark::Modern(generate_modern_ballpark(name1, name2)),
+        });
+        self.error.clear();
+    }
+
+    /// loads the park in [save_location] as the selected era
+    pub fn open(&mut self) -> Result<(), ParseError> {
+        let file = self.save_location.as_str();
+        let contents = fs::read_to_string(file).map_err(|e| ParseError::io(file, e))?;
+        self.park = Some(match self.era {
+            Era::Ancient => Ballpark::Ancient(load_park_ancient(contents, file)?),
+            _ => Ballpark::Modern(load_park_modern(contents, file)?),
+        });
+        self.error.clear();
+
+        Ok(())
+    }
+
+    /// quirks of the park being edited
+    pub fn quirks_mut(&mut self) -> Option<&mut Vec<Quirks>> {
+        self.park.as_mut().map(Ballpark::quirks_mut)
+    }
+
+    /// adds a quirk, a rolled one if [quirk] is None - replaces the "no quirks" placeholder
+    pub fn add_quirk(&mut self, quirk: Option<Quirks>) {
+        if let Some(quirks) = self.quirks_mut() {
+            quirks.retain(|q| *q != Quirks::None);
+            quirks.push(quirk.unwrap_or_else(roll_quirk));
+        }
+    }
+
+    /// takes a quirk off the park, an empty list goes back to the "no quirks" placeholder
+    pub fn remove_quirk(&mut self, index: usize) {
+        if let Some(quirks) = self.quirks_mut() {
+            if index < quirks.len() {
+                quirks.remove(index);
+            }
+            if quirks.is_empty() {
+                quirks.push(Quirks::None);
+            }
+        }
+    }
+
+    /// writes the park to [save_location]
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        match &self.park {
+            Some(park) => park.write(&self.save_location),
+            None => Ok(()),
+        }
+    }
+}
+
 /*========================================================
 FUNCTION DEFINITIONS
 ========================================================*/
+/// starts pitching[index] for the home or away team of the game being set up
+pub fn pick_starter(app: &mut DeadballApp, home: bool, index: usize) {
+    let Some(game) = app.game_modern.as_mut() else {
+        return;
+    };
+    let active = if home {
+        &mut game.home_active
+    } else {
+        &mut game.away_active
+    };
+    active.set_starter(index);
+    let active = Some(active.clone());
+    if home {
+        app.home_team_active = active;
+    } else {
+        app.away_team_active = active;
+    }
+}
+
+/// starts the next rested pitcher in each team's rotation for the current season
+pub fn pick_rested_starters(app: &mut DeadballApp) {
+    let Some(game) = app.game_modern.as_ref() else {
+        return;
+    };
+    let season = app.stats_window.season;
+    let home = app
+        .stats
+        .next_starter(&game.home.name, season, &game.home_active.pitching);
+    let away = app
+        .stats
+        .next_starter(&game.away.name, season, &game.away_active.pitching);
+    pick_starter(app, true, home);
+    pick_starter(app, false, away);
+}
+
 /// produces a String with player first name + last name
 /// takes Player struct as input
 pub fn get_player_name(player: &Player) -> String {
@@ -174,54 +692,208 @@ pub fn update_player_labels(team: &ActiveTeam) -> Vec<String> {
     ]
 }
 
-/// returns 3 bools indicating if runners are on each base based on game state
-pub fn runners_on_bool(runners: RunnersOn) -> (bool, bool, bool) {
-    let on_first: bool;
-    let on_second: bool;
-    let on_third: bool;
-    match runners {
-        RunnersOn::Runner000 => {
-            on_first = false;
-            on_second = false;
-            on_third = false;
-        }
-        RunnersOn::Runner100 => {
-            on_first = true;
-            on_second = false;
-            on_third = false;
-        }
-        RunnersOn::Runner010 => {
-            on_first = false;
-            on_second = true;
-            on_third = false;
+/// runs a swing/decision on the current game, through the game record when there is one so the
+/// game can be replayed later - with physical dice on it waits for the players' rolls instead
+pub fn play_action(app: &mut DeadballApp, action: Action) -> Result<(), ActionError> {
+    if app.physical_dice.enabled {
+        if let (Some(game), Some(state)) = (&app.game_modern, &app.game_state) {
+            state.check_action(game, &action)?;
         }
-        RunnersOn::Runner001 => {
-            on_first = false;
-            on_second = false;
-            on_third = true;
-        }
-        RunnersOn::Runner110 => {
-            on_first = true;
-            on_second = true;
-            on_third = false;
+        app.physical_dice.pending = Some(action);
+        app.physical_dice.rolls.clear();
+        return resolve_physical_dice(app).map(|_| ());
+    }
+    run_action(app, action, None)
+}
+
+/// tries the pending physical dice play with the rolls entered so far, it's played for real once
+/// nothing is missing - returns true if it was
+pub fn resolve_physical_dice(app: &mut DeadballApp) -> Result<bool, ActionError> {
+    let Some(action) = app.physical_dice.pending.clone() else {
+        return Ok(false);
+    };
+    let (Some(mut game), Some(state)) = (app.game_modern.clone(), app.game_state.clone()) else {
+        app.physical_dice = PhysicalDice {
+            enabled: true,
+            ..Default::default()
+        };
+        return Ok(false);
+    };
+    // a dry run on a copy finds the next roll the play needs
+    game.quiet = true;
+    let mut debug = DebugConfig::physical(app.physical_dice.rolls.clone());
+    if let Err(err) = apply_action(&mut game, state, &action, &mut debug) {
+        app.physical_dice.pending = None;
+        app.physical_dice.request = None;
+        return Err(err);
+    }
+    app.physical_dice.error.clear();
+    match debug.missing {
+        Some(request) => {
+            app.physical_dice.request = Some(request);
+            Ok(false)
         }
-        RunnersOn::Runner101 => {
-            on_first = true;
-            on_second = false;
-            on_third = true;
+        None => {
+            let rolls = std::mem::take(&mut app.physical_dice.rolls);
+            app.physical_dice.pending = None;
+            app.physical_dice.request = None;
+            run_action(app, action, Some(rolls))?;
+            Ok(true)
         }
-        RunnersOn::Runner011 => {
-            on_first = false;
-            on_second = true;
-            on_third = true;
+    }
+}
+
+/// adds the players' roll for the pending physical dice play, checked against the die asked for
+pub fn enter_physical_roll(app: &mut DeadballApp, roll: i32) -> Result<bool, String> {
+    let Some(request) = &app.physical_dice.request else {
+        return Err("No roll needed.".to_string());
+    };
+    if roll < 1 || roll > request.side {
+        return Err(format!(
+            "A d{} roll is between 1 and {}.",
+            request.side, request.side
+        ));
+    }
+    app.physical_dice.rolls.push(roll);
+
+    resolve_physical_dice(app).map_err(|err| err.to_string())
+}
+
+// plays [action] for real, [physical] rolls replace the debug roll settings - every play goes
+// through here, so it's where a finished game gets added to the stats
+fn run_action(
+    app: &mut DeadballApp,
+    action: Action,
+    physical: Option<Vec<i32>>,
+) -> Result<(), ActionError> {
+    let Some(snapshot) = game_snapshot(app) else {
+        return Ok(());
+    };
+    let (Some(game), Some(state)) = (app.game_modern.as_mut(), app.game_state.clone()) else {
+        return Ok(());
+    };
+    let mut physical_debug;
+    let debug = match physical {
+        Some(rolls) => {
+            physical_debug = DebugConfig::physical(rolls);
+            &mut physical_debug
         }
-        RunnersOn::Runner111 => {
-            on_first = true;
-            on_second = true;
-            on_third = true;
+        None => &mut app.debug_settings.debug_roll_state,
+    };
+    let label = action.label();
+    app.game_state = Some(match app.game_record.as_mut() {
+        Some(record) => record.play(game, state, action, debug)?,
+        None => apply_action(game, state, &action, debug)?,
+    });
+    app.undo_history.push(snapshot);
+    // game just ended, add it to the stats database
+    if record_finished_game(app) {
+        save_stats(app, label)?;
+    }
+
+    Ok(())
+}
+
+// writes the stats database after [action] added or took out the finished game
+fn save_stats(app: &DeadballApp, action: String) -> Result<(), ActionError> {
+    write_stats(&app.stats, &app.settings.stats_file()).map_err(|err| ActionError {
+        action,
+        reason: format!("failed to save stats: {}", err),
+    })
+}
+
+/// the current game, None if one hasn't been started
+pub fn game_snapshot(app: &DeadballApp) -> Option<GameSnapshot> {
+    Some(GameSnapshot {
+        state: app.game_state.clone()?,
+        game: app.game_modern.clone(),
+        record: app.game_record.clone(),
+    })
+}
+
+fn restore_snapshot(app: &mut DeadballApp, snapshot: GameSnapshot) {
+    // a play waiting on dice was for the game being left behind
+    app.physical_dice.pending = None;
+    app.physical_dice.request = None;
+    app.game_state = Some(snapshot.state);
+    app.game_modern = snapshot.game;
+    app.game_record = snapshot.record;
+}
+
+/// adds the game to the stats database the first time it's over, returns true if it was added
+/// - the stats from before are kept so undoing the last play can take the game back out
+pub fn record_finished_game(app: &mut DeadballApp) -> bool {
+    let (Some(game), Some(state)) = (&app.game_modern, &app.game_state) else {
+        return false;
+    };
+    if state.status != GameStatus::Over || app.stats_before_game.is_some() {
+        return false;
+    }
+    app.stats_before_game = Some(app.stats.clone());
+    app.stats.record_game(app.stats_window.season, game, state);
+
+    true
+}
+
+/// takes the game back out of the stats database once it isn't over anymore, returns true if it
+/// was taken out
+pub fn unrecord_finished_game(app: &mut DeadballApp) -> bool {
+    let over = app
+        .game_state
+        .as_ref()
+        .is_some_and(|state| state.status == GameStatus::Over);
+    if over {
+        return false;
+    }
+    match app.stats_before_game.take() {
+        Some(stats) => {
+            app.stats = stats;
+            true
         }
+        None => false,
+    }
+}
+
+/// rewinds the game to before the last play, returns false if there was nothing to undo
+/// NOTE: undoing the play that ended the game takes it back out of the stats database
+pub fn undo_play(app: &mut DeadballApp) -> Result<bool, ActionError> {
+    let Some(current) = game_snapshot(app) else {
+        return Ok(false);
+    };
+    let Some(previous) = app.undo_history.undo(current) else {
+        return Ok(false);
+    };
+    restore_snapshot(app, previous);
+    if unrecord_finished_game(app) {
+        save_stats(app, "Undo".to_string())?;
+    }
+
+    Ok(true)
+}
+
+/// puts back the last undone play, returns false if there was nothing to redo
+pub fn redo_play(app: &mut DeadballApp) -> Result<bool, ActionError> {
+    let Some(current) = game_snapshot(app) else {
+        return Ok(false);
+    };
+    let Some(next) = app.undo_history.redo(current) else {
+        return Ok(false);
+    };
+    restore_snapshot(app, next);
+    if record_finished_game(app) {
+        save_stats(app, "Redo".to_string())?;
     }
-    (on_first, on_second, on_third)
+
+    Ok(true)
+}
+
+/// [folder] inside [data_dir], ending in a separator so a file name can be typed after it
+pub fn data_folder(data_dir: &str, folder: &str) -> String {
+    Path::new(data_dir)
+        .join(folder)
+        .join("")
+        .display()
+        .to_string()
 }
 
 /// builds string for tooltip for batters and baserunners
@@ -277,3 +949,35 @@ pub fn update_debug_textedits(app: &mut DeadballApp) {
             [(app.debug_settings.debug_state.inning - 1) as usize] = errors;
     }
 }
+
+/// starts using [settings] - the create game defaults, data directory and theme
+/// stats are reloaded if the data directory moved
+pub fn apply_settings(app: &mut DeadballApp, ctx: Option<&egui::Context>, settings: Settings) {
+    if settings.data_dir != app.settings.data_dir {
+        app.stats.loaded = false;
+    }
+    app.data_dir = settings.data_dir.clone();
+    app.create_game_era = settings.default_era.clone();
+    app.oddity = settings.oddity;
+    app.gui_windows.console_window = settings.console_window;
+    app.replay_window.file = settings.record_dir();
+    if let Some(ctx) = ctx {
+        ctx.set_theme(theme_preference(settings.theme));
+    }
+    app.settings = settings;
+}
+
+/// writes the app's settings to the config directory, returns where they went
+pub fn save_settings(app: &DeadballApp) -> Result<PathBuf, String> {
+    let path = settings_path().ok_or("No config directory to save settings in.")?;
+    write_settings(&app.settings, &path).map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
+pub fn theme_preference(theme: Theme) -> egui::ThemePreference {
+    match theme {
+        Theme::System => egui::ThemePreference::System,
+        Theme::Dark => egui::ThemePreference::Dark,
+        Theme::Light => egui::ThemePreference::Light,
+    }
+}
@@ -0,0 +1,154 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use eframe::egui::{self, Color32, Label, RichText, Sense, Ui};
+
+use crate::characters::players::Player;
+use crate::core::stats::{player_key, BattingLine, BoxScore, PitchingLine};
+
+use super::gui_functions::get_player_name;
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// one team's batting order for the side panels, with today's stat lines - any length of lineup
+pub struct LineupPanel<'a> {
+    lineup: &'a [Player],
+    current_batter: Option<usize>,
+    pitcher: Option<&'a Player>,
+    box_score: Option<&'a BoxScore>,
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl<'a> LineupPanel<'a> {
+    pub fn new(lineup: &'a [Player]) -> Self {
+        LineupPanel {
+            lineup,
+            current_batter: None,
+            pitcher: None,
+            box_score: None,
+        }
+    }
+
+    /// batting order slot (0 based) to highlight as up or due up
+    pub fn current_batter(mut self, slot: Option<usize>) -> Self {
+        self.current_batter = slot;
+        self
+    }
+
+    /// pitcher in the game for this team, listed under the lineup
+    pub fn pitcher(mut self, pitcher: Option<&'a Player>) -> Self {
+        self.pitcher = pitcher;
+        self
+    }
+
+    /// box score the stat lines come from, nothing is shown without one
+    pub fn box_score(mut self, box_score: Option<&'a BoxScore>) -> Self {
+        self.box_score = box_score;
+        self
+    }
+
+    /// draws the lineup, returns the player whose name was clicked
+    pub fn show(self, ui: &mut Ui) -> Option<Player> {
+        let mut clicked = None;
+        egui::Grid::new("lineup").show(ui, |ui| {
+            for (slot, player) in self.lineup.iter().enumerate() {
+                let current = self.current_batter == Some(slot);
+                let line = self
+                    .box_score
+                    .and_then(|box_score| box_score.batting.get(&player_key(player)))
+                    .map(batting_summary)
+                    .unwrap_or_default();
+                let order = format!("{}.", slot + 1);
+                if current {
+                    ui.label(
+                        RichText::new(format!("▶ {}", order))
+                            .strong()
+                            .color(Color32::YELLOW),
+                    );
+                } else {
+                    ui.label(order);
+                }
+                if name_label(ui, player, current) {
+                    clicked = Some(player.clone());
+                }
+                ui.label(RichText::new(line).weak());
+                ui.end_row();
+            }
+            if let Some(pitcher) = self.pitcher {
+                let line = self
+                    .box_score
+                    .and_then(|box_score| box_score.pitching.get(&player_key(pitcher)))
+                    .map(pitching_summary)
+                    .unwrap_or_default();
+                ui.label("P");
+                if name_label(ui, pitcher, false) {
+                    clicked = Some(pitcher.clone());
+                }
+                ui.label(RichText::new(line).weak());
+                ui.end_row();
+            }
+        });
+
+        clicked
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// today's line for a hitter, e.g. "2-4, HR, 3 RBI" - empty before their first trip to the plate
+pub fn batting_summary(line: &BattingLine) -> String {
+    if line.plate_appearances == 0 {
+        return String::new();
+    }
+    let mut parts = vec![format!("{}-{}", line.hits, line.at_bats)];
+    for (count, stat) in [
+        (line.doubles, "2B"),
+        (line.triples, "3B"),
+        (line.home_runs, "HR"),
+        (line.rbi, "RBI"),
+        (line.walks, "BB"),
+        (line.strikeouts, "K"),
+    ] {
+        match count {
+            0 => {}
+            1 => parts.push(stat.to_string()),
+            n => parts.push(format!("{} {}", n, stat)),
+        }
+    }
+    parts.join(", ")
+}
+
+/// today's line for a pitcher, e.g. "5.1 IP, 4 H, 2 R, 1 BB, 6 K"
+pub fn pitching_summary(line: &PitchingLine) -> String {
+    format!(
+        "{} IP, {} H, {} R, {} BB, {} K",
+        line.innings(),
+        line.hits,
+        line.runs,
+        line.walks,
+        line.strikeouts
+    )
+}
+
+// clickable name with the old position/hand/targets/traits summary on hover
+fn name_label(ui: &mut Ui, player: &Player, current: bool) -> bool {
+    let mut name = RichText::new(get_player_name(player));
+    if current {
+        name = name.strong();
+    }
+    let info = format!(
+        "{:?} | {:?} | {} | {} | {:?}",
+        player.position,
+        player.handedness,
+        player.batter_target,
+        player.on_base_target,
+        player.traits
+    );
+    ui.add(Label::new(name).sense(Sense::click()))
+        .on_hover_text(info)
+        .clicked()
+}
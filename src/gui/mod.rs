@@ -2,3 +2,6 @@ pub mod app; // main app logic, egui render loop
 pub mod debug; // functions and features related to debugging program
 pub mod gui_functions; // functions and structures related to interface, IO, etc.
 pub mod draw_fn; // functions to render specific windows, outside of the main app interface
+pub mod scoreboard; // line score widget, runs by inning with outs, bases and the matchup
+pub mod diamond; // base runners, fielder labels and the last play animated on the field image
+pub mod lineup; // batting order side panels with the current batter and game stat lines
@@ -0,0 +1,207 @@
+/*========================================================
+MODULE INCLUSIONS
+========================================================*/
+use eframe::egui::{
+    self, Color32, Pos2, Response, RichText, Sense, Shape, Stroke, Ui, Vec2, Widget,
+};
+
+use crate::characters::{players::Player, teams::TeamState};
+use crate::core::bases::Bases;
+use crate::core::game_functions::{GameState, GameStatus, InningTB, Outs};
+
+use super::gui_functions::get_player_name;
+
+/*========================================================
+STRUCT DEFINITIONS
+========================================================*/
+/// one team's line in the line score
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineScoreRow {
+    pub innings: Vec<Option<u32>>, // runs by inning, None for innings the team hasn't batted in yet
+    pub runs: u32,
+    pub hits: u32,
+    pub errors: u32,
+}
+
+/// inning by inning line score with the outs, bases and current matchup - add it with ui.add()
+pub struct Scoreboard<'a> {
+    state: &'a GameState,
+    away: &'a str,
+    home: &'a str,
+    batter: Option<&'a Player>,
+    pitcher: Option<&'a Player>,
+}
+
+/*========================================================
+IMPL BLOCKS
+========================================================*/
+impl LineScoreRow {
+    /// [batted] is how many innings the team has come up in, [innings] is how many columns to fill
+    fn new(team: &TeamState, batted: usize, innings: usize) -> Self {
+        LineScoreRow {
+            innings: (0..innings)
+                .map(|i| {
+                    if i < batted {
+                        Some(team.runs.get(i).copied().unwrap_or(0))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+            runs: team.runs.iter().sum(),
+            hits: team.hits.iter().sum(),
+            errors: team.errors.iter().sum(),
+        }
+    }
+}
+
+impl<'a> Scoreboard<'a> {
+    pub fn new(state: &'a GameState) -> Self {
+        Scoreboard {
+            state,
+            away: "Away",
+            home: "Home",
+            batter: None,
+            pitcher: None,
+        }
+    }
+
+    /// team names for the two rows
+    pub fn teams(mut self, away: &'a str, home: &'a str) -> Self {
+        self.away = away;
+        self.home = home;
+        self
+    }
+
+    /// batter at the plate and the pitcher facing them
+    pub fn matchup(mut self, batter: Option<&'a Player>, pitcher: Option<&'a Player>) -> Self {
+        self.batter = batter;
+        self.pitcher = pitcher;
+        self
+    }
+}
+
+impl Widget for Scoreboard<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let state = self.state;
+        let [away, home] = line_score(state);
+        let current = state.inning as usize;
+        ui.vertical(|ui| {
+            egui::Grid::new("line score").striped(true).show(ui, |ui| {
+                ui.label("");
+                for inning in 1..=away.innings.len() {
+                    let text = RichText::new(inning.to_string());
+                    if inning == current && state.status == GameStatus::Ongoing {
+                        ui.label(text.strong().color(Color32::YELLOW));
+                    } else {
+                        ui.label(text);
+                    }
+                }
+                for header in ["R", "H", "E"] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+                for (name, row) in [(self.away, &away), (self.home, &home)] {
+                    ui.label(name);
+                    for runs in row.innings.iter() {
+                        ui.label(runs.map_or(String::new(), |runs| runs.to_string()));
+                    }
+                    ui.label(RichText::new(row.runs.to_string()).strong());
+                    ui.label(row.hits.to_string());
+                    ui.label(row.errors.to_string());
+                    ui.end_row();
+                }
+            });
+            ui.horizontal(|ui| {
+                let status = match (&state.status, &state.inning_half) {
+                    (GameStatus::NotStarted, _) => "Pregame".to_string(),
+                    (GameStatus::Over, _) => "Final".to_string(),
+                    (GameStatus::Ongoing, InningTB::Top) => format!("Top {}", state.inning),
+                    (GameStatus::Ongoing, InningTB::Bottom) => format!("Bot {}", state.inning),
+                };
+                ui.label(RichText::new(status).strong());
+                ui.label("Outs:");
+                out_lights(ui, &state.outs);
+                base_lights(ui, &state.bases);
+                if let Some(batter) = self.batter {
+                    ui.label(format!("AB: {}", get_player_name(batter)));
+                }
+                if let Some(pitcher) = self.pitcher {
+                    ui.label(format!("P: {}", get_player_name(pitcher)));
+                }
+            });
+        })
+        .response
+    }
+}
+
+/*========================================================
+FUNCTION DEFINITIONS
+========================================================*/
+/// away and home lines, at least 9 innings wide and one more column for every extra inning
+pub fn line_score(state: &GameState) -> [LineScoreRow; 2] {
+    let inning = state.inning as usize;
+    let (away_batted, home_batted) = match (&state.status, &state.inning_half) {
+        (GameStatus::NotStarted, _) => (0, 0),
+        // the last half inning flips back to the top without starting a new inning
+        (GameStatus::Over, _) => (inning, inning),
+        (GameStatus::Ongoing, InningTB::Top) => (inning, inning - 1),
+        (GameStatus::Ongoing, InningTB::Bottom) => (inning, inning),
+    };
+    let innings = inning.max(9);
+    [
+        LineScoreRow::new(&state.away_state, away_batted, innings),
+        LineScoreRow::new(&state.home_state, home_batted, innings),
+    ]
+}
+
+// three lights, lit for each out
+fn out_lights(ui: &mut Ui, outs: &Outs) {
+    let lit = match outs {
+        Outs::None => 0,
+        Outs::One => 1,
+        Outs::Two => 2,
+        Outs::Three => 3,
+    };
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(42.0, 14.0), Sense::hover());
+    let painter = ui.painter();
+    for i in 0..3 {
+        let center = Pos2::new(rect.left() + 7.0 + 14.0 * i as f32, rect.center().y);
+        if i < lit {
+            painter.circle_filled(center, 5.0, Color32::RED);
+        } else {
+            painter.circle_stroke(center, 5.0, Stroke::new(1.0, Color32::GRAY));
+        }
+    }
+}
+
+// small diamond with first, second and third lit when a runner is on
+fn base_lights(ui: &mut Ui, bases: &Bases) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(30.0, 22.0), Sense::hover());
+    let painter = ui.painter();
+    let center = rect.center();
+    let offsets = [
+        (1, Vec2::new(9.0, 4.0)),
+        (2, Vec2::new(0.0, -5.0)),
+        (3, Vec2::new(-9.0, 4.0)),
+    ];
+    for (base, offset) in offsets {
+        let base_center = center + offset;
+        let points = vec![
+            base_center + Vec2::new(0.0, -5.0),
+            base_center + Vec2::new(5.0, 0.0),
+            base_center + Vec2::new(0.0, 5.0),
+            base_center + Vec2::new(-5.0, 0.0),
+        ];
+        let fill = if bases.is_occupied(base) {
+            Color32::GOLD
+        } else {
+            Color32::TRANSPARENT
+        };
+        painter.add(Shape::convex_polygon(
+            points,
+            fill,
+            Stroke::new(1.0, Color32::GRAY),
+        ));
+    }
+}
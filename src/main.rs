@@ -3,6 +3,7 @@
  * ===========================================================================================*/
 // LOCAL IMPORTS
 mod characters;
+mod cli;
 mod core;
 mod gui;
 use gui::app::*;
@@ -11,9 +12,19 @@ use gui::app::*;
 use eframe::egui::{self, ViewportBuilder};
 
 fn main() -> Result<(), eframe::Error> {
+    // command line tools (league checks, etc.), no arguments starts the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
+    // window comes back the size and place it was left in
+    let settings = core::settings::startup_settings();
+    let [width, height] = settings.window_size;
     let viewport = ViewportBuilder {
         title: Some("Deadball".to_string()),
-        inner_size: Some(egui::vec2(800.0, 600.0)),
+        inner_size: Some(egui::vec2(width, height)),
+        position: settings.window_pos.map(|[x, y]| egui::pos2(x, y)),
         ..Default::default()
     };
     let options = eframe::NativeOptions {
@@ -25,7 +36,7 @@ fn main() -> Result<(), eframe::Error> {
         options,
         Box::new(|cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::<DeadballApp>::default())
+            Ok(Box::new(DeadballApp::new(&cc.egui_ctx, settings)))
         }),
     )
     /* SEGMENT OF CODE TO GENERATE TEAMS, DON'T NEED IT EVERY TIME
@@ -157,11 +168,14 @@ TESTS
 #[allow(unused_variables)]
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use std::{fs, vec};
 
     use crate::characters::ballparks::*;
     //use crate::core::gameFunctions::atBatResults;
-    use crate::core::{game_functions, roll};
+    use crate::core::bases::Bases;
+    use crate::core::stats::*;
+    use crate::core::{game_functions, generate_id, roll, seed_rolls};
     use crate::gui::debug::{debug_roll, DebugConfig};
     use crate::{
         characters::players::*, characters::teams::*, core::file_locations::*,
@@ -173,6 +187,32 @@ mod tests {
 
     use super::*;
 
+    /// blue team at red team in Nightside Field, the game most tests play with
+    fn test_game(oddity: bool) -> GameModern {
+        let load = |file: &str| load_team(fs::read_to_string(file).unwrap(), file).unwrap();
+        let ballpark = load_park_modern(
+            fs::read_to_string("src/testfiles/game/ballparks/Nightside Field.dbb").unwrap(),
+            "src/testfiles/game/ballparks/Nightside Field.dbb",
+        )
+        .unwrap();
+        create_modern_game(
+            load("src/testfiles/game/teams/red_team.dbt"),
+            load("src/testfiles/game/teams/blue_team.dbt"),
+            ballpark,
+            oddity,
+            Path::new(DATA_LOCATION),
+        )
+        .unwrap()
+    }
+
+    /// placeholder runner for [base], named so tests can tell runners apart
+    fn test_runner(base: u32) -> Player {
+        Player {
+            last_name: format!("Runner{}", base),
+            ..Player::default()
+        }
+    }
+
     #[test]
     fn dice_roll_check() {
         // kind of hard to test that the dice rolls are random, but this should at least test that they are within expected range
@@ -182,6 +222,28 @@ mod tests {
             test_roll <= side && test_roll >= 1,
             "dice roll is outside of expected bounds"
         );
+
+        // every face comes up, the top one included (1000 rolls all missing one face is
+        // ~1e-79 likely)
+        let mut config = DebugConfig {
+            mode: true,
+            rolls: vec![],
+            ..DebugConfig::default()
+        };
+        let mut faces = [[false; 6]; 2];
+        for _ in 0..1000 {
+            for (i, face) in [roll(6), debug_roll(&mut config, 6, "test")]
+                .into_iter()
+                .enumerate()
+            {
+                assert!(
+                    (1..=6).contains(&face),
+                    "dice roll is outside of expected bounds"
+                );
+                faces[i][face as usize - 1] = true;
+            }
+        }
+        assert!(faces.iter().flatten().all(|seen| *seen));
     }
 
     #[test]
@@ -205,8 +267,8 @@ mod tests {
     #[test]
     fn load_player_file() {
         let player_file_path = "src/testfiles/sample_player.dbp".to_string();
-        let contents = fs::read_to_string(player_file_path).unwrap();
-        let test_player = load_player(contents);
+        let contents = fs::read_to_string(&player_file_path).unwrap();
+        let test_player = load_player(contents, &player_file_path).unwrap();
         let test_name = test_player.first_name;
         let test_last = test_player.last_name;
         let test_pos = test_player.position;
@@ -232,8 +294,8 @@ mod tests {
         assert!(matches!(vec![InjurySeverity::Uninjured], test_sev));
 
         let player2_file_path = "src/testfiles/sample2.dbp".to_string();
-        let contents2 = fs::read_to_string(player2_file_path).unwrap();
-        let test_player2 = load_player(contents2);
+        let contents2 = fs::read_to_string(&player2_file_path).unwrap();
+        let test_player2 = load_player(contents2, &player2_file_path).unwrap();
         let test_nick2 = test_player2.nickname;
         let test_loc2 = test_player2.injury_location;
         let test_sev2 = test_player2.injury_severity;
@@ -245,6 +307,7 @@ mod tests {
     #[test]
     fn write_player_file() {
         let test_player = Player {
+            id: "".to_string(),
             first_name: "Seth".to_string(),
             last_name: "Loveall".to_string(),
             nickname: "Seth Loveall".to_string(),
@@ -273,7 +336,7 @@ mod tests {
         let write_result = write_player(&test_player, filename);
 
         let contents = fs::read_to_string(filename).unwrap();
-        let test_player = load_player(contents);
+        let test_player = load_player(contents, filename).unwrap();
         let test_first = test_player.first_name;
         let test_last = test_player.last_name;
         let test_nick = test_player.nickname;
@@ -323,9 +386,9 @@ mod tests {
     #[test]
     fn test_load_team() {
         let team_file_path = "src/testfiles/detroit_steam_hammers.dbt".to_string();
-        let contents = fs::read_to_string(team_file_path).unwrap();
+        let contents = fs::read_to_string(&team_file_path).unwrap();
 
-        let test_team = load_team(contents);
+        let test_team = load_team(contents, &team_file_path).unwrap();
         let test_name = test_team.name;
         let test_ballpark = test_team.ballpark;
         let test_manager = test_team.manager;
@@ -389,6 +452,7 @@ mod tests {
     #[test]
     fn test_write_team() {
         let test_team = Team {
+            id: "test".to_string(),
             name: "Test Team".to_string(),
             ballpark: "Test Ballpark".to_string(),
             manager: "Test Manager".to_string(),
@@ -423,7 +487,7 @@ mod tests {
         let write_result = write_team(test_team, filename);
 
         let contents = fs::read_to_string(filename).unwrap();
-        let read_team = load_team(contents);
+        let read_team = load_team(contents, filename).unwrap();
         let test_name = read_team.name;
         let test_ballpark = read_team.ballpark;
         let test_manager = read_team.manager;
@@ -492,9 +556,9 @@ mod tests {
     #[test]
     fn test_load_park() {
         let park_file_path = "src/testfiles/railyard.dbb".to_string();
-        let contents = fs::read_to_string(park_file_path).unwrap();
+        let contents = fs::read_to_string(&park_file_path).unwrap();
 
-        let test_park_modern = load_park_modern(contents);
+        let test_park_modern = load_park_modern(contents, &park_file_path).unwrap();
         let modern_name = test_park_modern.name;
         let modern_location = test_park_modern.location;
         let modern_type = test_park_modern.park_type;
@@ -514,9 +578,9 @@ mod tests {
         assert!(matches!(vec![Quirks::ExpansiveOutfield], modern_quirks));
 
         let ancient_file_path = "src/testfiles/mayfair_park.dbb".to_string();
-        let ancient_contents = fs::read_to_string(ancient_file_path).unwrap();
+        let ancient_contents = fs::read_to_string(&ancient_file_path).unwrap();
 
-        let test_park_ancient = load_park_ancient(ancient_contents);
+        let test_park_ancient = load_park_ancient(ancient_contents, &ancient_file_path).unwrap();
         let ancient_name = test_park_ancient.name;
         let ancient_location = test_park_ancient.location;
         let ancient_type = test_park_ancient.park_type;
@@ -546,6 +610,7 @@ mod tests {
     #[test]
     fn test_create_modern_game() {
         let mut team1 = Team {
+            id: "test".to_string(),
             name: "Test 1".to_string(),
             ballpark: "test".to_string(),
             manager: "test".to_string(),
@@ -573,6 +638,7 @@ mod tests {
         };
 
         let mut team2 = Team {
+            id: "test".to_string(),
             name: "Test 2".to_string(),
             ballpark: "test".to_string(),
             manager: "test".to_string(),
@@ -610,6 +676,7 @@ mod tests {
         };
 
         let ballpark = BallparkModern {
+            id: "test".to_string(),
             name: "test".to_string(),
             location: Location::SmallTown,
             park_type: StadiumTypeModern::Retro,
@@ -620,7 +687,13 @@ mod tests {
             quirks: vec![Quirks::OddLeft],
         };
 
-        let test_result = create_modern_game(team1.clone(), team2.clone(), ballpark.clone(), false);
+        let test_result = create_modern_game(
+            team1.clone(),
+            team2.clone(),
+            ballpark.clone(),
+            false,
+            Path::new(DATA_LOCATION),
+        );
         assert!(matches!(
             Err::<GameModern, core::game_functions::TeamError>(TeamError {
                 message: "Home team does not have a complete roster".to_string(),
@@ -641,7 +714,13 @@ mod tests {
             "test".to_string(),
         ];
 
-        let test_result2 = create_modern_game(team1, team2.clone(), ballpark, false);
+        let test_result2 = create_modern_game(
+            team1,
+            team2.clone(),
+            ballpark,
+            false,
+            Path::new(DATA_LOCATION),
+        );
         assert!(matches!(
             Err::<GameModern, core::game_functions::TeamError>(TeamError {
                 message: "Away team is not for the modern era".to_string(),
@@ -671,7 +750,7 @@ mod tests {
     fn test_load_roster() {
         let filename = "src/testfiles/detroit_steam_hammers.dbt";
         let contents = fs::read_to_string(filename).unwrap();
-        let read_team = load_team(contents);
+        let read_team = load_team(contents, filename).unwrap();
         let test_roster = &read_team.roster;
         let test_bench = &read_team.bench;
         let test_pitcher = &read_team.pitcher;
@@ -708,6 +787,7 @@ mod tests {
     fn test_runnerson() {
         // create pitcher to fill in game state for test
         let test_player = Player {
+            id: "".to_string(),
             first_name: "".to_string(),
             last_name: "".to_string(),
             nickname: "".to_string(),
@@ -723,6 +803,7 @@ mod tests {
         let home_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -731,6 +812,7 @@ mod tests {
         let away_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -741,43 +823,42 @@ mod tests {
             inning: 1,
             inning_half: InningTB::Bottom,
             outs: Outs::Two,
-            runners: RunnersOn::Runner000,
-            runner1: None,
-            runner2: None,
-            runner3: None,
+            bases: Bases::default(),
             home_state,
             away_state,
             game_text: "test".to_string(),
+            box_score: BoxScore::default(),
+            plays: vec![],
         };
 
         let r1 = runnerson(&state);
         assert_eq!(r1, 0);
 
-        state.runners = RunnersOn::Runner100;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner100, |_| test_player.clone());
         let r2 = runnerson(&state);
         assert_eq!(r2, 1);
 
-        state.runners = RunnersOn::Runner010;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner010, |_| test_player.clone());
         let r3 = runnerson(&state);
         assert_eq!(r3, 1);
 
-        state.runners = RunnersOn::Runner001;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner001, |_| test_player.clone());
         let r4 = runnerson(&state);
         assert_eq!(r4, 1);
 
-        state.runners = RunnersOn::Runner110;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner110, |_| test_player.clone());
         let r5 = runnerson(&state);
         assert_eq!(r5, 2);
 
-        state.runners = RunnersOn::Runner101;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner101, |_| test_player.clone());
         let r6 = runnerson(&state);
         assert_eq!(r6, 2);
 
-        state.runners = RunnersOn::Runner011;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner011, |_| test_player.clone());
         let r7 = runnerson(&state);
         assert_eq!(r7, 2);
 
-        state.runners = RunnersOn::Runner111;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner111, |_| test_player.clone());
         let r8 = runnerson(&state);
         assert_eq!(r8, 3);
     }
@@ -787,6 +868,7 @@ mod tests {
     fn test_runners_advance() {
         // create test structures
         let test_player = Player {
+            id: "".to_string(),
             first_name: "".to_string(),
             last_name: "".to_string(),
             nickname: "".to_string(),
@@ -802,6 +884,7 @@ mod tests {
         let home_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -810,6 +893,7 @@ mod tests {
         let away_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -820,41 +904,40 @@ mod tests {
             inning: 1,
             inning_half: InningTB::Bottom,
             outs: Outs::Two,
-            runners: RunnersOn::Runner100,
-            runner1: None,
-            runner2: None,
-            runner3: None,
+            bases: Bases::from_occupancy(RunnersOn::Runner100, |_| test_player.clone()),
             home_state,
             away_state,
             game_text: "test".to_string(),
+            box_score: BoxScore::default(),
+            plays: vec![],
         };
 
         state = runners_advance(state, &1);
-        assert!(matches!(state.runners, RunnersOn::Runner010));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner010);
 
         state = runners_advance(state, &1);
-        assert!(matches!(state.runners, RunnersOn::Runner001));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner001);
 
         state = runners_advance(state, &1);
-        assert!(matches!(state.runners, RunnersOn::Runner000));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner000);
         assert_eq!(state.home_state.runs[(state.inning - 1) as usize], 1);
 
-        state.runners = RunnersOn::Runner100;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner100, |_| test_player.clone());
         state = runners_advance(state, &2);
-        assert!(matches!(state.runners, RunnersOn::Runner001));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner001);
 
         state = runners_advance(state, &2);
-        assert!(matches!(state.runners, RunnersOn::Runner000));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner000);
         assert_eq!(state.home_state.runs[(state.inning - 1) as usize], 2);
 
-        state.runners = RunnersOn::Runner011;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner011, |_| test_player.clone());
         state = runners_advance(state, &2);
-        assert!(matches!(state.runners, RunnersOn::Runner000));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner000);
         assert_eq!(state.home_state.runs[(state.inning - 1) as usize], 4);
 
-        state.runners = RunnersOn::Runner110;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner110, |_| test_player.clone());
         state = runners_advance(state, &3);
-        assert!(matches!(state.runners, RunnersOn::Runner000));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner000);
         assert_eq!(state.home_state.runs[(state.inning - 1) as usize], 6);
     }
 
@@ -862,6 +945,7 @@ mod tests {
     #[test]
     fn test_add_runners() {
         let test_player = Player {
+            id: "".to_string(),
             first_name: "".to_string(),
             last_name: "".to_string(),
             nickname: "".to_string(),
@@ -877,6 +961,7 @@ mod tests {
         let home_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -885,6 +970,7 @@ mod tests {
         let away_state = TeamState {
             current_batter: 1,
             current_pitcher: test_player.clone(),
+            starter: test_player.clone(),
             innings_pitched: 1,
             runs: vec![0],
             hits: vec![0],
@@ -895,16 +981,16 @@ mod tests {
             inning: 1,
             inning_half: InningTB::Bottom,
             outs: Outs::Two,
-            runners: RunnersOn::Runner100,
-            runner1: None,
-            runner2: None,
-            runner3: None,
+            bases: Bases::from_occupancy(RunnersOn::Runner100, |_| test_player.clone()),
             home_state,
             away_state,
             game_text: "test".to_string(),
+            box_score: BoxScore::default(),
+            plays: vec![],
         };
 
         let player1 = Player {
+            id: "".to_string(),
             first_name: "Seth".to_string(),
             nickname: "".to_string(),
             last_name: "Loveall".to_string(),
@@ -917,20 +1003,415 @@ mod tests {
             injury_location: vec![InjuryLocation::None],
             injury_severity: vec![],
         };
-        state.runners = RunnersOn::Runner011;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner011, |_| test_player.clone());
         state = add_runner(state, &1, player1.clone());
-        assert!(matches!(state.runners, RunnersOn::Runner111));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner111);
 
-        state.runners = RunnersOn::Runner101;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner101, |_| test_player.clone());
         state = add_runner(state, &2, player1.clone());
-        assert!(matches!(state.runners, RunnersOn::Runner111));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner111);
 
-        state.runners = RunnersOn::Runner000;
+        state.bases = Bases::from_occupancy(RunnersOn::Runner000, |_| test_player.clone());
         state = add_runner(state, &1, player1.clone());
-        assert!(matches!(state.runners, RunnersOn::Runner100));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner100);
 
         state = add_runner(state, &2, player1.clone());
-        assert!(matches!(state.runners, RunnersOn::Runner110));
+        assert_eq!(state.bases.occupancy(), RunnersOn::Runner110);
+    }
+
+    // Bases advance/force test function
+    #[test]
+    fn test_bases() {
+        let mut bases = Bases::from_occupancy(RunnersOn::Runner101, test_runner);
+        assert_eq!(bases.count(), 2);
+        assert_eq!(bases.lead_runner(), Some(3));
+        assert!(!bases.place(3, test_runner(4)));
+
+        // walk only pushes the forced runners
+        assert_eq!(bases.force(1), 0);
+        assert_eq!(bases.occupancy(), RunnersOn::Runner011);
+        assert_eq!(bases.runner(2).unwrap().last_name, "Runner1");
+        assert!(bases.place(1, test_runner(4)));
+        assert_eq!(bases.force(1), 1);
+        assert_eq!(bases.runner(3).unwrap().last_name, "Runner1");
+
+        // advancing moves the lead runner first
+        assert_eq!(bases.advance_from(2, 1), 1);
+        assert_eq!(bases.occupancy(), RunnersOn::Runner001);
+        assert_eq!(bases.runner(3).unwrap().last_name, "Runner4");
+        assert_eq!(bases.advance(1), 1);
+        assert!(bases.is_empty());
+    }
+
+    #[test]
+    fn test_productive_out1() {
+        let mut state = new_game_state_struct();
+        state.status = GameStatus::Ongoing;
+        state.home_state.current_pitcher.traits = vec![Traits::None];
+
+        // grounder to second moves the runner up, only the batter is out
+        state.bases = Bases::from_occupancy(RunnersOn::Runner100, test_runner);
+        let after = productive_out1(state.clone(), &44);
+        assert_eq!(after.outs, Outs::One);
+        assert_eq!(after.bases.occupancy(), RunnersOn::Runner010);
+        state.bases = Bases::from_occupancy(RunnersOn::Runner101, test_runner);
+        let after = productive_out1(state.clone(), &44);
+        assert_eq!(after.outs, Outs::One);
+        assert_eq!(after.bases.occupancy(), RunnersOn::Runner011);
+
+        // GB+ turns two on a 2, with or without a runner on third
+        state.home_state.current_pitcher.traits = vec![Traits::GroundballMachine];
+        state.bases = Bases::from_occupancy(RunnersOn::Runner100, test_runner);
+        let after = productive_out1(state.clone(), &42);
+        assert_eq!(after.outs, Outs::Two);
+        assert!(after.bases.is_empty());
+        state.bases = Bases::from_occupancy(RunnersOn::Runner101, test_runner);
+        let after = productive_out1(state, &42);
+        assert_eq!(after.outs, Outs::Two);
+        assert_eq!(after.bases.occupancy(), RunnersOn::Runner001);
+    }
+
+    #[test]
+    fn test_actions() {
+        use crate::core::actions::{apply_action, Action, ActionSide};
+
+        let mut game = test_game(true);
+        game.quiet = true;
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        let legal = |state: &GameState| -> Vec<String> {
+            state
+                .action_options(&game)
+                .iter()
+                .filter(|option| option.legal && option.side == ActionSide::Offense)
+                .map(|option| option.action.label())
+                .collect()
+        };
+
+        // nothing but the first pitch before the game starts
+        assert_eq!(legal(&state), vec!["Swing", "Away substitution"]);
+        state.status = GameStatus::Ongoing;
+        assert_eq!(legal(&state), vec!["Swing", "Away substitution"]);
+
+        state.bases = Bases::from_occupancy(RunnersOn::Runner100, test_runner);
+        assert_eq!(
+            legal(&state),
+            vec![
+                "Swing",
+                "Steal 2nd",
+                "Bunt",
+                "Hit & Run",
+                "Away substitution"
+            ]
+        );
+        state.bases = Bases::from_occupancy(RunnersOn::Runner110, test_runner);
+        assert_eq!(
+            legal(&state),
+            vec![
+                "Swing",
+                "Steal 3rd",
+                "Double Steal",
+                "Bunt",
+                "Away substitution"
+            ]
+        );
+        // only a speedy runner steals home
+        state.bases = Bases::from_occupancy(RunnersOn::Runner001, test_runner);
+        assert_eq!(legal(&state), vec!["Swing", "Bunt", "Away substitution"]);
+        state.bases = Bases::from_occupancy(RunnersOn::Runner001, |_| Player {
+            traits: vec![Traits::SpeedyRunner],
+            ..Player::default()
+        });
+        assert!(legal(&state).contains(&"Steal Home".to_string()));
+
+        // the fielding team can always make a change, and the inning has to be live to run
+        let options = state.action_options(&game);
+        let defense: Vec<_> = options
+            .iter()
+            .filter(|option| option.side == ActionSide::Defense)
+            .collect();
+        assert_eq!(defense.len(), 1);
+        assert_eq!(defense[0].action.label(), "Home substitution");
+        assert!(defense[0].legal);
+        state.outs = Outs::Three;
+        assert_eq!(legal(&state), vec!["Swing", "Away substitution"]);
+
+        // illegal actions are errors, not panics, and leave the game alone
+        state.outs = Outs::None;
+        state.bases = Bases::default();
+        let mut debug = DebugConfig::default();
+        let steal = Action::Steal(StealType::Second);
+        let err = apply_action(&mut game, state.clone(), &steal, &mut debug).unwrap_err();
+        assert_eq!(err.reason, "no runner on base 1");
+        assert!(apply_action(&mut game, state.clone(), &Action::HitAndRun, &mut debug).is_err());
+        let mut team = game.home_active.clone();
+        team.batting_order.pop();
+        let short = Action::Substitution { home: true, team };
+        let err = apply_action(&mut game, state.clone(), &short, &mut debug).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Home substitution: 8 batters in the lineup, needs 9"
+        );
+        assert_eq!(game.home_active.batting_order.len(), 9);
+        state.status = GameStatus::Over;
+        assert!(state
+            .action_options(&game)
+            .iter()
+            .all(|option| !option.legal));
+    }
+
+    #[test]
+    fn test_team_editor() {
+        use crate::characters::teams::RosterGroup;
+        use crate::gui::gui_functions::TeamEditor;
+
+        let data_dir = Path::new(DATA_LOCATION);
+        let mut editor = TeamEditor::default();
+        assert!(editor
+            .open("src/testfiles/game/teams/missing.dbt", data_dir)
+            .is_err());
+        editor
+            .open("src/testfiles/game/teams/red_team.dbt", data_dir)
+            .unwrap();
+        let team = editor.team.clone().unwrap();
+        assert_eq!(
+            editor.groups[RosterGroup::Roster as usize].len(),
+            team.roster.len()
+        );
+        assert_eq!(
+            editor.groups[RosterGroup::Bullpen as usize].len(),
+            team.bullpen.len()
+        );
+
+        // move a reliever into the rotation, swap out the bench and regenerate a starter
+        let reliever = team.bullpen[0].clone();
+        editor.move_player(RosterGroup::Bullpen, 0, RosterGroup::Pitcher);
+        editor.remove_player(RosterGroup::Bench, 0);
+        let firstnames = vec!["Test".to_string()];
+        let lastnames = vec!["Player".to_string()];
+        let catcher = editor.groups[RosterGroup::Roster as usize][0]
+            .player
+            .clone();
+        editor.regenerate_player(RosterGroup::Roster, 0, &firstnames, &lastnames);
+        let new = editor.groups[RosterGroup::Roster as usize][0]
+            .player
+            .clone();
+        assert_eq!(new.position, catcher.position);
+        assert_ne!(new.id, catcher.id);
+
+        // save somewhere else, the new player gets a file and the team points at it
+        let folder = std::env::temp_dir().join(format!("deadball_editor_{}", roll(100000)));
+        let file = folder.join("red_team.dbt");
+        editor.file = file.to_string_lossy().to_string();
+        editor.team.as_mut().unwrap().motto = "Edited".to_string();
+        editor.save(&folder).unwrap();
+        assert!(folder
+            .join("players")
+            .join(format!("{}.dbp", new.id))
+            .exists());
+        let saved = load_team(fs::read_to_string(&file).unwrap(), &editor.file).unwrap();
+        assert_eq!(saved.motto, "Edited");
+        assert_eq!(saved.roster[0], new.id);
+        assert_eq!(saved.roster[1..], team.roster[1..]);
+        assert_eq!(
+            saved.pitcher,
+            [team.pitcher.clone(), vec![reliever]].concat()
+        );
+        assert_eq!(saved.bench, team.bench[1..]);
+        assert_eq!(saved.bullpen, team.bullpen[1..]);
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_team_wizard() {
+        use crate::gui::gui_functions::TeamWizard;
+
+        let folder = std::env::temp_dir().join(format!("deadball_wizard_{}", roll(100000)));
+        let databases = DeadballDatabases {
+            first_names: vec![],
+            last_names: vec!["Wizard".to_string()],
+            park1: vec!["Test".to_string()],
+            park2: vec!["Park".to_string()],
+            ..Default::default()
+        };
+        let mut wizard = TeamWizard {
+            name_override: true,
+            name: "Wizards".to_string(),
+            location_override: true,
+            location: Location::Metropolis,
+            counts: [7, 2, 5, 3],
+            output_dir: folder.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        assert!(wizard.generate(&databases).is_err());
+        wizard.counts[RosterGroup::Roster as usize] = 9;
+        // no first names loaded and none picked
+        assert!(wizard.generate(&databases).is_err());
+        wizard.first_name_db = "src/databases/firstname.csv".to_string();
+        wizard.generate(&databases).unwrap();
+
+        // the preview is the whole team, nothing on disk yet
+        let generated = wizard.preview.clone().unwrap();
+        assert!(!folder.exists());
+        assert_eq!(generated.team.location, Location::Metropolis);
+        assert_eq!(generated.team.ballpark, generated.ballpark.id());
+        for group in RosterGroup::ALL {
+            assert_eq!(
+                generated.players[group as usize].len(),
+                wizard.counts[group as usize] as usize
+            );
+            assert_eq!(
+                generated.team.group(group).len(),
+                wizard.counts[group as usize] as usize
+            );
+        }
+        assert_eq!(generated.players[0][0].position, Position::Catcher);
+        assert_eq!(generated.players[0][8].position, Position::Firstbase);
+        assert!(generated.players[2]
+            .iter()
+            .all(|p| p.position == Position::Pitcher));
+
+        // written into the league folders under the output directory
+        let file = wizard.save().unwrap();
+        assert_eq!(
+            file,
+            folder
+                .join("teams")
+                .join(format!("{}.dbt", generated.team.id))
+        );
+        let team = load_team(fs::read_to_string(&file).unwrap(), "wizard").unwrap();
+        assert_eq!(team.pitcher.len(), 5);
+        for reference in team.pitcher.iter().chain(team.roster.iter()) {
+            load_player_ref(reference, &folder).unwrap();
+        }
+        assert!(folder
+            .join("ballparks")
+            .join(format!("{}.dbb", team.ballpark))
+            .exists());
+
+        // saving the same preview again picks new IDs instead of overwriting the first team
+        let second = wizard.save().unwrap();
+        assert_ne!(second, file);
+        let second = load_team(fs::read_to_string(&second).unwrap(), "wizard").unwrap();
+        assert_ne!(second.ballpark, team.ballpark);
+        assert!(second.roster.iter().all(|id| !team.roster.contains(id)));
+        let files = |sub: &str| fs::read_dir(folder.join(sub)).unwrap().count();
+        assert_eq!(files("players"), 2 * 19);
+        assert_eq!(files("ballparks"), 2);
+        assert_eq!(files("teams"), 2);
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_ballpark_editor() {
+        use crate::gui::gui_functions::BallparkEditor;
+
+        let folder = std::env::temp_dir().join(format!("deadball_park_{}", roll(100000)));
+        fs::create_dir_all(&folder).unwrap();
+        let mut editor = BallparkEditor {
+            name_override: true,
+            name: "Test Grounds".to_string(),
+            ..Default::default()
+        };
+        for era in [Era::Ancient, Era::Modern] {
+            editor.era = era.clone();
+            editor.generate(&[], &[]);
+            match (&editor.park, &era) {
+                (Some(Ballpark::Ancient(park)), Era::Ancient) => {
+                    assert_eq!(park.name, "Test Grounds ")
+                }
+                (Some(Ballpark::Modern(park)), Era::Modern) => {
+                    assert_eq!(park.name, "Test Grounds ")
+                }
+                _ => panic!("generated the wrong era"),
+            }
+
+            // manual and rolled quirks replace the placeholder, removing them all puts it back
+            editor.quirks_mut().unwrap().clear();
+            editor.remove_quirk(0);
+            assert_eq!(editor.quirks_mut().unwrap(), &vec![Quirks::None]);
+            editor.add_quirk(Some(Quirks::HighMound));
+            editor.add_quirk(None);
+            let quirks = editor.quirks_mut().unwrap().clone();
+            assert_eq!(quirks.len(), 2);
+            assert_eq!(quirks[0], Quirks::HighMound);
+            assert_ne!(quirks[1], Quirks::None);
+
+            // saved through the era's writer and loads back the same
+            editor.save_location = folder.join("park.dbb").to_string_lossy().to_string();
+            editor.save().unwrap();
+            editor.park = None;
+            editor.open().unwrap();
+            assert_eq!(editor.quirks_mut().unwrap(), &quirks);
+        }
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_player_editor() {
+        use crate::gui::gui_functions::PlayerEditor;
+
+        let mut editor = PlayerEditor::default();
+        assert!(editor
+            .open("src/testfiles/game/players/missing.dbp")
+            .is_err());
+        editor
+            .open("src/testfiles/game/players/Ab_Dow.dbp")
+            .unwrap();
+        let mut player = editor.player.clone().unwrap();
+        player.traits = vec![Traits::PowerHitter, Traits::SpeedyRunner];
+        player.batter_target = 30;
+        player.on_base_target = 38;
+        player.pitch_die = -8;
+        player.injury_location = vec![];
+        player.injury_severity = vec![];
+        assert_eq!(check_player(&player), Ok(()));
+
+        // conflicting traits, a target out of order and a pitch die that isn't a step
+        let mut bad = player.clone();
+        bad.traits.push(Traits::WeakHitter);
+        bad.traits.push(Traits::SlowRunner);
+        bad.on_base_target = 20;
+        bad.pitch_die = 6;
+        bad.injury_location.push(InjuryLocation::Knee);
+        let problems = check_player(&bad).unwrap_err();
+        assert_eq!(problems.len(), 5);
+        assert!(problems.contains(&"P+ and P- can't go together.".to_string()));
+        assert!(problems.contains(&"S+ and S- can't go together.".to_string()));
+        for die in PITCH_DICE {
+            assert_eq!(change_pitch_die(die, 0), die);
+        }
+
+        // preview follows the card, a better pitch die means fewer hits
+        editor.player = Some(player.clone());
+        let easy = editor.preview().unwrap();
+        editor.preview_die = 20;
+        let hard = editor.preview().unwrap();
+        assert!((easy.swings.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(hard.swings[2] < easy.swings[2]);
+        assert_eq!(easy.input.power_mod, 1);
+        // the batter's own D+ doesn't count at the plate
+        let mut fielder = player.clone();
+        fielder.traits.push(Traits::GreatDefender);
+        editor.player = Some(fielder);
+        assert_eq!(editor.preview().unwrap().input.defense_mod, 0);
+        editor.player = Some(player.clone());
+
+        // a bad card isn't written, a good one round trips
+        let folder = std::env::temp_dir().join(format!("deadball_player_{}", roll(100000)));
+        fs::create_dir_all(&folder).unwrap();
+        editor.file = folder.join("edited.dbp").to_string_lossy().to_string();
+        editor.player = Some(bad);
+        assert!(editor.save().is_err());
+        assert!(!Path::new(&editor.file).exists());
+        editor.player = Some(player.clone());
+        editor.save().unwrap();
+        let saved = load_player(fs::read_to_string(&editor.file).unwrap(), &editor.file).unwrap();
+        assert_eq!(saved.traits, player.traits);
+        assert_eq!(saved.on_base_target, 38);
+        fs::remove_dir_all(folder).unwrap();
     }
 
     // load_csv function test
@@ -946,6 +1427,44 @@ mod tests {
         assert!(result[3] == "test");
     }
 
+    #[test]
+    fn test_database_search() {
+        let folder = std::env::temp_dir().join(format!("deadball_databases_{}", roll(100000)));
+        let (override_dir, empty_dir) = (folder.join("override"), folder.join("empty"));
+        fs::create_dir_all(&override_dir).unwrap();
+        fs::create_dir_all(&empty_dir).unwrap();
+        fs::write(override_dir.join("mascot.csv"), "Owls\nBats\n\n").unwrap();
+        fs::write(empty_dir.join("logo.csv"), "\n").unwrap();
+        let search = vec![empty_dir.clone(), override_dir.clone()];
+
+        // first folder with the file wins
+        let (mascots, report) = load_database("mascot.csv", &search);
+        assert_eq!(mascots, vec!["Owls".to_string(), "Bats".to_string()]);
+        assert_eq!(
+            report.source,
+            DatabaseSource::File(override_dir.join("mascot.csv"))
+        );
+        // nothing on the search path, the built in copy matches the source tree
+        let (first_names, report) = load_database("firstname.csv", &search);
+        assert_eq!(report.source, DatabaseSource::Embedded);
+        assert_eq!(
+            first_names,
+            load_csv("src/databases/firstname.csv", "\n").unwrap()
+        );
+        // an override that's no good falls back to the built in copy and says why
+        let (logos, report) = load_database("logo.csv", &search);
+        assert_eq!(report.source, DatabaseSource::Embedded);
+        assert!(report.error.is_some());
+        assert!(!logos.is_empty());
+
+        let mut toasts = egui_toast::Toasts::new();
+        let databases = load_databases(&mut toasts, &search);
+        assert!(databases.status_park1 && databases.status_park2 && databases.status_mascot);
+        assert_eq!(databases.reports.len(), 9);
+        assert_eq!(databases.mascots.len(), 2);
+        fs::remove_dir_all(folder).unwrap();
+    }
+
     // generate player function test
     #[test]
     fn test_generate_player() {
@@ -973,8 +1492,8 @@ mod tests {
             filename.push_str(&i.to_string());
             filename.push_str(".dbp");
             _ = write_player(&test_player, &filename);
-            let contents = fs::read_to_string(filename).unwrap();
-            let read_player = load_player(contents);
+            let contents = fs::read_to_string(&filename).unwrap();
+            let read_player = load_player(contents, &filename).unwrap();
 
             let position = read_player.position;
             let handedness = read_player.handedness;
@@ -995,8 +1514,8 @@ mod tests {
     fn test_find_by_position() {
         let filename = "src/testfiles/game/teams/blue_team.dbt";
         let contents = fs::read_to_string(filename).unwrap();
-        let team = load_team(contents);
-        let (roster, _, _, _) = load_roster(&team);
+        let team = load_team(contents, filename).unwrap();
+        let (roster, _, _, _) = load_roster(&team, Path::new(DATA_LOCATION)).unwrap();
         let second_baseman = find_by_position(Position::Secondbase, &roster).unwrap();
         assert_eq!(second_baseman.batter_target, 26); // this was easier than actually comparing
                                                       // name strings or something, lol
@@ -1009,10 +1528,11 @@ mod tests {
             mode: true,
             rolls: vec![12, 15, 20],
             roll_index: 0,
+            ..Default::default()
         };
-        let result1 = debug_roll(&mut config, 10);
-        let result2 = debug_roll(&mut config, 10);
-        let result3 = debug_roll(&mut config, 10);
+        let result1 = debug_roll(&mut config, 10, "test");
+        let result2 = debug_roll(&mut config, 10, "test");
+        let result3 = debug_roll(&mut config, 10, "test");
         assert_eq!(result1, 12);
         assert_eq!(result2, 15);
         assert_eq!(result3, 20);
@@ -1061,6 +1581,7 @@ mod tests {
     #[test]
     fn test_trait_check() {
         let mut player1 = Player {
+            id: "".to_string(),
             first_name: "Seth".to_string(),
             nickname: "".to_string(),
             last_name: "Loveall".to_string(),
@@ -1124,6 +1645,38 @@ mod tests {
         assert_eq!(player2.control(), 3);
     }
 
+    #[test]
+    fn test_pow_trait_check() {
+        let mut game = test_game(false);
+        // the pitcher bats 9th, after his swing the order has already wrapped to the top
+        game.away_active.batting_order[0].traits = vec![Traits::None];
+        game.away_active.batting_order[8].traits = vec![Traits::ElitePowerHitter];
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.away_state.current_batter = 0;
+        assert_eq!(pow_trait_check(&game, &state), 2);
+    }
+
+    #[test]
+    fn test_oddity_batting_order() {
+        let game = test_game(true);
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Ongoing;
+        // the 9th hitter is up and the order has already wrapped, a rain delay sends him back
+        state.away_state.current_batter = 0;
+        let after = oddity(&mut DebugConfig::default(), &4, &1, &game, state.clone());
+        assert_eq!(after.away_state.current_batter, 8);
+        // catcher interference puts the leadoff man on first, the 2nd hitter is up next
+        state.away_state.current_batter = 1;
+        let after = oddity(&mut DebugConfig::default(), &20, &1, &game, state);
+        assert_eq!(after.away_state.current_batter, 1);
+    }
+
     // TODO: make test function names uniform
 
     #[test]
@@ -1150,14 +1703,7 @@ mod tests {
     #[test]
     fn test_process_steals() {
         // create GameState, GameModern, DebugConfig, Player
-        let red_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/red_team.dbt").unwrap());
-        let blue_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/blue_team.dbt").unwrap());
-        let ballpark = load_park_modern(
-            fs::read_to_string("src/testfiles/game/ballparks/Nightside Field.dbb").unwrap(),
-        );
-        let game = create_modern_game(red_team, blue_team, ballpark, false).unwrap();
+        let game = test_game(false);
         let mut state = init_new_game_state(
             game.home_active.pitching[0].clone(),
             game.away_active.pitching[0].clone(),
@@ -1166,84 +1712,129 @@ mod tests {
             mode: true,
             rolls: vec![3],
             roll_index: 0,
+            ..Default::default()
         };
         let mut stealer = game.home_active.batting_order[2].clone();
         let mut catcher = find_by_position(Position::Catcher, &game.away_active.roster).unwrap();
         stealer.traits = vec![Traits::SpeedyRunner];
         catcher.traits = vec![Traits::None];
         state.inning_half = InningTB::Bottom;
+        // away's line gets the next inning when the top half ends
+        state.away_state.runs.push(0);
+        state.away_state.hits.push(0);
+        state.away_state.errors.push(0);
         state.status = GameStatus::Ongoing;
-        state.runners = RunnersOn::Runner100;
-        state.runner1 = Some(stealer.clone());
+        state.bases.place(1, stealer.clone());
         state.home_state.current_batter = 3;
 
-        let mut new_state =
-            process_steals(StealType::Second, state.clone(), debug.clone(), &catcher);
+        let mut new_state = process_steals(
+            StealType::Second,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner010);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner010);
 
         stealer.traits = vec![Traits::SlowRunner];
-        state.runner1 = Some(stealer.clone());
+        state.bases.remove(1);
+        state.bases.place(1, stealer.clone());
         debug.rolls = vec![4];
-        new_state = process_steals(StealType::Second, state.clone(), debug.clone(), &catcher);
+        new_state = process_steals(
+            StealType::Second,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
 
         stealer.traits = vec![Traits::SpeedyRunner];
-        state.runner1 = None;
-        state.runner2 = Some(stealer.clone());
-        state.runners = RunnersOn::Runner010;
+        state.bases.clear();
+        state.bases.place(2, stealer.clone());
         debug.rolls = vec![4];
-        new_state = process_steals(StealType::Third, state.clone(), debug.clone(), &catcher);
+        new_state = process_steals(
+            StealType::Third,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner001);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner001);
 
         debug.rolls = vec![2];
         catcher.traits = vec![Traits::GreatDefender];
-        new_state = process_steals(StealType::Third, state.clone(), debug.clone(), &catcher);
+        new_state = process_steals(
+            StealType::Third,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
 
         debug.rolls = vec![8];
-        state.runners = RunnersOn::Runner001;
-        state.runner3 = state.runner2.clone();
-        state.runner2 = None;
-        new_state = process_steals(StealType::Home, state.clone(), debug.clone(), &catcher);
+        state.bases.move_runner(2, 3);
+        new_state =
+            process_steals(StealType::Home, state.clone(), &mut debug.clone(), &catcher).unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
         assert_eq!(new_state.home_state.runs[(state.inning - 1) as usize], 1);
 
         debug.rolls = vec![1];
-        state.runners = RunnersOn::Runner110;
-        state.runner2 = state.runner3.clone();
-        state.runner1 = state.runner2.clone();
-        state.runner3 = None;
-        new_state = process_steals(StealType::Double, state.clone(), debug.clone(), &catcher);
+        state.bases.move_runner(3, 2);
+        state
+            .bases
+            .place(1, game.home_active.batting_order[1].clone());
+        new_state = process_steals(
+            StealType::Double,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner010);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner010);
 
         debug.rolls = vec![4];
-        new_state = process_steals(StealType::Double, state.clone(), debug.clone(), &catcher);
+        new_state = process_steals(
+            StealType::Double,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner001);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner001);
 
         debug.rolls = vec![7];
-        new_state = process_steals(StealType::Double, state.clone(), debug.clone(), &catcher);
+        new_state = process_steals(
+            StealType::Double,
+            state.clone(),
+            &mut debug.clone(),
+            &catcher,
+        )
+        .unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner011);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner011);
+
+        // the lead runner has nowhere to go, and nobody is on third to steal home
+        state
+            .bases
+            .place(3, game.home_active.batting_order[0].clone());
+        assert!(process_steals(StealType::Double, state.clone(), &mut debug, &catcher).is_err());
+        state.bases.clear();
+        assert!(process_steals(StealType::Home, state, &mut debug, &catcher).is_err());
     }
 
     #[test]
     fn test_bunt() {
         // create GameState, GameModern, DebugConfig, Player
-        let red_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/red_team.dbt").unwrap());
-        let blue_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/blue_team.dbt").unwrap());
-        let ballpark = load_park_modern(
-            fs::read_to_string("src/testfiles/game/ballparks/Nightside Field.dbb").unwrap(),
-        );
-        let game = create_modern_game(red_team, blue_team, ballpark, false).unwrap();
+        let game = test_game(false);
         let mut state = init_new_game_state(
             game.home_active.pitching[0].clone(),
             game.away_active.pitching[0].clone(),
@@ -1252,63 +1843,60 @@ mod tests {
             mode: true,
             rolls: vec![1],
             roll_index: 0,
+            ..Default::default()
         };
         let mut batter = game.home_active.batting_order[3].clone();
         state.inning_half = InningTB::Bottom;
+        // away's line gets the next inning when the top half ends
+        state.away_state.runs.push(0);
+        state.away_state.hits.push(0);
+        state.away_state.errors.push(0);
         state.status = GameStatus::Ongoing;
-        state.runners = RunnersOn::Runner100;
-        state.runner1 = Some(game.home_active.batting_order[2].clone());
+        state
+            .bases
+            .place(1, game.home_active.batting_order[2].clone());
         state.home_state.current_batter = 3;
         batter.traits = vec![Traits::ContactHitter];
 
         // bunt_result = 2
-        let mut new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        let mut new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner100);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner100);
 
         // bunt_result = 3
         batter.traits = vec![Traits::FreeSwinger];
         debug.rolls = vec![4];
-        new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner010);
-        state.runners = RunnersOn::Runner001;
-        state.runner3 = state.runner1.clone();
-        state.runner1 = None;
-        new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner010);
+        state.bases.move_runner(1, 3);
+        new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner100);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner100);
 
         // bunt_result = 4/5
         batter.traits = vec![Traits::None];
         debug.rolls = vec![5];
-        new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
 
         // bunt_result = 6
         debug.rolls = vec![6];
-        new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
         debug.rolls = vec![6, 4];
         batter.traits = vec![Traits::SpeedyRunner];
-        new_state = bunt(state.clone(), &game, debug.clone(), batter.clone());
+        new_state = bunt(state.clone(), &game, &mut debug.clone(), batter.clone());
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner100);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner100);
     }
 
     #[test]
     fn test_hit_and_run() {
         // create GameState, GameModern, DebugConfig, Player
-        let red_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/red_team.dbt").unwrap());
-        let blue_team =
-            load_team(fs::read_to_string("src/testfiles/game/teams/blue_team.dbt").unwrap());
-        let ballpark = load_park_modern(
-            fs::read_to_string("src/testfiles/game/ballparks/Nightside Field.dbb").unwrap(),
-        );
-        let game = create_modern_game(red_team, blue_team, ballpark, false).unwrap();
+        let game = test_game(false);
         let mut state = init_new_game_state(
             game.home_active.pitching[0].clone(),
             game.away_active.pitching[0].clone(),
@@ -1317,6 +1905,7 @@ mod tests {
             mode: true,
             rolls: vec![8, 1, 37],
             roll_index: 0,
+            ..Default::default()
         };
         let mut stealer = game.home_active.batting_order[2].clone();
         let mut batter = game.home_active.batting_order[3].clone();
@@ -1325,40 +1914,50 @@ mod tests {
         batter.traits = vec![Traits::ContactHitter];
         stealer.traits = vec![Traits::SpeedyRunner];
         state.inning_half = InningTB::Bottom;
+        // away's line gets the next inning when the top half ends
+        state.away_state.runs.push(0);
+        state.away_state.hits.push(0);
+        state.away_state.errors.push(0);
         state.status = GameStatus::Ongoing;
-        state.runners = RunnersOn::Runner100;
-        state.runner1 = Some(stealer.clone());
+        state.bases.place(1, stealer.clone());
         state.home_state.current_batter = 3;
 
-        let mut new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        let mut new_state =
+            hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner101);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner101);
 
         batter.traits = vec![Traits::FreeSwinger];
         debug.rolls = vec![1, 1, 10];
-        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::None);
-        assert_eq!(new_state.runners, RunnersOn::Runner110);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner110);
 
         debug.rolls = vec![8, 1, 70];
-        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner100);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner100);
 
         debug.rolls = vec![1, 1, 37];
-        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::Two);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
 
         debug.rolls = vec![8, 4, 70];
-        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::One);
-        assert_eq!(new_state.runners, RunnersOn::Runner010);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner010);
 
         debug.rolls = vec![1, 4, 70];
-        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone());
+        new_state = hit_and_run(state.clone(), &game, &mut debug.clone(), batter.clone()).unwrap();
         assert_eq!(new_state.outs, Outs::Two);
-        assert_eq!(new_state.runners, RunnersOn::Runner000);
+        assert_eq!(new_state.bases.occupancy(), RunnersOn::Runner000);
+
+        // the runner can't go with second taken
+        state
+            .bases
+            .place(2, game.home_active.batting_order[1].clone());
+        assert!(hit_and_run(state, &game, &mut debug, batter).is_err());
     }
 
     #[test]
@@ -1380,4 +1979,1283 @@ mod tests {
         pd = change_pitch_die(pd, 1);
         assert_eq!(pd, -8);
     }
+
+    #[test]
+    fn test_stats() {
+        let game = test_game(false);
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Ongoing;
+        let batter = game.away_active.batting_order[0].clone();
+        let pitcher = player_key(&game.home_active.pitching[0]);
+
+        // solo home run
+        let mut after = state.clone();
+        after.away_state.hits[0] += 1;
+        after.away_state.runs[0] += 1;
+        state = record_plate_appearance(&state, after, &batter, &AtBatResults::Hit, &20, 4);
+        // strikeout
+        let mut after = state.clone();
+        after.outs = Outs::One;
+        state = record_plate_appearance(&state, after, &batter, &AtBatResults::Out, &31, 0);
+        // walk
+        let mut after = state.clone();
+        after.bases.place(1, batter.clone());
+        state = record_plate_appearance(&state, after, &batter, &AtBatResults::Walk, &50, 0);
+
+        let line = &state.box_score.batting[&player_key(&batter)];
+        assert_eq!(line.plate_appearances, 3);
+        assert_eq!(line.at_bats, 2);
+        assert_eq!(line.hits, 1);
+        assert_eq!(line.home_runs, 1);
+        assert_eq!(line.walks, 1);
+        assert_eq!(line.strikeouts, 1);
+        assert_eq!(line.rbi, 1);
+        assert_eq!(line.average(), 0.5);
+        assert_eq!(line.slugging(), 2.0);
+        let pitching = &state.box_score.pitching[&pitcher];
+        assert_eq!(pitching.outs, 1);
+        assert_eq!(pitching.runs, 1);
+        assert_eq!(pitching.home_runs, 1);
+        assert_eq!(pitching.strikeouts, 1);
+        assert_eq!(pitching.era(), 27.0);
+
+        // a single that ends up on 2nd after a throwing error is still a single
+        let mut after = state.clone();
+        after.away_state.hits[0] += 1;
+        after.bases.place(2, batter.clone());
+        let single = record_plate_appearance(&state, after, &batter, &AtBatResults::Hit, &20, 1);
+        let line = &single.box_score.batting[&player_key(&batter)];
+        assert_eq!(line.hits, 2);
+        assert_eq!(line.doubles, 0);
+
+        let mut db = StatsDatabase::default();
+        db.record_game(1, &game, &state);
+        db.record_game(2, &game, &state);
+        let leaders = db.leaderboard(LeaderStat::HomeRuns, Some(1), 5);
+        assert_eq!(leaders[0], (player_key(&batter), 1.0));
+        let leaders = db.leaderboard(LeaderStat::HomeRuns, None, 5);
+        assert_eq!(leaders[0], (player_key(&batter), 2.0));
+        let (seasons, total) = db.career(&player_key(&batter));
+        assert_eq!(seasons.len(), 2);
+        assert_eq!(total.batting.games, 2);
+        assert_eq!(db.teams[&(game.away.name.clone(), 1)].wins, 1);
+        // stats follow the player's ID, a namesake doesn't share their line
+        assert_eq!(player_key(&batter), batter.id);
+        let name = format!("{} {}", batter.first_name, batter.last_name);
+        assert_eq!(db.name(&batter.id), name);
+        let mut namesake = batter.clone();
+        namesake.id = "Namesake_0001".to_string();
+        assert_eq!(db.career(&player_key(&namesake)).0.len(), 0);
+
+        // commas and quotes in names survive the round trip
+        db.names
+            .insert(batter.id.clone(), "Smith, \"Smitty\" Jr.".to_string());
+        let team_line = db.teams[&(game.away.name.clone(), 1)].clone();
+        db.teams
+            .insert(("Washington, D.C.".to_string(), 1), team_line);
+        db.starts
+            .insert(("Washington, D.C.".to_string(), 1, batter.id.clone()), 3);
+
+        let filename = "src/testfiles/write_stats_test.dbs";
+        write_stats(&db, filename).unwrap();
+        let read_db = load_stats(filename).unwrap();
+        assert_eq!(read_db.players, db.players);
+        assert_eq!(read_db.teams, db.teams);
+        assert_eq!(read_db.names, db.names);
+        assert_eq!(read_db.starts, db.starts);
+        assert_eq!(read_db.name(&batter.id), "Smith, \"Smitty\" Jr.");
+
+        // more extra base hits than hits can only come from a bad file, but shouldn't panic
+        let corrupt = BattingLine {
+            at_bats: 4,
+            hits: 1,
+            home_runs: 2,
+            ..Default::default()
+        };
+        assert_eq!(corrupt.slugging(), 2.0);
+    }
+
+    #[test]
+    fn test_line_score() {
+        use crate::gui::scoreboard::line_score;
+
+        let pitcher = generate_player(
+            PlayerClass::Pitchers,
+            Position::Pitcher,
+            &["Line".to_string()],
+            &["Score".to_string()],
+        );
+        let mut state = init_new_game_state(pitcher.clone(), pitcher);
+        let [away, home] = line_score(&state);
+        assert_eq!(away.innings, vec![None; 9]);
+        assert_eq!(home.innings, vec![None; 9]);
+
+        // top of the 2nd, the home team hasn't come up yet this inning
+        state.status = GameStatus::Ongoing;
+        state.inning = 2;
+        state.away_state.runs = vec![1, 2];
+        state.away_state.hits = vec![2, 3];
+        state.home_state.runs = vec![0, 0];
+        state.home_state.errors = vec![1, 0];
+        let [away, home] = line_score(&state);
+        assert_eq!(&away.innings[..3], &[Some(1), Some(2), None]);
+        assert_eq!(&home.innings[..2], &[Some(0), None]);
+        assert_eq!((away.runs, away.hits, away.errors), (3, 5, 0));
+        assert_eq!((home.runs, home.hits, home.errors), (0, 0, 1));
+
+        // extra innings add columns, a finished game shows every half inning
+        state.inning = 11;
+        state.inning_half = InningTB::Bottom;
+        state.away_state.runs = vec![0; 11];
+        state.home_state.runs = vec![0; 11];
+        state.home_state.runs[10] = 1;
+        let [away, home] = line_score(&state);
+        assert_eq!(away.innings.len(), 11);
+        assert_eq!(home.innings[10], Some(1));
+        state.status = GameStatus::Over;
+        state.inning_half = InningTB::Top;
+        state.home_state.runs.push(0);
+        let [_, home] = line_score(&state);
+        assert_eq!(home.innings.len(), 11);
+        assert_eq!(home.innings[10], Some(1));
+        assert_eq!(home.runs, 1);
+    }
+
+    #[test]
+    fn test_play_animation() {
+        use crate::core::retrosheet::{record_play, PlayKind};
+        use crate::gui::diamond::*;
+
+        let names = |last: &str| {
+            generate_player(
+                PlayerClass::StartingHitter,
+                Position::Leftfield,
+                &["Diamond".to_string()],
+                &[last.to_string()],
+            )
+        };
+        let (first, second, batter) = (names("First"), names("Second"), names("Batter"));
+        let pitcher = generate_player(
+            PlayerClass::Pitchers,
+            Position::Pitcher,
+            &["Diamond".to_string()],
+            &["Pitcher".to_string()],
+        );
+        let mut state = init_new_game_state(pitcher.clone(), pitcher);
+        state.status = GameStatus::Ongoing;
+        state.bases.place(1, first.clone());
+        state.bases.place(2, second.clone());
+
+        // single to left, runner on 2nd scores and the runner on 1st goes to 3rd
+        let mut after = state.clone();
+        after.away_state.hits[0] += 1;
+        after.away_state.runs[0] += 1;
+        after.bases.clear();
+        after.bases.place(1, batter.clone());
+        after.bases.place(3, first.clone());
+        let kind = PlayKind::AtBat(AtBatResults::Hit);
+        let after = record_play(&state, after, kind, Some(&batter), &47);
+        let play = after.plays.last().unwrap();
+        let moves = runner_moves(play);
+        assert_eq!(moves.len(), 3);
+        assert_eq!((moves[0].from, &moves[0].end), (2, &RunnerEnd::Scored));
+        assert_eq!((moves[1].from, &moves[1].end), (1, &RunnerEnd::Base(3)));
+        assert_eq!((moves[2].from, &moves[2].end), (0, &RunnerEnd::Base(1)));
+        assert_eq!(play_fielder(play), Some(Position::Leftfield));
+
+        // runners follow the baselines, halfway from 1st to 3rd is standing on 2nd
+        assert_eq!(moves[1].position(0.0), BASE_SPOTS[1]);
+        assert_eq!(moves[1].position(0.5), BASE_SPOTS[2]);
+        assert_eq!(moves[1].position(1.0), BASE_SPOTS[3]);
+        assert_eq!(moves[0].position(1.0), BASE_SPOTS[0]);
+
+        let mut animation = PlayAnimation::default();
+        animation.update(&after, 10.0);
+        assert_eq!(animation.fielder, Some(Position::Leftfield));
+        assert!(!animation.error);
+        assert!(animation.is_running(10.5));
+        assert!(!animation.is_running(10.0 + ANIMATION_SECONDS));
+        // nothing new, the play keeps its start time
+        animation.update(&after, 20.0);
+        assert_eq!(animation.started, 10.0);
+        // undoing back to before the play clears the diamond
+        animation.update(&state, 30.0);
+        assert!(animation.moves.is_empty());
+        assert_eq!(animation.fielder, None);
+
+        // runner thrown out on a possible error that wasn't, pitcher's chance goes to short
+        let mut after = state.clone();
+        after.outs = Outs::One;
+        after.bases.remove(2);
+        after.bases.move_runner(1, 2);
+        after.bases.place(1, batter.clone());
+        let kind = PlayKind::AtBat(AtBatResults::PossibleError);
+        let after = record_play(&state, after, kind, Some(&batter), &51);
+        let moves = runner_moves(after.plays.last().unwrap());
+        assert_eq!(moves[0].end, RunnerEnd::Out);
+        assert_eq!(moves[0].position(0.5), BASE_SPOTS[2]);
+        animation.update(&after, 40.0);
+        assert_eq!(animation.fielder, Some(Position::Shortstop));
+    }
+
+    #[test]
+    fn test_lineup_summary() {
+        use crate::core::stats::{BattingLine, PitchingLine};
+        use crate::gui::lineup::{batting_summary, pitching_summary};
+
+        assert_eq!(batting_summary(&BattingLine::default()), "");
+        let line = BattingLine {
+            plate_appearances: 5,
+            at_bats: 4,
+            hits: 2,
+            home_runs: 1,
+            rbi: 3,
+            walks: 1,
+            strikeouts: 2,
+            ..Default::default()
+        };
+        assert_eq!(batting_summary(&line), "2-4, HR, 3 RBI, BB, 2 K");
+        let line = PitchingLine {
+            outs: 16,
+            hits: 4,
+            runs: 2,
+            walks: 1,
+            strikeouts: 6,
+            ..Default::default()
+        };
+        assert_eq!(pitching_summary(&line), "5.1 IP, 4 H, 2 R, 1 BB, 6 K");
+    }
+
+    #[test]
+    fn test_settings() {
+        use crate::core::settings::*;
+
+        // recent files, newest first with no repeats
+        let mut recent = vec![];
+        for i in 0..10 {
+            add_recent(&mut recent, &format!("team{}.dbt", i));
+        }
+        add_recent(&mut recent, "team5.dbt");
+        assert_eq!(recent.len(), RECENT_FILES);
+        assert_eq!(recent[0], "team5.dbt");
+        assert_eq!(recent[1], "team9.dbt");
+        assert_eq!(recent.iter().filter(|f| *f == "team5.dbt").count(), 1);
+
+        let folder = std::env::temp_dir().join(format!("deadball_settings_{}", roll(100000)));
+        let path = folder.join(SETTINGS_FILE);
+        let settings = Settings {
+            data_dir: "leagues/test/".to_string(),
+            default_era: Era::Modern,
+            oddity: true,
+            toast_seconds: 5.5,
+            theme: Theme::Dark,
+            window_pos: Some([10.0, 20.0]),
+            recent_teams: recent,
+            ..Default::default()
+        };
+        write_settings(&settings, &path).unwrap();
+        assert_eq!(load_settings(&path).unwrap(), settings);
+        // compared as paths, the separator is different on Windows
+        let league = Path::new("leagues").join("test");
+        assert_eq!(Path::new(&settings.stats_file()), league.join("stats.dbs"));
+        // with or without the trailing slash
+        let settings = Settings {
+            data_dir: "leagues/test".to_string(),
+            ..settings
+        };
+        assert_eq!(Path::new(&settings.stats_file()), league.join("stats.dbs"));
+        assert_eq!(Path::new(&settings.record_dir()), league.join("records"));
+        let players = crate::gui::gui_functions::data_folder("leagues/test", "players");
+        assert_eq!(Path::new(&players), league.join("players"));
+        assert!(players.ends_with(std::path::MAIN_SEPARATOR));
+
+        // older files without every field still load
+        fs::write(&path, "oddity = true\n").unwrap();
+        let partial = load_settings(&path).unwrap();
+        assert!(partial.oddity);
+        assert_eq!(partial.data_dir, DATA_LOCATION);
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_rotation() {
+        let mut game = test_game(false);
+        // five man rotation out of the bullpen
+        let relievers: Vec<Player> = game.home_active.bullpen[..4].to_vec();
+        game.home_active.pitching.extend(relievers);
+        let rotation = game.home_active.pitching.clone();
+        let team = game.home.name.clone();
+
+        // the starter takes the pitcher's spot in the batting order
+        let mut active = game.home_active.clone();
+        active.set_starter(2);
+        assert_eq!(active.pitching[0], rotation[2]);
+        assert_eq!(active.pitching[2], rotation[0]);
+        assert!(active.batting_order.contains(&rotation[2]));
+        assert!(!active.batting_order.contains(&rotation[0]));
+
+        // a season of games cycles through the rotation in order
+        let mut db = StatsDatabase::default();
+        assert_eq!(db.next_starter(&team, 1, &rotation), 0);
+        for game_num in 0..7 {
+            let next = db.next_starter(&team, 1, &rotation);
+            assert_eq!(next, game_num % 5);
+            assert!(db.is_rested(&team, 1, &rotation[next]));
+            let mut home = game.home_active.clone();
+            home.set_starter(next);
+            let state = init_new_game_state(
+                home.pitching[0].clone(),
+                game.away_active.pitching[0].clone(),
+            );
+            db.record_game(1, &game, &state);
+        }
+        // starts went 0, 1, 2, 3, 4, 0, 1
+        assert_eq!(db.games_rested(&team, 1, &rotation[0]), Some(1));
+        assert_eq!(db.games_rested(&team, 1, &rotation[1]), Some(0));
+        assert_eq!(db.games_rested(&team, 1, &rotation[2]), Some(4));
+        assert!(!db.is_rested(&team, 1, &rotation[1]));
+        assert_eq!(db.games_rested(&team, 2, &rotation[1]), None);
+
+        // nobody rested, the most rested pitcher goes
+        assert_eq!(db.next_starter(&team, 1, &rotation[..2]), 0);
+
+        let filename = "src/testfiles/write_rotation_test.dbs";
+        write_stats(&db, filename).unwrap();
+        let read_db = load_stats(filename).unwrap();
+        assert_eq!(read_db.starts, db.starts);
+        fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_player_ids() {
+        let data_dir = Path::new(DATA_LOCATION);
+        // IDs resolve to files in the league folder
+        assert_eq!(
+            resolve_reference("Ab_Dow", data_dir, PLAYER_FOLDER, "dbp"),
+            data_dir.join("players").join("Ab_Dow.dbp")
+        );
+        assert_eq!(
+            resolve_reference("James_St. Claire", data_dir, PLAYER_FOLDER, "dbp"),
+            data_dir.join("players").join("James_St. Claire.dbp")
+        );
+        // old style paths still work, even if the league folder moved
+        assert_eq!(
+            resolve_reference(
+                "src/testfiles/game/players/Ab_Dow.dbp",
+                data_dir,
+                PLAYER_FOLDER,
+                "dbp"
+            ),
+            Path::new("src/testfiles/game/players/Ab_Dow.dbp")
+        );
+        assert_eq!(
+            resolve_reference(
+                "/home/seth/Deadball-Game/src/testfiles/railyard.dbb",
+                Path::new("src/testfiles"),
+                "",
+                "dbb"
+            ),
+            Path::new("src/testfiles").join("railyard.dbb")
+        );
+
+        // players without an ID line get their file name
+        let team = load_team(
+            fs::read_to_string("src/testfiles/game/teams/red_team.dbt").unwrap(),
+            "src/testfiles/game/teams/red_team.dbt",
+        )
+        .unwrap();
+        assert_eq!(team.id, "Red_Team");
+        let (roster, bench, pitcher, bullpen) = load_roster(&team, data_dir).unwrap();
+        assert_eq!(roster[0].id, "Rich_Cameron");
+        assert_eq!(roster.len(), 8);
+        assert_eq!(bench.len(), 4);
+        assert_eq!(pitcher.len(), 1);
+        assert_eq!(bullpen.len(), 5);
+
+        // missing players are an error instead of being dropped
+        let mut broken = team.clone();
+        broken.bench.push("Nobody_Here".to_string());
+        assert!(load_roster(&broken, data_dir).is_err());
+
+        let id = generate_id("Seth Loveall");
+        assert!(id.starts_with("Seth_Loveall_"));
+        // IDs don't use the game dice
+        seed_rolls(27);
+        let first = roll(100);
+        seed_rolls(27);
+        generate_id("Seth Loveall");
+        assert_eq!(roll(100), first);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // no trailing newline is fine, values aren't case sensitive
+        let player = load_player(
+            "First Name: Ab\nPosition: cf\nTraits: None, S+,\nPitch Die: -8".to_string(),
+            "inline.dbp",
+        )
+        .unwrap();
+        assert_eq!(player.position, Position::Centerfield);
+        assert_eq!(player.traits, vec![Traits::None, Traits::SpeedyRunner]);
+        assert_eq!(player.pitch_die, -8);
+
+        // typos are reported with the line, field and value
+        let err =
+            load_player("First Name: Ab\nPosition: XF\n".to_string(), "typo.dbp").unwrap_err();
+        assert_eq!(err.file, "typo.dbp");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.field, "Position");
+        assert_eq!(err.value, "XF");
+        assert_eq!(
+            format!("{}", err),
+            "typo.dbp line 2: unrecognized value for Position: 'XF'"
+        );
+        let err = load_player("Batter Target: 3O\n".to_string(), "num.dbp").unwrap_err();
+        assert_eq!((err.line, err.value.as_str()), (1, "3O"));
+        let err = load_player("Traits: P+, Q+\n".to_string(), "trait.dbp").unwrap_err();
+        assert_eq!(err.value, "Q+");
+        let err = load_player("Batter Targt: 30\n".to_string(), "key.dbp").unwrap_err();
+        assert_eq!(err.message, "unknown field");
+        let err = load_player("Seth Loveall\n".to_string(), "colon.dbp").unwrap_err();
+        assert_eq!(err.line, 1);
+
+        let err = load_team("TEAM: Test\n\n## INFO\nFANBASE: Loyl".to_string(), "t.dbt")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.field.as_str()), (4, "FANBASE"));
+        let err = load_park_modern("ROOF: Sunroof".to_string(), "p.dbb")
+            .err()
+            .unwrap();
+        assert_eq!(err.field, "ROOF");
+        let err = load_park_ancient("CAPACITY: lots".to_string(), "p.dbb")
+            .err()
+            .unwrap();
+        assert_eq!(err.value, "lots");
+
+        // files written by older versions still load
+        let team = load_team(
+            "Location: Small Town\nTEAM PRIORITY: StartingPitching\nFANBASE: Loyal/n/n## MANAGER INFO"
+                .to_string(),
+            "old.dbt",
+        )
+        .unwrap();
+        assert!(matches!(team.location, Location::SmallTown));
+        assert!(matches!(team.fanbase, Fanbase::Loyal));
+
+        // every file in the sample league parses
+        for folder in [PLAYER_FOLDER, TEAM_FOLDER, BALLPARK_FOLDER] {
+            for entry in fs::read_dir(Path::new(DATA_LOCATION).join(folder)).unwrap() {
+                let path = entry.unwrap().path();
+                let file = path.display().to_string();
+                let contents = fs::read_to_string(&path).unwrap();
+                match folder {
+                    PLAYER_FOLDER => _ = load_player(contents, &file).unwrap(),
+                    TEAM_FOLDER => _ = load_team(contents, &file).unwrap(),
+                    _ => _ = load_park_modern(contents, &file).unwrap(),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_data_formats() {
+        let data_dir = Path::new(DATA_LOCATION);
+        let player = load_player_ref("Ab_Dow", data_dir).unwrap();
+        let team = load_team(
+            fs::read_to_string("src/testfiles/game/teams/red_team.dbt").unwrap(),
+            "red_team.dbt",
+        )
+        .unwrap();
+        let park_file = "src/testfiles/game/ballparks/Nightside Field.dbb";
+        let park = load_park_modern(fs::read_to_string(park_file).unwrap(), park_file).unwrap();
+
+        // same data comes back out of both formats
+        for extension in ["json", "toml"] {
+            let filename = format!("src/testfiles/write_test.{}", extension);
+            write_player(&player, &filename).unwrap();
+            let contents = fs::read_to_string(&filename).unwrap();
+            assert_eq!(load_player(contents, &filename).unwrap(), player);
+
+            let filename = format!("src/testfiles/write_team_test.{}", extension);
+            write_team(team.clone(), &filename).unwrap();
+            let read_team = load_team(fs::read_to_string(&filename).unwrap(), &filename).unwrap();
+            assert_eq!(read_team.id, team.id);
+            assert_eq!(read_team.roster, team.roster);
+            assert!(matches!(read_team.priority, Priority::Average));
+
+            let filename = format!("src/testfiles/write_park_test.{}", extension);
+            write_ballpark_modern(&park, &filename).unwrap();
+            let read_park =
+                load_park_modern(fs::read_to_string(&filename).unwrap(), &filename).unwrap();
+            assert_eq!(read_park.name, park.name);
+            assert_eq!(read_park.capacity, park.capacity);
+        }
+        assert_eq!(data_format("players/Ab_Dow.dbp"), DataFormat::Legacy);
+        assert_eq!(data_format("Ab_Dow.JSON"), DataFormat::Json);
+
+        // IDs find JSON/TOML players in the league folder too
+        let league = std::env::temp_dir().join("deadball_formats_test");
+        fs::create_dir_all(league.join(PLAYER_FOLDER)).unwrap();
+        let toml_file = league.join(PLAYER_FOLDER).join("Ab_Dow.toml");
+        write_player(&player, toml_file.to_str().unwrap()).unwrap();
+        assert_eq!(load_player_ref("Ab_Dow", &league).unwrap(), player);
+
+        // errors still point at the line
+        let err = load_player(
+            "{\n  \"id\": \"Ab_Dow\",\n  \"position\": \"Centre\"\n}".to_string(),
+            "bad.json",
+        )
+        .unwrap_err();
+        assert_eq!(err.line, 3);
+        let err =
+            load_player("id = \"Ab_Dow\"\nposition = 5\n".to_string(), "bad.toml").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_league_check_and_convert() {
+        use crate::core::league::*;
+
+        // work on a copy of the sample league
+        let league = std::env::temp_dir().join("deadball_league_test");
+        _ = fs::remove_dir_all(&league);
+        for folder in [PLAYER_FOLDER, TEAM_FOLDER, BALLPARK_FOLDER] {
+            fs::create_dir_all(league.join(folder)).unwrap();
+            for entry in fs::read_dir(Path::new(DATA_LOCATION).join(folder)).unwrap() {
+                let path = entry.unwrap().path();
+                fs::copy(&path, league.join(folder).join(path.file_name().unwrap())).unwrap();
+            }
+        }
+        let report = validate_league(&league);
+        assert_eq!((report.players, report.teams, report.ballparks), (36, 2, 2));
+        assert!(report.issues.is_empty());
+
+        // old style path to a moved player, a broken one, a shared player and a bad file
+        let red = fs::read_to_string(league.join("teams/red_team.dbt")).unwrap();
+        let broken = red
+            .replace("ID: Red_Team", "ID: Broken_Team")
+            .replace("TEAM: Red Team", "TEAM: Broken Team")
+            .replace(
+                "PLAYER: Rich_Cameron",
+                "PLAYER: /home/seth/players/Rich_Cameron.dbp",
+            )
+            .replace("PLAYER: Bill_Reid\n", "")
+            .replace("PITCHER: Jack_Tate", "PITCHER: Nobody");
+        fs::write(league.join("teams/broken_team.dbt"), broken).unwrap();
+        fs::write(league.join("players/Bad_Player.dbp"), "Position: XF\n").unwrap();
+        let report = validate_league(&league);
+        assert_eq!(report.count(IssueKind::Parse), 1);
+        assert_eq!(report.count(IssueKind::BrokenReference), 1);
+        assert_eq!(report.count(IssueKind::Roster), 1); // 7 fielders
+
+        // everyone on red team except Bill Reid and Jack Tate
+        assert_eq!(report.count(IssueKind::DuplicatePlayer), 16);
+
+        // converting to JSON replaces the files and keeps references working
+        let report = convert_league(&league, DataFormat::Json);
+        assert_eq!(report.converted, 36 + 3 + 2);
+        assert!(league.join("players/Ab_Dow.json").exists());
+        assert!(!league.join("players/Ab_Dow.dbp").exists());
+        let converted = fs::read_to_string(league.join("teams/broken_team.json")).unwrap();
+        assert!(converted.contains("\"Rich_Cameron\""));
+        let report = validate_league(&league);
+        assert_eq!((report.players, report.teams, report.ballparks), (36, 3, 2));
+        assert_eq!(report.count(IssueKind::BrokenReference), 1);
+
+        // and back again
+        let report = convert_league(&league, DataFormat::Legacy);
+        assert_eq!(report.converted, 36 + 3 + 2);
+        assert!(league.join("players/Ab_Dow.dbp").exists());
+        let red = load_team(
+            fs::read_to_string(league.join("teams/red_team.dbt")).unwrap(),
+            "red_team.dbt",
+        )
+        .unwrap();
+        assert_eq!(load_roster(&red, &league).unwrap().0.len(), 8);
+    }
+
+    #[test]
+    fn test_lahman_import() {
+        use crate::core::lahman::*;
+        use crate::core::league::*;
+
+        assert_eq!(pitch_die_from_era(1.95), 20);
+        assert_eq!(pitch_die_from_era(2.63), 12);
+        assert_eq!(pitch_die_from_era(3.25), 8);
+        assert_eq!(pitch_die_from_era(3.99), 4);
+        assert_eq!(pitch_die_from_era(5.10), -4);
+
+        let import = import_lahman(Path::new("src/testfiles/lahman"), 1927).unwrap();
+        assert_eq!(import.teams.len(), 1);
+        assert_eq!(import.players.len(), 17); // Ruth's 1928 line is skipped
+        assert_eq!(import.skipped, ["nobodyjo01"]); // not in People.csv
+        let team = &import.teams[0];
+        assert_eq!(team.id, "NYA_1927");
+        assert_eq!(team.era, Era::Modern);
+        assert_eq!(season_era(1894), Era::Ancient);
+        assert_eq!(
+            team.roster,
+            [
+                "combsea01",
+                "gehrilo01",
+                "lazzeto01",
+                "ruthba01",
+                "koenima01",
+                "meusebo01",
+                "duganjo01",
+                "collipa01"
+            ]
+            .map(|id| format!("{}_1927", id))
+        );
+        assert_eq!(team.bench.len(), 2);
+        // five starters with the most starts, the rest pitch in relief
+        assert_eq!(
+            team.pitcher,
+            [
+                "hoytwa01",
+                "shockur01",
+                "pennohe01",
+                "ruethdu01",
+                "moorewi01"
+            ]
+            .map(|id| format!("{}_1927", id))
+        );
+        assert_eq!(team.bullpen, ["thomamy01_1927", "giardjo01_1927"]);
+
+        let player = |id: &str| {
+            import
+                .players
+                .iter()
+                .find(|player| player.id == format!("{}_1927", id))
+                .unwrap()
+        };
+        let ruth = player("ruthba01");
+        assert_eq!((ruth.batter_target, ruth.on_base_target), (36, 49));
+        assert_eq!(ruth.position, Position::Leftfield); // listed as OF
+        assert_eq!(ruth.handedness, Handedness::Left);
+        assert!(ruth.traits.contains(&Traits::ElitePowerHitter));
+        let hoyt = player("hoytwa01");
+        assert_eq!(hoyt.position, Position::Pitcher);
+        assert_eq!(hoyt.pitch_die, 12);
+
+        // every team gets a placeholder park, so the written league checks out clean
+        assert_eq!(team.ballpark, "NYA_1927_park");
+        assert_eq!(import.ballparks[0].id(), team.ballpark);
+        let league = std::env::temp_dir().join("deadball_lahman_test");
+        _ = fs::remove_dir_all(&league);
+        write_import(&import, &league).unwrap();
+        let report = validate_league(&league);
+        assert_eq!((report.players, report.teams, report.ballparks), (17, 1, 1));
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_retrosheet_export() {
+        use crate::core::retrosheet::*;
+
+        let game = test_game(false);
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Ongoing;
+        let away = &game.away_active.batting_order;
+
+        // single
+        let mut after = state.clone();
+        after.away_state.current_batter = 1;
+        after.away_state.hits[0] += 1;
+        after.bases.place(1, away[0].clone());
+        let kind = PlayKind::AtBat(AtBatResults::Hit);
+        state = record_play(&state, after, kind, Some(&away[0]), &45);
+        // two run homer
+        let mut after = state.clone();
+        after.away_state.current_batter = 2;
+        after.away_state.hits[0] += 1;
+        after.away_state.runs[0] += 2;
+        after.bases.clear();
+        let kind = PlayKind::AtBat(AtBatResults::CriticalHit);
+        state = record_play(&state, after, kind, Some(&away[1]), &20);
+        // walk, steal, then a fly ball that moves the runner up
+        let mut after = state.clone();
+        after.away_state.current_batter = 3;
+        after.bases.place(1, away[2].clone());
+        let kind = PlayKind::AtBat(AtBatResults::Walk);
+        state = record_play(&state, after, kind, Some(&away[2]), &50);
+        let mut after = state.clone();
+        after.bases.move_runner(1, 2);
+        state = record_play(&state, after, PlayKind::Steal, None, &0);
+        let mut after = state.clone();
+        after.away_state.current_batter = 4;
+        after.outs = Outs::One;
+        after.bases.move_runner(2, 3);
+        let kind = PlayKind::AtBat(AtBatResults::ProductiveOut1);
+        state = record_play(&state, after, kind, Some(&away[3]), &48);
+        // strikeout, then the runner on 3rd is thrown out at home on a fielder's choice
+        let mut after = state.clone();
+        after.away_state.current_batter = 5;
+        after.outs = Outs::Two;
+        let kind = PlayKind::AtBat(AtBatResults::Out);
+        state = record_play(&state, after, kind, Some(&away[4]), &31);
+        let mut after = state.clone();
+        after.away_state.current_batter = 6;
+        after.outs = Outs::Three;
+        after.bases.remove(3);
+        after.bases.place(1, away[5].clone());
+        let kind = PlayKind::AtBat(AtBatResults::Out);
+        state = record_play(&state, after, kind, Some(&away[5]), &66);
+
+        // new pitcher, then the leadoff man reaches on an error
+        state.inning_half = InningTB::Bottom;
+        state.outs = Outs::None;
+        state.bases.clear();
+        state.away_state.current_pitcher = game.away_active.bullpen[0].clone();
+        let home = &game.home_active.batting_order;
+        let mut after = state.clone();
+        after.home_state.current_batter = 1;
+        after.away_state.errors[0] += 1;
+        after.bases.place(1, home[0].clone());
+        let kind = PlayKind::AtBat(AtBatResults::PossibleError);
+        state = record_play(&state, after, kind, Some(&home[0]), &15);
+
+        let export = export_game(&game, &state, (2024, 4, 1), 0);
+        let lines: Vec<&str> = export.lines().collect();
+        let code = |player: &Player| player.id.clone();
+        assert_eq!(lines[0], "id,RED202404010");
+        assert!(lines.contains(&"info,date,2024/04/01"));
+        assert!(lines.contains(&"info,visteam,BLU"));
+        // 9 starters a side, pitcher bats 9th
+        assert_eq!(lines.iter().filter(|l| l.starts_with("start,")).count(), 18);
+        assert!(lines.contains(
+            &format!(
+                "start,{},\"{} {}\",0,9,1",
+                code(&away[8]),
+                away[8].first_name,
+                away[8].last_name
+            )
+            .as_str()
+        ));
+        let plays: Vec<&str> = lines
+            .iter()
+            .filter(|l| l.starts_with("play,"))
+            .map(|l| l.rsplit(',').next().unwrap())
+            .collect();
+        assert_eq!(
+            plays,
+            ["S", "HR.1-H", "W", "SB2", "8/F.2-3", "K", "FC.3XH", "E6"]
+        );
+        // the steal is credited to whoever was up
+        assert!(lines.contains(&format!("play,1,0,{},??,,SB2", code(&away[3])).as_str()));
+        let bullpen = &game.away_active.bullpen[0];
+        let sub = lines.iter().position(|l| l.starts_with("sub,")).unwrap();
+        assert_eq!(
+            lines[sub],
+            format!(
+                "sub,{},\"{} {}\",0,9,1",
+                code(bullpen),
+                bullpen.first_name,
+                bullpen.last_name
+            )
+        );
+        assert!(lines[sub + 1].starts_with("play,1,1,"));
+        assert!(
+            lines.contains(&format!("data,er,{},2", code(&game.home_active.pitching[0])).as_str())
+        );
+
+        // a second game on the same day makes a doubleheader, games 1 and 2
+        let filename = std::env::temp_dir().join("deadball_retrosheet_test.EVN");
+        _ = fs::remove_file(&filename);
+        let filename = filename.to_string_lossy().to_string();
+        let first = write_event_file(&game, &state, &filename).unwrap();
+        assert_eq!(first.len(), 12);
+        assert!(first.ends_with('0'));
+        let second = write_event_file(&game, &state, &filename).unwrap();
+        assert!(second.ends_with('2'));
+        let contents = fs::read_to_string(&filename).unwrap();
+        let ids: Vec<&str> = contents.lines().filter(|l| l.starts_with("id,")).collect();
+        assert_eq!(
+            ids,
+            [format!("id,{}1", &first[..11]), format!("id,{}", second)]
+        );
+        assert_eq!(
+            contents
+                .lines()
+                .filter(|l| l.starts_with("info,number,"))
+                .collect::<Vec<_>>(),
+            ["info,number,1", "info,number,2"]
+        );
+    }
+
+    #[test]
+    fn test_simulation() {
+        use crate::core::simulation::*;
+
+        let game = test_game(true);
+
+        // tied after 9 on total runs keeps going even if the inning lines differ
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Ongoing;
+        state.inning = 10;
+        for team in [&mut state.home_state, &mut state.away_state] {
+            team.runs = vec![0; 10];
+            team.hits = vec![0; 10];
+            team.errors = vec![0; 10];
+        }
+        state.home_state.runs[0] = 1;
+        state.away_state.runs[1] = 1;
+        let state = modern_game_flow(&game, state, &mut DebugConfig::default());
+        assert_eq!(state.status, GameStatus::Ongoing);
+
+        // a finished 3-1 home win, then a game that never finished
+        let mut state = init_new_game_state(
+            game.home_active.pitching[0].clone(),
+            game.away_active.pitching[0].clone(),
+        );
+        state.status = GameStatus::Over;
+        state.home_state.runs = vec![2, 1];
+        state.away_state.runs = vec![0, 1];
+        state.home_state.hits = vec![4, 2];
+        state.away_state.hits = vec![1, 1];
+        let mut report = SimulationReport::default();
+        report.record(&state);
+        state.status = GameStatus::Ongoing;
+        report.record(&state);
+        assert_eq!(report.games, 2);
+        assert_eq!(report.home.wins, 1);
+        assert_eq!(report.away.wins, 0);
+        assert_eq!(report.ties, 1);
+        assert_eq!(report.home.runs[&3], 2);
+        assert_eq!(report.home_win_pct(), 0.5);
+        assert_eq!(report.means(true), (3.0, 6.0, 0.0));
+
+        let mut combined = report.clone();
+        combined.add(&report);
+        assert_eq!(combined.games, 4);
+        assert_eq!(combined.home.hits, report.home.hits * 2);
+
+        // a batch of whole games
+        let report = simulate_games(&game, 25, 3);
+        assert_eq!(report.games, 25);
+        assert_eq!(report.home.wins + report.away.wins + report.ties, 25);
+        assert_eq!(report.home.runs.values().sum::<u32>(), 25);
+        assert_eq!(report.away.runs.values().sum::<u32>(), 25);
+        assert!((report.home_win_pct() + report.away_win_pct()) <= 1.0);
+        // at least 27 outs a side, so plenty of swings get logged
+        assert!(report.at_bats.iter().sum::<u32>() >= 25 * 51);
+        let total: f32 = report.at_bat_frequencies().iter().map(|(_, f)| f).sum();
+        assert!((total - 1.0).abs() < 0.001);
+        let (runs, hits, _) = report.means(true);
+        assert!(runs >= 0.0 && hits > 0.0);
+    }
+
+    #[test]
+    fn test_calibration() {
+        use crate::core::calibration::*;
+
+        let input = |bt, obt, pitch_die, oddity, power_mod, defense_mod| CalibrationInput {
+            bat_target: bt,
+            on_base_target: obt,
+            pitch_die,
+            oddity,
+            power_mod,
+            defense_mod,
+        };
+        // rule changes show up as a diff against these, run with UPDATE_GOLDEN=1 to accept them
+        for (file, input) in [
+            ("bt32_obt40_d12.txt", input(32, 40, 12, false, 0, 0)),
+            ("bt25_obt31_-d4_oddity.txt", input(25, 31, -4, true, -1, -1)),
+            ("bt40_obt46_d20_oddity.txt", input(40, 46, 20, true, 2, 1)),
+        ] {
+            let calibration = calibrate(&input);
+            for table in [
+                &calibration.swings[..],
+                &calibration.hits[..],
+                &calibration.crit_hits[..],
+                &calibration.defense[..],
+            ] {
+                assert!((table.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            }
+            let report = calibration_report(&calibration);
+            let path = format!("src/testfiles/calibration/{}", file);
+            if std::env::var("UPDATE_GOLDEN").is_ok() {
+                fs::write(&path, &report).unwrap();
+            }
+            assert_eq!(
+                report,
+                fs::read_to_string(&path).unwrap(),
+                "{} changed",
+                path
+            );
+        }
+
+        // the game's dice should land close to the exact odds
+        let input = input(32, 40, 12, true, 0, 0);
+        let exact = calibrate(&input).swings;
+        let sampled = sample_swings(&input, 20000);
+        for (exact, sampled) in exact.iter().zip(sampled.iter()) {
+            assert!((exact - sampled).abs() < 0.015, "{} vs {}", exact, sampled);
+        }
+    }
+
+    #[test]
+    fn test_invariants() {
+        use crate::core::invariants::check_invariants;
+
+        let pitcher = Player::default();
+        let mut state = init_new_game_state(pitcher.clone(), pitcher.clone());
+        assert!(check_invariants(&state).is_empty());
+
+        // the same player can't be on two bases
+        state.bases.place(1, pitcher.clone());
+        state.bases.place(3, pitcher.clone());
+        assert_eq!(check_invariants(&state).len(), 1);
+        state.bases.remove(3);
+        assert!(check_invariants(&state).is_empty());
+
+        // away's line should already have the next inning during the bottom half
+        state.inning_half = InningTB::Bottom;
+        state.home_state.current_batter = 9;
+        let broken = check_invariants(&state);
+        assert_eq!(broken.len(), 4);
+        assert!(broken
+            .iter()
+            .any(|message| message.contains("batter index")));
+    }
+
+    #[test]
+    fn test_replay() {
+        use crate::core::actions::Action;
+        use crate::core::replay::{load_record, replay, write_record, GameRecord};
+
+        let mut game = test_game(true);
+        game.quiet = true;
+
+        // play a game with every kind of decision in it
+        let mut record = GameRecord::new(&game, 1234);
+        let mut state = record.start();
+        let mut debug = DebugConfig::default();
+        let mut actions = 0;
+        while state.status != GameStatus::Over && actions < 5000 {
+            let occupancy = state.bases.occupancy();
+            let action = match actions % 7 {
+                _ if state.outs == Outs::Three => Action::Swing,
+                4 if occupancy == RunnersOn::Runner100 => Action::Steal(StealType::Second),
+                4 if occupancy == RunnersOn::Runner010 => Action::Steal(StealType::Third),
+                5 if occupancy != RunnersOn::Runner000 => Action::Bunt,
+                6 if occupancy == RunnersOn::Runner100 => Action::HitAndRun,
+                _ if actions == 30 => {
+                    let mut team = game.home_active.clone();
+                    std::mem::swap(&mut team.pitching[0], &mut team.bullpen[0]);
+                    Action::Substitution { home: true, team }
+                }
+                _ => Action::Swing,
+            };
+            state = record.play(&mut game, state, action, &mut debug).unwrap();
+            actions += 1;
+        }
+        assert_eq!(state.status, GameStatus::Over);
+        assert!(record.decisions.len() > 1);
+        assert!(!record.edited);
+
+        // replaying rolls the same dice and ends up in the same place
+        let steps = replay(&record);
+        assert_eq!(steps.len(), actions + 1);
+        let last = &steps.last().unwrap().state;
+        assert_eq!(format!("{:?}", last), format!("{:?}", state));
+
+        // and so does a saved record
+        let folder = std::env::temp_dir().join(format!("deadball_records_{}", roll(100000)));
+        let path = write_record(&record, &folder.to_string_lossy()).unwrap();
+        let loaded = load_record(&path.to_string_lossy()).unwrap();
+        let steps = replay(&loaded);
+        let last = &steps.last().unwrap().state;
+        assert_eq!(format!("{:?}", last), format!("{:?}", state));
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_undo_history() {
+        use crate::core::actions::Action;
+        use crate::core::replay::{replay, GameRecord};
+        use crate::gui::gui_functions::{
+            play_action, record_finished_game, redo_play, undo_play, UNDO_LIMIT,
+        };
+
+        let mut game = test_game(true);
+        game.quiet = true;
+        let mut app = DeadballApp::default();
+        // the finished game's stats get saved
+        let folder = std::env::temp_dir().join(format!("deadball_undo_{}", roll(100000)));
+        fs::create_dir_all(&folder).unwrap();
+        app.settings.data_dir = folder.display().to_string();
+        let record = GameRecord::new(&game, 99);
+        app.game_state = Some(record.start());
+        app.game_record = Some(record);
+        app.game_modern = Some(game);
+
+        // nothing to undo yet
+        assert!(!undo_play(&mut app).unwrap());
+        let start = format!("{:?}", app.game_state);
+        play_action(&mut app, Action::Swing).unwrap();
+        play_action(&mut app, Action::Swing).unwrap();
+        let after = format!("{:?}", app.game_state);
+
+        // back to the first pitch, then forward again to the same place
+        assert!(undo_play(&mut app).unwrap());
+        assert!(undo_play(&mut app).unwrap());
+        assert!(!undo_play(&mut app).unwrap());
+        assert_eq!(format!("{:?}", app.game_state), start);
+        assert_eq!(app.game_record.as_ref().unwrap().at_bats, 0);
+        assert!(redo_play(&mut app).unwrap());
+        assert!(redo_play(&mut app).unwrap());
+        assert!(!redo_play(&mut app).unwrap());
+        assert_eq!(format!("{:?}", app.game_state), after);
+
+        // a new play after an undo throws the redo away, the record still replays
+        assert!(undo_play(&mut app).unwrap());
+        let mut team = app.game_modern.as_ref().unwrap().home_active.clone();
+        std::mem::swap(&mut team.pitching[0], &mut team.bullpen[0]);
+        play_action(&mut app, Action::Substitution { home: true, team }).unwrap();
+        assert!(!redo_play(&mut app).unwrap());
+        play_action(&mut app, Action::Swing).unwrap();
+        let record = app.game_record.as_ref().unwrap();
+        assert_eq!(record.at_bats, 2);
+        assert_eq!(record.decisions.len(), 1);
+        let steps = replay(record);
+        let last = &steps.last().unwrap().state;
+        assert_eq!(
+            format!("{:?}", last),
+            format!("{:?}", app.game_state.as_ref().unwrap())
+        );
+
+        // substitutions come back out with an undo
+        let pitcher = app.game_modern.as_ref().unwrap().home_active.pitching[0].clone();
+        assert!(undo_play(&mut app).unwrap());
+        assert!(undo_play(&mut app).unwrap());
+        assert_ne!(
+            app.game_modern.as_ref().unwrap().home_active.pitching[0],
+            pitcher
+        );
+
+        // history is capped
+        let team = app.game_modern.as_ref().unwrap().home_active.clone();
+        for _ in 0..UNDO_LIMIT + 10 {
+            let team = team.clone();
+            play_action(&mut app, Action::Substitution { home: true, team }).unwrap();
+        }
+        let mut undone = 0;
+        while undo_play(&mut app).unwrap() {
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_LIMIT);
+
+        // the last play of the game goes into the stats once, undoing it takes the game back out
+        let play_out = |app: &mut DeadballApp| {
+            let mut swings = 0;
+            while app.game_state.as_ref().unwrap().status != GameStatus::Over && swings < 5000 {
+                play_action(app, Action::Swing).unwrap();
+                swings += 1;
+            }
+        };
+        play_out(&mut app);
+        assert!(app.stats_before_game.is_some());
+        assert!(!record_finished_game(&mut app));
+        let game = app.game_modern.clone().unwrap();
+        let season = app.stats_window.season;
+        let (home, away) = ((game.home.name, season), (game.away.name, season));
+        assert_eq!(app.stats.teams[&home].games, 1);
+        assert!(undo_play(&mut app).unwrap());
+        assert!(app.stats_before_game.is_none());
+        assert!(!app.stats.teams.contains_key(&home));
+        let saved = load_stats(&app.settings.stats_file()).unwrap();
+        assert!(!saved.teams.contains_key(&home));
+        assert!(redo_play(&mut app).unwrap());
+        assert_eq!(app.stats.teams[&home].games, 1);
+
+        // a different ending replaces the undone one
+        assert!(undo_play(&mut app).unwrap());
+        app.game_state.as_mut().unwrap().away_state.runs[0] += 5;
+        play_out(&mut app);
+        let state = app.game_state.as_ref().unwrap();
+        assert_eq!(state.status, GameStatus::Over);
+        let saved = load_stats(&app.settings.stats_file()).unwrap();
+        for stats in [&app.stats, &saved] {
+            assert_eq!(stats.teams[&home].games, 1);
+            assert_eq!(
+                stats.teams[&away].runs_scored,
+                state.away_state.runs.iter().sum::<u32>()
+            );
+        }
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_physical_dice() {
+        use crate::core::actions::{apply_action, Action};
+        use crate::core::replay::{replay, GameRecord};
+        use crate::gui::debug::RollRequest;
+        use crate::gui::gui_functions::{enter_physical_roll, play_action};
+
+        let mut game = test_game(true);
+        game.quiet = true;
+        let mut app = DeadballApp::default();
+        let record = GameRecord::new(&game, 7);
+        let start = record.start();
+        app.game_state = Some(start.clone());
+        app.game_record = Some(record);
+        app.game_modern = Some(game.clone());
+        app.physical_dice.enabled = true;
+
+        // the swing waits on the pitch die first
+        play_action(&mut app, Action::Swing).unwrap();
+        let request = app.physical_dice.request.clone().unwrap();
+        assert_eq!(request.purpose, "pitch");
+        assert_eq!(
+            format!("{:?}", app.game_state),
+            format!("{:?}", Some(&start))
+        );
+        assert!(enter_physical_roll(&mut app, request.side + 1).is_err());
+
+        // then the swing roll and whatever it leads to
+        assert_eq!(enter_physical_roll(&mut app, 1), Ok(false));
+        assert_eq!(
+            app.physical_dice.request,
+            Some(RollRequest {
+                side: 100,
+                purpose: "swing".to_string()
+            })
+        );
+        let mut rolls = vec![1, 60];
+        let mut done = enter_physical_roll(&mut app, 60).unwrap();
+        while !done {
+            rolls.push(2);
+            done = enter_physical_roll(&mut app, 2).unwrap();
+        }
+        assert!(app.physical_dice.pending.is_none());
+
+        // same result as queuing the rolls up front
+        let mut debug = DebugConfig {
+            mode: true,
+            rolls: rolls.clone(),
+            ..Default::default()
+        };
+        let expected = apply_action(&mut game, start, &Action::Swing, &mut debug).unwrap();
+        assert_eq!(
+            format!("{:?}", app.game_state.as_ref().unwrap()),
+            format!("{:?}", expected)
+        );
+
+        // the rolls are kept with the swing, so the game can be replayed
+        let record = app.game_record.as_ref().unwrap();
+        assert!(!record.edited);
+        assert_eq!(record.decisions.len(), 1);
+        assert_eq!(record.decisions[0].rolls, rolls);
+
+        // a game played out with physical dice goes into the stats and replays the same
+        let folder = std::env::temp_dir().join(format!("deadball_physical_{}", roll(100000)));
+        fs::create_dir_all(&folder).unwrap();
+        app.settings.data_dir = folder.display().to_string();
+        let mut plays = 0;
+        while app.game_state.as_ref().unwrap().status != GameStatus::Over && plays < 5000 {
+            play_action(&mut app, Action::Swing).unwrap();
+            while let Some(request) = app.physical_dice.request.clone() {
+                enter_physical_roll(&mut app, roll(request.side)).unwrap();
+            }
+            plays += 1;
+        }
+        assert!(app.stats_before_game.is_some());
+        let home = game.home.name.clone();
+        assert_eq!(app.stats.teams[&(home, app.stats_window.season)].games, 1);
+        let steps = replay(app.game_record.as_ref().unwrap());
+        assert_eq!(
+            format!("{:?}", steps.last().unwrap().state),
+            format!("{:?}", app.game_state.as_ref().unwrap())
+        );
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    // the choices a player can make from the game menu, whatever the rules allow right now
+    fn test_choice(state: &GameState, game: &GameModern, choice: u8) -> GameState {
+        use crate::core::actions::{apply_action, Action, ActionSide};
+
+        let legal: Vec<Action> = state
+            .action_options(game)
+            .into_iter()
+            .filter(|option| option.legal && option.side == ActionSide::Offense)
+            .map(|option| option.action)
+            .collect();
+        let steals: Vec<&Action> = legal
+            .iter()
+            .filter(|action| matches!(action, Action::Steal(_)))
+            .collect();
+        let action = match choice {
+            6..=9 if !steals.is_empty() => steals[(choice - 6) as usize % steals.len()].clone(),
+            10 | 11 => legal
+                .iter()
+                .find(|action| match action {
+                    Action::Bunt => choice == 10,
+                    Action::HitAndRun => choice == 11,
+                    _ => false,
+                })
+                .cloned()
+                .unwrap_or(Action::Swing),
+            _ => Action::Swing,
+        };
+        let mut game = game.clone();
+        apply_action(
+            &mut game,
+            state.clone(),
+            &action,
+            &mut DebugConfig::default(),
+        )
+        .unwrap()
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(48))]
+
+        // random choices through whole games, the state has to stay consistent after every step
+        #[test]
+        fn test_game_invariants(
+            oddity: bool,
+            choices in proptest::collection::vec(0..12u8, 1..400),
+        ) {
+            use crate::core::invariants::check_invariants;
+            use crate::core::simulation::MAX_GAME_STEPS;
+
+            let mut game = test_game(oddity);
+            game.quiet = true;
+            let mut state = init_new_game_state(
+                game.home_active.pitching[0].clone(),
+                game.away_active.pitching[0].clone(),
+            );
+
+            // make the choices while the game lasts, then swing away until it's over
+            let choices = choices.into_iter().chain(std::iter::repeat(0));
+            for (step, choice) in choices.take(MAX_GAME_STEPS as usize).enumerate() {
+                if state.status == GameStatus::Over {
+                    break;
+                }
+                state = if state.status == GameStatus::Ongoing && state.outs != Outs::Three {
+                    test_choice(&state, &game, choice)
+                } else {
+                    modern_game_flow(&game, state, &mut DebugConfig::default())
+                };
+                let broken = check_invariants(&state);
+                proptest::prop_assert!(broken.is_empty(), "step {}: {:?}", step, broken);
+            }
+
+            proptest::prop_assert_eq!(&state.status, &GameStatus::Over);
+            proptest::prop_assert!(state.inning >= 9);
+            let home_runs = state.home_state.runs.iter().sum::<u32>();
+            let away_runs = state.away_state.runs.iter().sum::<u32>();
+            proptest::prop_assert_ne!(home_runs, away_runs);
+        }
+    }
 }